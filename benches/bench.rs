@@ -1,7 +1,7 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 
 use std::time::Duration;
 
@@ -11,23 +11,17 @@ use ark_ec::{
     AffineRepr, CurveGroup,
 };
 use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::ops::Mul;
 use ark_std::str::FromStr;
 use ark_std::test_rng;
 
-use groth_sahai::{
-    prover::{
-        batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
-        CProof, Commit1, Commit2, Provable,
-    },
-    statement::PPE,
-    verifier::Verifiable,
-    AbstractCrs, Com1, Mat, Matrix, B1, CRS,
-};
+use groth_sahai::data_structures::{Mat, MillerAccumulator};
+use groth_sahai::prelude::*;
 
 type G1Projective = <F as Pairing>::G1;
 type G1Affine = <F as Pairing>::G1Affine;
-//type G2Projective = <F as PairingEngine>::G2Projective;
+type G2Projective = <F as Pairing>::G2;
 type G2Affine = <F as Pairing>::G2Affine;
 type GT = PairingOutput<F>;
 type Fr = <F as Pairing>::ScalarField;
@@ -194,6 +188,43 @@ pub fn bench_small_B1_matrix_mul_par(c: &mut Criterion) {
     });
 }
 
+// Every entry of the product is an inner product of length 128, computed as one MSM per coordinate
+// (rather than 128 separate scalar multiplications), and the 128 x 128 result is normalized to
+// affine with a single batch inversion.
+pub fn bench_large_B1_matrix_mul_par(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let g1gen = G1Projective::rand(&mut rng).into_affine();
+
+    let dim = 128;
+    let mut rhs: Matrix<Com1<F>> = Vec::with_capacity(dim);
+    let mut lhs: Matrix<Fr> = Vec::with_capacity(dim);
+    for _ in 0..dim {
+        let mut com_row = Vec::with_capacity(dim);
+        let mut scalar_row = Vec::with_capacity(dim);
+        for _ in 0..dim {
+            com_row.push(Com1::<F>(
+                affine_group_rand!(g1gen, rng),
+                affine_group_rand!(g1gen, rng),
+            ));
+            scalar_row.push(Fr::rand(&mut rng));
+        }
+        rhs.push(com_row);
+        lhs.push(scalar_row);
+    }
+    c.bench_function(
+        &format!(
+            "concurrent ({} x {}) Fp * ({} x {}) B1 matrix mult",
+            dim, dim, dim, dim
+        ),
+        |bench| {
+            bench.iter(|| {
+                let _ = rhs.left_mul(&lhs, true);
+            });
+        },
+    );
+}
+
 fn bench_B1_scalar_mul(c: &mut Criterion) {
     std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
     let mut rng = test_rng();
@@ -575,6 +606,57 @@ fn bench_large_PPE_verify(c: &mut Criterion) {
             });
         },
     );
+    // The clones of the equation and the proof are made in the setup, outside of the measurement
+    c.bench_function(
+        &format!(
+            "verify (consuming) PPE equation with {} G1 vars, {} G2 var",
+            m, n
+        ),
+        |bench| {
+            bench.iter_batched(
+                || (equ.clone(), proof.clone()),
+                |(equ, proof)| equ.verify_consuming(proof, &crs),
+                BatchSize::LargeInput,
+            );
+        },
+    );
+}
+
+fn bench_pairing_sum_256(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let g1gen = G1Projective::rand(&mut rng).into_affine();
+    let g2gen = G2Projective::rand(&mut rng).into_affine();
+
+    let n = 256;
+    let mut g1s: Vec<G1Affine> = Vec::with_capacity(n);
+    let mut g2s: Vec<G2Affine> = Vec::with_capacity(n);
+    for _ in 0..n {
+        g1s.push(affine_group_rand!(g1gen, rng));
+        g2s.push(affine_group_rand!(g2gen, rng));
+    }
+
+    c.bench_function(&format!("sum of {} individual pairings", n), |bench| {
+        bench.iter(|| {
+            let _: GT = g1s
+                .iter()
+                .zip(g2s.iter())
+                .map(|(x, y)| F::pairing(*x, *y))
+                .sum();
+        });
+    });
+    c.bench_function(
+        &format!("accumulate {} pairings with MillerAccumulator", n),
+        |bench| {
+            bench.iter(|| {
+                let mut acc = MillerAccumulator::<F>::new();
+                for (x, y) in g1s.iter().zip(g2s.iter()) {
+                    acc.add_pair(*x, *y);
+                }
+                let _: GT = acc.finalize();
+            });
+        },
+    );
 }
 
 criterion_group! {
@@ -598,8 +680,39 @@ criterion_group! {
         bench_small_B1_matrix_mul,
         bench_small_B1_matrix_mul_par,
 }
+criterion_group! {
+    name = large_B1_matrix_mul;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::new(60, 0));
+    targets =
+        bench_large_B1_matrix_mul_par
+}
 // operations in G2/B2 are ~4x that of G1/B1, respectively
 
+fn bench_load_commitments(c: &mut Criterion) {
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let m = 10_000;
+    let xvars: Vec<G1Affine> = (0..m).map(|_| G1Affine::rand(&mut rng)).collect();
+    let coms = batch_commit_G1(&xvars, &crs, &mut rng).coms;
+    let mut compressed = Vec::new();
+    coms.serialize_compressed(&mut compressed).unwrap();
+    let mut uncompressed = Vec::new();
+    coms.serialize_uncompressed(&mut uncompressed).unwrap();
+
+    c.bench_function(&format!("load {} B1 compressed", m), |bench| {
+        bench.iter(|| Vec::<Com1<F>>::deserialize_compressed(&compressed[..]).unwrap());
+    });
+    c.bench_function(&format!("load {} B1 uncompressed", m), |bench| {
+        bench.iter(|| Vec::<Com1<F>>::deserialize_uncompressed(&uncompressed[..]).unwrap());
+    });
+    c.bench_function(&format!("load {} B1 uncompressed unchecked", m), |bench| {
+        bench.iter(|| {
+            Vec::<Com1<F>>::deserialize_uncompressed_unchecked(&uncompressed[..]).unwrap()
+        });
+    });
+}
+
 criterion_group! {
     name = G1_arith;
     config = Criterion::default().sample_size(100);
@@ -613,6 +726,13 @@ criterion_group! {
         bench_B1_scalar_mul
 }
 
+criterion_group! {
+    name = pairing_accumulation;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::new(20, 0));
+    targets =
+        bench_pairing_sum_256
+}
+
 criterion_group! {
     name = small_commit;
     config = Criterion::default().sample_size(50).measurement_time(Duration::new(10, 0));
@@ -633,6 +753,13 @@ criterion_group! {
         bench_large_batch_commit_scalar_to_B2
 }
 
+criterion_group! {
+    name = load;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::new(20, 0));
+    targets =
+        bench_load_commitments
+}
+
 criterion_group! {
     name = small_prove;
     config = Criterion::default().sample_size(200);
@@ -663,9 +790,12 @@ criterion_main!(
     //    small_field_matrix_mul,
     //    large_field_matrix_mul,
     //    small_B1_matrix_mul,
+    //    large_B1_matrix_mul,
     //    G1_arith
+    pairing_accumulation,
     small_commit,
     large_commit,
+    load,
     small_prove,
     large_prove,
     small_ver,
@@ -0,0 +1,132 @@
+//! Adapters implementing the [`CommitmentScheme`] trait of `ark-crypto-primitives` (behind the
+//! `ark-crypto-primitives` feature) for commitments to a single group element or scalar, e.g. to use
+//! them in place of Pedersen commitments.
+//!
+//! The trait commits to bytes, so the input is the canonical compressed serialization of the committed
+//! value (i.e. of an `E::G1Affine`, `E::G2Affine` or `E::ScalarField`), which is validated as by
+//! [`deserialize_compressed`](CanonicalDeserialize::deserialize_compressed) and must not have trailing
+//! bytes. The commitments are the same as those of [`commit_G1`](crate::prover::commit_G1),
+//! [`commit_G2`](crate::prover::commit_G2), [`commit_scalar_to_B1`](crate::prover::commit_scalar_to_B1)
+//! and [`commit_scalar_to_B2`](crate::prover::commit_scalar_to_B2) with the given randomness.
+//!
+//! The trait has no way to check an opening, so each adapter also has a `verify` function, which
+//! recomputes the commitment from the input and randomness.
+
+use ark_crypto_primitives::commitment::CommitmentScheme;
+use ark_crypto_primitives::Error;
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::{
+    marker::PhantomData,
+    rand::{
+        distributions::{Distribution, Standard},
+        Rng,
+    },
+};
+
+use crate::data_structures::{Com1, Com2, Matrix};
+use crate::generator::{AbstractCrs, CRS};
+use crate::prover::commit::{
+    commit_G1_with, commit_G2_with, commit_scalar_to_B1_with, commit_scalar_to_B2_with,
+};
+
+/// The randomness `(r_1, r_2)` of a commitment to a group element, i.e. the single row of the `1 x 2`
+/// randomness matrix of [`commit_G1`](crate::prover::commit_G1) or [`commit_G2`](crate::prover::commit_G2).
+///
+/// Sampling it draws `r_1` and then `r_2`, in the same order as `commit_G1` and `commit_G2` do.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GsRandomness<F: Field> {
+    pub r1: F,
+    pub r2: F,
+}
+
+impl<F: Field> Distribution<GsRandomness<F>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GsRandomness<F> {
+        let r1 = F::rand(rng);
+        let r2 = F::rand(rng);
+        GsRandomness { r1, r2 }
+    }
+}
+
+/// The `1 x 2` randomness matrix, e.g. for [`batch_verify_openings_G1`](crate::prover::batch_verify_openings_G1).
+impl<F: Field> From<GsRandomness<F>> for Matrix<F> {
+    fn from(rand: GsRandomness<F>) -> Self {
+        vec![vec![rand.r1, rand.r2]]
+    }
+}
+
+// Decodes the committed value, rejecting trailing bytes
+fn decode<T: CanonicalDeserialize>(input: &[u8]) -> Result<T, Error> {
+    let mut reader = input;
+    let value = T::deserialize_compressed(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(SerializationError::InvalidData.into());
+    }
+    Ok(value)
+}
+
+macro_rules! impl_commitment_scheme {
+    (
+        $(
+            $(#[$doc:meta])*
+            $scheme:ident, $input:ident, $com:ident, $rand:ty, |$x:ident, $key:ident, $r:ident| $commit:expr
+        );*
+    ) => {
+        $(
+            $(#[$doc])*
+            pub struct $scheme<E: Pairing>(PhantomData<E>);
+
+            impl<E: Pairing> CommitmentScheme for $scheme<E> {
+                type Output = $com<E>;
+                type Parameters = CRS<E>;
+                type Randomness = $rand;
+
+                /// Generates a CRS as [`generate_crs`](AbstractCrs::generate_crs) does.
+                fn setup<R: Rng>(rng: &mut R) -> Result<CRS<E>, Error> {
+                    Ok(CRS::<E>::generate_crs(rng))
+                }
+
+                fn commit(
+                    parameters: &CRS<E>,
+                    input: &[u8],
+                    r: &Self::Randomness,
+                ) -> Result<$com<E>, Error> {
+                    let ($x, $key, $r): (E::$input, &CRS<E>, &Self::Randomness) =
+                        (decode(input)?, parameters, r);
+                    Ok($commit)
+                }
+            }
+
+            impl<E: Pairing> $scheme<E> {
+                /// Whether `commitment` opens to `input` with randomness `r`.
+                pub fn verify(
+                    parameters: &CRS<E>,
+                    input: &[u8],
+                    commitment: &$com<E>,
+                    r: &<Self as CommitmentScheme>::Randomness,
+                ) -> Result<bool, Error> {
+                    Ok(Self::commit(parameters, input, r)? == *commitment)
+                }
+            }
+        )*
+    }
+}
+impl_commitment_scheme!(
+    /// Commitments to a [`G1`](ark_ec::pairing::Pairing::G1Affine) element in
+    /// [`B1`](crate::data_structures::Com1), as by [`commit_G1`](crate::prover::commit_G1).
+    GsCommitmentG1, G1Affine, Com1, GsRandomness<E::ScalarField>,
+        |x, key, r| commit_G1_with(&x, key, &r.r1, &r.r2);
+    /// Commitments to a [`G2`](ark_ec::pairing::Pairing::G2Affine) element in
+    /// [`B2`](crate::data_structures::Com2), as by [`commit_G2`](crate::prover::commit_G2).
+    GsCommitmentG2, G2Affine, Com2, GsRandomness<E::ScalarField>,
+        |y, key, s| commit_G2_with(&y, key, &s.r1, &s.r2);
+    /// Commitments to a scalar in [`B1`](crate::data_structures::Com1), as by
+    /// [`commit_scalar_to_B1`](crate::prover::commit_scalar_to_B1), whose randomness is a single scalar.
+    GsCommitmentScalarB1, ScalarField, Com1, E::ScalarField,
+        |x, key, r| commit_scalar_to_B1_with(&x, key, r);
+    /// Commitments to a scalar in [`B2`](crate::data_structures::Com2), as by
+    /// [`commit_scalar_to_B2`](crate::prover::commit_scalar_to_B2), whose randomness is a single scalar.
+    GsCommitmentScalarB2, ScalarField, Com2, E::ScalarField,
+        |y, key, s| commit_scalar_to_B2_with(&y, key, s)
+);
@@ -18,35 +18,509 @@
 //!
 //! The Groth-Sahai proof system uses matrices of commitment group elements in its computations as
 //! well.
+//!
+//! # Serialization
+//!
+//! `Com1` and `Com2`, as well as the [`CRS`](crate::generator::CRS), commitments and proofs built from
+//! them, implement [`CanonicalSerialize`] and [`CanonicalDeserialize`] in both encodings of points:
+//! * Compressed encodings store one coordinate per point, and are half the size of uncompressed ones.
+//!   Decompressing a point takes a square root, which dominates the time to load many commitments.
+//! * Uncompressed encodings store both coordinates, so loading them only checks the curve equation.
+//!
+//! Either way, deserializing also checks that every point is in the prime-order subgroup, which costs
+//! about a scalar multiplication per point. When the bytes come from a trusted source (e.g. local
+//! storage written by the same application), [`deserialize_uncompressed_unchecked`] skips both checks
+//! and is the fastest way to load them. Bytes from anyone else must be validated. In either case, the
+//! lengths and dimensions they claim are bounded before anything is allocated for them, see
+//! [`FORMAT_VERSION`].
+//!
+//! [`deserialize_uncompressed_unchecked`]: CanonicalDeserialize::deserialize_uncompressed_unchecked
 
+#[cfg(feature = "subtle")]
+use ark_ec::short_weierstrass::{Affine as SWAffine, SWCurveConfig};
 use ark_ec::{
     pairing::{Pairing, PairingOutput},
-    AffineRepr, CurveGroup,
+    scalar_mul::BatchMulPreprocessing,
+    AffineRepr, CurveGroup, VariableBaseMSM,
 };
 use ark_ff::{Field, One, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{
-    fmt::Debug,
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
     iter::Sum,
-    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Deref, Mul, Neg, Sub, SubAssign},
+    rand::Rng,
+    UniformRand,
 };
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
 
+// Without the `parallel` feature, or on wasm32 (which has no rayon thread pool) even with it, the
+// parallel iterators fall back to the sequential ones, so that a parallel computation (e.g. with
+// `is_parallel`) runs on the current thread instead
+#[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
+trait IntoSequentialIterator: IntoIterator + Sized {
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoIter {
+        self.into_iter()
+    }
+}
+#[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
+impl<I: IntoIterator> IntoSequentialIterator for I {}
+
 use crate::generator::CRS;
 
 pub trait Mat<Elem: Clone>: Eq + Clone + Debug {
     type Other;
 
+    /// Entry-wise addition of two matrices of the same shape. Use [`zeros`](MatrixExt::zeros) for the
+    /// zero matrix of a given shape, e.g. for the product of empty matrices when there are no
+    /// variables of some kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices have different shapes.
     fn add(&self, other: &Self) -> Self;
+    /// In-place [`add`](Mat::add), i.e. `self += other`, reusing the allocation of `self`.
+    fn add_assign(&mut self, other: &Self);
+    /// In-place `self += k * other`, reusing the allocation of `self` and without allocating `k * other`.
+    /// As in [`add`](Mat::add), the matrices must have the same shape.
+    fn scaled_add_assign(&mut self, k: &Self::Other, other: &Self);
     fn neg(&self) -> Self;
+    /// Negates the matrix in place, without allocating a new one as [`neg`](Mat::neg) does.
+    fn into_neg(self) -> Self;
     fn scalar_mul(&self, other: &Self::Other) -> Self;
+    /// Multiplies every row `i` by `scalars[i]`, i.e. the product `diag(scalars) * self` without
+    /// materializing the diagonal matrix, or returns an error with the expected (column vector)
+    /// shape of `scalars` if it does not have one scalar per row.
+    fn scale_rows(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError>;
+    /// Multiplies every column `j` by `scalars[j]`, i.e. the product `self * diag(scalars)`, or
+    /// returns an error as in [`scale_rows`](Mat::scale_rows) if it does not have one scalar per column.
+    fn scale_cols(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError>;
     fn transpose(&self) -> Self;
+    /// Transposes the matrix by moving its entries, without cloning them as [`transpose`](Mat::transpose) does.
+    fn into_transpose(self) -> Self;
+    /// The matrix product `lhs * self`.
+    ///
+    /// With `is_parallel`, every row (and every entry within it) of the product is computed
+    /// independently on the rayon thread pool; the result is identical to the sequential path.
     fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self;
+    /// The matrix product `self * rhs`, parallelized as in [`left_mul`](Mat::left_mul).
     fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self;
 }
 
+/// Products of a matrix with a vector, computed directly instead of through the column (or row)
+/// matrix of the vector, i.e. without [`vec_to_col_vec`] and [`col_vec_to_vec`].
+///
+/// Implemented for field matrices with field vectors, and for matrices of commitment group elements
+/// with scalar vectors. See [`ScalarMatVec`] for scalar matrices with vectors of commitment group elements.
+///
+/// As for [`Mat`], the empty matrix `vec![]` has no rows, so its product with any vector is empty.
+///
+/// # Panics
+///
+/// Panics if the length of `v` does not match the number of columns (for [`mul_vec`](MatVec::mul_vec))
+/// or rows (for [`vec_mul`](MatVec::vec_mul)) of the matrix.
+pub trait MatVec<V> {
+    type Output;
+
+    /// The product `self * v` with the column vector `v`.
+    fn mul_vec(&self, v: &[V]) -> Vec<Self::Output>;
+    /// The product `v^T * self` with the row vector `v`.
+    fn vec_mul(&self, v: &[V]) -> Vec<Self::Output>;
+}
+
+/// Products of a scalar matrix with a vector of commitment group elements, e.g. `Γ d` for the
+/// commitments `d` to the `y` variables, with the same conventions as [`MatVec`].
+///
+/// These are separate from [`MatVec`] (and named differently), as scalar matrices are already
+/// multiplied with scalar vectors through [`MatVec`].
+pub trait ScalarMatVec<C> {
+    /// The product `self * v` with the column vector `v`.
+    fn mul_com_vec(&self, v: &[C]) -> Vec<C>;
+    /// The product `v^T * self` with the row vector `v`.
+    fn com_vec_mul(&self, v: &[C]) -> Vec<C>;
+}
+
+/// Inversion and linear solving for square scalar matrices, e.g. relating the commitment keys to the
+/// generators when extracting or simulating.
+///
+/// These use Gauss-Jordan elimination, which takes `O(n^3)` field operations for an `n x n` matrix
+/// (in particular only a handful for the `2 x 2` matrices of the SXDH instantiation). The empty matrix
+/// `vec![]` is the `0 x 0` identity, so its determinant is one and it is its own inverse.
+///
+/// Each method returns a [`ShapeError`] if the matrix is not square.
+pub trait SquareMat<F>: Sized {
+    /// The determinant of the matrix.
+    fn determinant(&self) -> Result<F, ShapeError>;
+    /// The inverse of the matrix, or `None` if it is singular.
+    fn inverse(&self) -> Result<Option<Self>, ShapeError>;
+    /// The unique `x` such that `self * x = rhs`, or `None` if the matrix is singular. Also returns a
+    /// [`ShapeError`] (expecting an `n x 1` column) if `rhs` does not have one entry per row.
+    fn solve(&self, rhs: &[F]) -> Result<Option<Vec<F>>, ShapeError>;
+}
+
+// Checks that `v` can be multiplied with every row of `mat`
+fn assert_mul_vec_shape<F, V>(mat: &Matrix<F>, v: &[V]) {
+    for row in mat.iter() {
+        assert!(
+            row.len() == v.len(),
+            "cannot multiply a {} x {} matrix by a vector of length {}",
+            mat.len(),
+            row.len(),
+            v.len()
+        );
+    }
+}
+
+// Checks that every column of `mat` can be multiplied with `v`
+fn assert_vec_mul_shape<F, V>(mat: &Matrix<F>, v: &[V]) {
+    assert!(
+        mat.len() == v.len(),
+        "cannot multiply a vector of length {} by a {} x {} matrix",
+        v.len(),
+        mat.len(),
+        mat.num_cols()
+    );
+    let n = mat.num_cols();
+    assert!(
+        mat.iter().all(|row| row.len() == n),
+        "cannot multiply by a matrix with rows of different lengths"
+    );
+}
+
+// Checks that the product `lhs * rhs` of rectangular matrices is defined, since a ragged row would
+// otherwise silently truncate the MSM of an entry of the product
+fn assert_mat_mul_shape<L, R>(lhs: &Matrix<L>, rhs: &Matrix<R>) {
+    let (k, n) = (lhs.num_cols(), rhs.num_cols());
+    assert!(
+        lhs.iter().all(|row| row.len() == k) && rhs.iter().all(|row| row.len() == n),
+        "cannot multiply by a matrix with rows of different lengths"
+    );
+    assert!(
+        k == rhs.len(),
+        "cannot multiply a {} x {} matrix by a {} x {} matrix",
+        lhs.len(),
+        k,
+        rhs.len(),
+        n
+    );
+}
+
+/// A row-major matrix, represented as a vector of rows.
+///
+/// As `Matrix` is an alias of the foreign type `Vec<Vec<E>>`, the `std::ops` operators (e.g. `&a + &b`
+/// or `-&a`) cannot be implemented for it. Matrix arithmetic is instead provided by the
+/// [`Mat`](self::Mat) trait, e.g. `a.add(&b)` and `a.neg()`. Matrices of scalars can be wrapped in a
+/// [`FieldMatrix`](self::FieldMatrix) to use the operators instead.
+///
+/// Likewise, `==` is the structural equality of the vectors of rows: the empty matrix `vec![]` and a
+/// matrix of rows without entries (e.g. `vec![vec![]; 3]`) are unequal, and (de)serialization preserves
+/// which of the two a matrix is. Use [`eq_entries`](MatrixExt::eq_entries) to treat all matrices
+/// without entries as equal.
 pub type Matrix<E> = Vec<Vec<E>>;
 
+/// Structural helpers for a [`Matrix`](self::Matrix) that do not depend on the type of its entries.
+///
+/// Rows and columns are borrowed rather than cloned. Out-of-range rows and columns panic like indexing
+/// does, while [`entry`](MatrixExt::entry) is the non-panicking lookup of a single entry (it is not named
+/// `get` so as not to shadow [`slice::get`] on the underlying vector of rows).
+pub trait MatrixExt<F> {
+    /// Applies `f` to every entry, preserving the shape of the matrix.
+    fn map<G, Fun: Fn(&F) -> G>(&self, f: Fun) -> Matrix<G>;
+    /// Builds an `r x c` matrix whose `(i, j)`-th entry is `f(i, j)`, calling `f` in row-major order.
+    ///
+    /// Call it as `MatrixExt::from_fn(..)`, since `Vec` may gain an inherent `from_fn` of its own.
+    fn from_fn<Fun: FnMut(usize, usize) -> F>(shape: (usize, usize), f: Fun) -> Self;
+    /// The `rows x cols` matrix of zeros, e.g. as the sum of no products in [`Mat::add`].
+    fn zeros(shape: (usize, usize)) -> Self
+    where
+        F: Zero,
+        Self: Sized,
+    {
+        Self::from_fn(shape, |_, _| F::zero())
+    }
+    /// Samples an `rows x cols` matrix in which each entry is, independently with probability `density`,
+    /// a uniformly random element and otherwise zero. Entries are sampled in row-major order.
+    ///
+    /// A `density` of `1.0` consumes the same randomness as sampling every entry with
+    /// [`UniformRand::rand`], and so yields the same matrix for the same `rng`.
+    ///
+    /// # Panics
+    ///
+    /// If `density` is not in `[0, 1]`.
+    fn rand_sparse<R: Rng + ?Sized>(rng: &mut R, rows: usize, cols: usize, density: f64) -> Self
+    where
+        F: UniformRand + Zero;
+    /// The fraction of entries that are non-zero, or `0.0` for a matrix without entries.
+    fn density(&self) -> f64
+    where
+        F: Zero + PartialEq;
+    /// Validates a vector of rows as a matrix, i.e. that there is at least one row and that all rows
+    /// have the same length.
+    fn try_from_vecs(vecs: Vec<Vec<F>>) -> Result<Self, MatrixError>
+    where
+        Self: Sized;
+    /// Validates a vector of rows as a matrix (see [`try_from_vecs`](MatrixExt::try_from_vecs)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vecs` is empty or its rows have different lengths.
+    fn from_vecs(vecs: Vec<Vec<F>>) -> Self
+    where
+        Self: Sized,
+    {
+        match Self::try_from_vecs(vecs) {
+            Ok(mat) => mat,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    /// Builds a matrix from its rows in row-major order, e.g. `Matrix::<Fr>::from_rows(vec![vec![..], ..])`.
+    ///
+    /// An alias of [`from_vecs`](MatrixExt::from_vecs), so the rows are validated in the same way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is empty or its rows have different lengths.
+    fn from_rows(rows: Vec<Vec<F>>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_vecs(rows)
+    }
+    /// Combines the entries of two matrices of the same shape with `f`, or returns an error
+    /// (with the shape of `self` as the expected shape) if their shapes differ.
+    fn zip_with<G, T, Fun: Fn(&F, &G) -> T>(
+        &self,
+        other: &Matrix<G>,
+        f: Fun,
+    ) -> Result<Matrix<T>, ShapeError>;
+
+    /// The number of columns, taken from the first row (`0` for an empty matrix).
+    fn num_cols(&self) -> usize;
+    /// Whether the matrix has no entries, i.e. it has no rows or only rows without entries.
+    ///
+    /// Unlike [`Vec::is_empty`], which only checks for the absence of rows.
+    fn is_empty_matrix(&self) -> bool;
+    /// Equality up to the shape of matrices without entries: two matrices are equal if neither has any
+    /// entry (see [`is_empty_matrix`](MatrixExt::is_empty_matrix)), and structurally equal otherwise.
+    fn eq_entries(&self, other: &Matrix<F>) -> bool
+    where
+        F: PartialEq;
+    /// The entry at `(i, j)`, or `None` if it is out of range.
+    fn entry(&self, idx: (usize, usize)) -> Option<&F>;
+    /// The `i`-th row.
+    fn row(&self, i: usize) -> &[F];
+    /// The `j`-th column, from top to bottom.
+    fn col<'a>(&'a self, j: usize) -> impl Iterator<Item = &'a F> + 'a
+    where
+        F: 'a;
+    fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [F]> + 'a
+    where
+        F: 'a;
+    fn cols<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = &'a F> + 'a> + 'a
+    where
+        F: 'a;
+    /// A read-only view of the transpose, borrowing the entries rather than cloning them as
+    /// [`transpose`](Mat::transpose) does.
+    fn transposed_view(&self) -> MatrixView<'_, F>;
+    /// Swaps the `i`-th and `j`-th rows in place.
+    fn swap_rows(&mut self, i: usize, j: usize);
+    /// Swaps the `i`-th and `j`-th columns in place.
+    fn swap_cols(&mut self, i: usize, j: usize);
+    /// Reorders the rows, so that the `i`-th row is the `perm[i]`-th row of `self`, or returns an error
+    /// if `perm` is not a permutation of the row indices.
+    ///
+    /// This is how the rows of e.g. `Γ` follow a reordering of the `x` variables (see
+    /// [`Commit1::permute`](crate::prover::Commit1::permute)).
+    fn permute_rows(&self, perm: &[usize]) -> Result<Self, PermError>
+    where
+        Self: Sized,
+        F: Clone;
+    /// Reorders the columns, so that the `j`-th column is the `perm[j]`-th column of `self`, or returns
+    /// an error if `perm` is not a permutation of the column indices.
+    fn permute_cols(&self, perm: &[usize]) -> Result<Self, PermError>
+    where
+        Self: Sized,
+        F: Clone;
+}
+
+impl<F> MatrixExt<F> for Matrix<F> {
+    fn map<G, Fun: Fn(&F) -> G>(&self, f: Fun) -> Matrix<G> {
+        self.iter()
+            .map(|row| row.iter().map(&f).collect())
+            .collect()
+    }
+
+    fn from_fn<Fun: FnMut(usize, usize) -> F>(shape: (usize, usize), mut f: Fun) -> Self {
+        (0..shape.0)
+            .map(|i| (0..shape.1).map(|j| f(i, j)).collect())
+            .collect()
+    }
+
+    fn rand_sparse<R: Rng + ?Sized>(rng: &mut R, rows: usize, cols: usize, density: f64) -> Self
+    where
+        F: UniformRand + Zero,
+    {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "density must be in [0, 1], found {}",
+            density
+        );
+        MatrixExt::from_fn((rows, cols), |_, _| {
+            if rng.gen_bool(density) {
+                F::rand(rng)
+            } else {
+                F::zero()
+            }
+        })
+    }
+
+    fn density(&self) -> f64
+    where
+        F: Zero + PartialEq,
+    {
+        let total: usize = self.iter().map(|row| row.len()).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let nnz = self.iter().flatten().filter(|x| !x.is_zero()).count();
+        nnz as f64 / total as f64
+    }
+
+    fn try_from_vecs(vecs: Vec<Vec<F>>) -> Result<Self, MatrixError> {
+        let expected = match vecs.first() {
+            Some(row) => row.len(),
+            None => return Err(MatrixError::Empty),
+        };
+        if let Some((row, found)) = vecs
+            .iter()
+            .map(|row| row.len())
+            .enumerate()
+            .find(|(_, len)| *len != expected)
+        {
+            return Err(MatrixError::Ragged {
+                row,
+                expected,
+                found,
+            });
+        }
+        Ok(vecs)
+    }
+
+    fn zip_with<G, T, Fun: Fn(&F, &G) -> T>(
+        &self,
+        other: &Matrix<G>,
+        f: Fun,
+    ) -> Result<Matrix<T>, ShapeError> {
+        if self.len() != other.len()
+            || self
+                .iter()
+                .zip(other.iter())
+                .any(|(lhs, rhs)| lhs.len() != rhs.len())
+        {
+            return Err(ShapeError {
+                expected: (self.len(), self.num_cols()),
+            });
+        }
+        Ok(self
+            .iter()
+            .zip(other.iter())
+            .map(|(lhs, rhs)| lhs.iter().zip(rhs.iter()).map(|(x, y)| f(x, y)).collect())
+            .collect())
+    }
+
+    #[inline]
+    fn num_cols(&self) -> usize {
+        self.first().map_or(0, |row| row.len())
+    }
+
+    #[inline]
+    fn is_empty_matrix(&self) -> bool {
+        self.iter().all(|row| row.is_empty())
+    }
+
+    fn eq_entries(&self, other: &Matrix<F>) -> bool
+    where
+        F: PartialEq,
+    {
+        (self.is_empty_matrix() && other.is_empty_matrix()) || self == other
+    }
+
+    #[inline]
+    fn entry(&self, idx: (usize, usize)) -> Option<&F> {
+        self.as_slice().get(idx.0)?.get(idx.1)
+    }
+
+    #[inline]
+    fn row(&self, i: usize) -> &[F] {
+        &self[i]
+    }
+
+    #[inline]
+    fn col<'a>(&'a self, j: usize) -> impl Iterator<Item = &'a F> + 'a
+    where
+        F: 'a,
+    {
+        self.iter().map(move |row| &row[j])
+    }
+
+    #[inline]
+    fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [F]> + 'a
+    where
+        F: 'a,
+    {
+        self.iter().map(|row| row.as_slice())
+    }
+
+    #[inline]
+    fn cols<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = &'a F> + 'a> + 'a
+    where
+        F: 'a,
+    {
+        (0..self.num_cols()).map(move |j| self.col(j))
+    }
+
+    #[inline]
+    fn transposed_view(&self) -> MatrixView<'_, F> {
+        MatrixView { mat: self }
+    }
+
+    #[inline]
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        self.swap(i, j);
+    }
+
+    fn swap_cols(&mut self, i: usize, j: usize) {
+        for row in self.iter_mut() {
+            row.swap(i, j);
+        }
+    }
+
+    fn permute_rows(&self, perm: &[usize]) -> Result<Self, PermError>
+    where
+        F: Clone,
+    {
+        check_permutation(perm, self.len())?;
+        Ok(perm.iter().map(|&i| self[i].clone()).collect())
+    }
+
+    fn permute_cols(&self, perm: &[usize]) -> Result<Self, PermError>
+    where
+        F: Clone,
+    {
+        check_permutation(perm, self.num_cols())?;
+        Ok(self
+            .iter()
+            .map(|row| perm.iter().map(|&j| row[j].clone()).collect())
+            .collect())
+    }
+}
+
 /// Encapsulates arithmetic traits for Groth-Sahai's bilinear group for commitments.
 pub trait B<E: Pairing>:
     Eq
@@ -76,6 +550,11 @@ pub trait B1<E: Pairing>:
     fn batch_linear_map(x_vec: &[E::G1Affine]) -> Vec<Self>;
     /// The linear map from scalar field to B1 for multi-scalar multiplication and quadratic equations.
     fn scalar_linear_map(x: &E::ScalarField, key: &CRS<E>) -> Self;
+    /// The base `self + (O, P)` of the scalar linear map, which can be computed once and reused with
+    /// [`scalar_linear_map_with_base`](B1::scalar_linear_map_with_base).
+    fn prepare_scalar_base(&self, p: &E::G1Affine) -> Self;
+    /// The linear map from scalar field to B1, given the base prepared by [`prepare_scalar_base`](B1::prepare_scalar_base).
+    fn scalar_linear_map_with_base(base: &Self, x: &E::ScalarField) -> Self;
     fn batch_scalar_linear_map(x_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self>;
 
     fn scalar_mul(&self, other: &E::ScalarField) -> Self;
@@ -94,6 +573,11 @@ pub trait B2<E: Pairing>:
     fn batch_linear_map(y_vec: &[E::G2Affine]) -> Vec<Self>;
     /// The linear map from scalar field to B2 for multi-scalar multiplication and quadratic equations.
     fn scalar_linear_map(y: &E::ScalarField, key: &CRS<E>) -> Self;
+    /// The base `self + (O, P)` of the scalar linear map, which can be computed once and reused with
+    /// [`scalar_linear_map_with_base`](B2::scalar_linear_map_with_base).
+    fn prepare_scalar_base(&self, p: &E::G2Affine) -> Self;
+    /// The linear map from scalar field to B2, given the base prepared by [`prepare_scalar_base`](B2::prepare_scalar_base).
+    fn scalar_linear_map_with_base(base: &Self, y: &E::ScalarField) -> Self;
     fn batch_scalar_linear_map(y_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self>;
 
     fn scalar_mul(&self, other: &E::ScalarField) -> Self;
@@ -107,33 +591,96 @@ pub trait BT<E: Pairing, C1: B1<E>, C2: B2<E>>: B<E> + From<Matrix<PairingOutput
     /// with respect to the bilinear pairing over the bilinear group (G1, G2, GT).
     fn pairing(x: C1, y: C2) -> Self;
     /// The entry-wise sum of bilinear pairings over the GS commitment group.
-    fn pairing_sum(x_vec: &[C1], y_vec: &[C2]) -> Self;
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_vec` and `y_vec` have different lengths (see [`try_pairing_sum`](BT::try_pairing_sum)).
+    fn pairing_sum(x_vec: &[C1], y_vec: &[C2]) -> Self {
+        match Self::try_pairing_sum(x_vec, y_vec) {
+            Ok(sum) => sum,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    /// The entry-wise sum of bilinear pairings over the GS commitment group, or an error if
+    /// `x_vec` and `y_vec` have different lengths.
+    fn try_pairing_sum(x_vec: &[C1], y_vec: &[C2]) -> Result<Self, PairingSumError>;
 
     /// The linear map from GT to BT for pairing-sum equations.
     #[allow(non_snake_case)]
     fn linear_map_PPE(z: &PairingOutput<E>) -> Self;
+    #[allow(non_snake_case)]
+    fn batch_linear_map_PPE(z_vec: &[PairingOutput<E>]) -> Vec<Self>;
     /// The linear map from G1 to BT for multi-scalar multiplication equations.
     #[allow(non_snake_case)]
     fn linear_map_MSMEG1(z: &E::G1Affine, key: &CRS<E>) -> Self;
+    /// The linear map from G1 to BT for multi-scalar multiplication equations, with the
+    /// target-independent factor computed once for all of `z_vec`.
+    #[allow(non_snake_case)]
+    fn batch_linear_map_MSMEG1(z_vec: &[E::G1Affine], key: &CRS<E>) -> Vec<Self>;
     /// The linear map from G2 to BT for multi-scalar multiplication equations.
     #[allow(non_snake_case)]
     fn linear_map_MSMEG2(z: &E::G2Affine, key: &CRS<E>) -> Self;
+    /// The linear map from G2 to BT for multi-scalar multiplication equations, with the
+    /// target-independent factor computed once for all of `z_vec`.
+    #[allow(non_snake_case)]
+    fn batch_linear_map_MSMEG2(z_vec: &[E::G2Affine], key: &CRS<E>) -> Vec<Self>;
     /// The linear map from Fr to BT for quadratic equations.
     fn linear_map_quad(z: &E::ScalarField, key: &CRS<E>) -> Self;
 }
 
+/// The error returned when pairing together two vectors of different lengths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PairingSumError {
+    pub x_len: usize,
+    pub y_len: usize,
+}
+
+impl Display for PairingSumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot pair {} B1 elements with {} B2 elements",
+            self.x_len, self.y_len
+        )
+    }
+}
+
+impl ark_std::error::Error for PairingSumError {}
+
 // SXDH instantiation's bilinear group for commitments
 
 /// Base [`B1`](crate::data_structures::B1) for the commitment group in the SXDH instantiation.
-#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Copy, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Com1<E: Pairing>(pub E::G1Affine, pub E::G1Affine);
 
 /// Extension [`B2`](crate::data_structures::B2) for the commitment group in the SXDH instantiation.
-#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Copy, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Com2<E: Pairing>(pub E::G2Affine, pub E::G2Affine);
 
+/// [`Com1`](crate::data_structures::Com1) in projective coordinates, for accumulating sums without
+/// converting back to affine (i.e. paying a field inversion) after every addition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Com1Projective<E: Pairing>(pub E::G1, pub E::G1);
+
+/// [`Com2`](crate::data_structures::Com2) in projective coordinates, for accumulating sums without
+/// converting back to affine (i.e. paying a field inversion) after every addition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Com2Projective<E: Pairing>(pub E::G2, pub E::G2);
+
+/// Precomputed tables for multiplying a fixed [`Com1`](crate::data_structures::Com1), e.g. a
+/// commitment key of the CRS, by many scalars.
+///
+/// Building the tables costs about as much as a few hundred group additions, after which each
+/// multiplication is a table lookup and addition per window of the scalar (i.e. without any
+/// doublings). The window size grows with the expected number of scalars.
+pub struct Com1MulContext<E: Pairing>(BatchMulPreprocessing<E::G1>, BatchMulPreprocessing<E::G1>);
+
+/// Precomputed tables for multiplying a fixed [`Com2`](crate::data_structures::Com2), e.g. a
+/// commitment key of the CRS, by many scalars, as for [`Com1MulContext`](self::Com1MulContext).
+pub struct Com2MulContext<E: Pairing>(BatchMulPreprocessing<E::G2>, BatchMulPreprocessing<E::G2>);
+
 /// Target [`BT`](crate::data_structures::BT) for the commitment group in the SXDH instantiation.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone)]
 pub struct ComT<E: Pairing>(
     pub PairingOutput<E>,
     pub PairingOutput<E>,
@@ -142,6 +689,9 @@ pub struct ComT<E: Pairing>(
 );
 
 /// Collapse matrix into a single vector.
+///
+/// A single row is returned as is, so that both `1 x n` and `n x 1` matrices collapse into a vector
+/// of `n` elements. The empty matrix collapses into the empty vector.
 pub fn col_vec_to_vec<F: Clone>(mat: &Matrix<F>) -> Vec<F> {
     if mat.len() == 1 {
         mat[0].clone()
@@ -151,6 +701,10 @@ pub fn col_vec_to_vec<F: Clone>(mat: &Matrix<F>) -> Vec<F> {
 }
 
 /// Expand vector into column vector (in matrix form).
+///
+/// The empty vector expands into the empty matrix `vec![]` rather than a `0 x 1` matrix, as there is
+/// no row to carry the number of columns. [`Mat::add`](self::Mat::add) panics when it is added to a
+/// non-empty matrix, so build accumulators of the right shape with [`zeros`](MatrixExt::zeros) instead.
 pub fn vec_to_col_vec<F: Clone>(vec: &[F]) -> Matrix<F> {
     let mut mat = Vec::with_capacity(vec.len());
     for elem in vec.iter() {
@@ -159,11 +713,296 @@ pub fn vec_to_col_vec<F: Clone>(vec: &[F]) -> Matrix<F> {
     mat
 }
 
+/// Applies [`B1::linear_map`] to every `G1` element and [`B2::linear_map`] to every `G2` element, e.g. to
+/// the `x` and `y` variables (or the constants `A` and `B`) of a pairing-product equation at once.
+///
+/// This is the same as calling [`B1::batch_linear_map`] and [`B2::batch_linear_map`] separately.
+pub fn batch_linear_map_both<E: Pairing>(
+    xs: &[E::G1Affine],
+    ys: &[E::G2Affine],
+) -> (Vec<Com1<E>>, Vec<Com2<E>>) {
+    (
+        Com1::<E>::batch_linear_map(xs),
+        Com2::<E>::batch_linear_map(ys),
+    )
+}
+
+/// The Kronecker (tensor) product `lhs ⊗ rhs` of two scalar matrices.
+///
+/// For an `m x n` matrix `lhs` and a `p x q` matrix `rhs`, the product is the `mp x nq` block matrix
+/// whose `(i, j)`-th `p x q` block is `lhs[i][j] * rhs`.
+pub fn kron<F: Field>(lhs: &Matrix<F>, rhs: &Matrix<F>) -> Matrix<F> {
+    kron_blocks(lhs, rhs)
+}
+
+/// The Kronecker (tensor) product `scalars ⊗ coms` of a scalar matrix with a matrix of commitment group
+/// elements, whose `(i, j)`-th block is `coms` scaled by `scalars[i][j]` (see [`kron`](self::kron)).
+pub fn kron_scalar_com<E, C>(scalars: &Matrix<E::ScalarField>, coms: &Matrix<C>) -> Matrix<C>
+where
+    E: Pairing,
+    C: B<E>,
+    Matrix<C>: Mat<C, Other = E::ScalarField>,
+{
+    kron_blocks(scalars, coms)
+}
+
+// Lays out the blocks `lhs[i][j] * rhs` row by row.
+fn kron_blocks<F, C>(lhs: &Matrix<F>, rhs: &Matrix<C>) -> Matrix<C>
+where
+    C: Clone,
+    Matrix<C>: Mat<C, Other = F>,
+{
+    if lhs.is_empty() || lhs[0].is_empty() || rhs.is_empty() || rhs[0].is_empty() {
+        return vec![];
+    }
+    let mut prod: Matrix<C> = Vec::with_capacity(lhs.len() * rhs.len());
+    for lhs_row in lhs.iter() {
+        let blocks: Vec<Matrix<C>> = lhs_row.iter().map(|elem| rhs.scalar_mul(elem)).collect();
+        for k in 0..rhs.len() {
+            prod.push(
+                blocks
+                    .iter()
+                    .flat_map(|block| block[k].iter().cloned())
+                    .collect(),
+            );
+        }
+    }
+    prod
+}
+
+/// The transpose of a [`Matrix`](self::Matrix), borrowing its entries (see
+/// [`transposed_view`](MatrixExt::transposed_view)).
+///
+/// The `(i, j)`-th entry of the view is the `(j, i)`-th entry of the matrix, so the rows of the view
+/// are the columns of the matrix. The view can be multiplied with owned matrices in either order,
+/// without materializing the transpose, e.g. to multiply by `Γ^T` for a large `Γ`.
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixView<'a, F> {
+    mat: &'a Matrix<F>,
+}
+
+impl<'a, F> MatrixView<'a, F> {
+    /// The shape `(rows, cols)` of the view, i.e. the shape of the matrix with its axes swapped.
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        (self.mat.num_cols(), self.mat.len())
+    }
+
+    /// The entry at `(i, j)`, or `None` if it is out of range.
+    #[inline]
+    pub fn entry(&self, (i, j): (usize, usize)) -> Option<&'a F> {
+        self.mat.entry((j, i))
+    }
+
+    /// The `i`-th row, i.e. the `i`-th column of the matrix.
+    #[inline]
+    pub fn row(&self, i: usize) -> impl Iterator<Item = &'a F> + 'a {
+        self.mat.col(i)
+    }
+
+    /// The `j`-th column, i.e. the `j`-th row of the matrix.
+    #[inline]
+    pub fn col(&self, j: usize) -> &'a [F] {
+        &self.mat[j]
+    }
+
+    /// Clones the entries into an owned matrix, equal to [`transpose`](Mat::transpose) of the matrix.
+    pub fn to_matrix(&self) -> Matrix<F>
+    where
+        F: Clone,
+    {
+        (0..self.mat.num_cols())
+            .map(|i| self.row(i).cloned().collect())
+            .collect()
+    }
+}
+
+impl<F: Field> MatrixView<'_, F> {
+    /// The matrix product `lhs * self`, as [`Mat::left_mul`] with the owned transpose, where the
+    /// `(i, j)`-th entry is the product of the `i`-th row of `lhs` and the `j`-th row of the matrix.
+    pub fn left_mul(&self, lhs: &Matrix<F>, is_parallel: bool) -> Matrix<F> {
+        if lhs.is_empty_matrix() || self.mat.is_empty_matrix() {
+            return vec![];
+        }
+        assert_eq!(lhs[0].len(), self.shape().0);
+        let entry = |i: usize, j: usize| -> F {
+            lhs[i]
+                .iter()
+                .zip(self.mat[j].iter())
+                .map(|(a, b)| *a * b)
+                .sum()
+        };
+        mul_entries((lhs.len(), self.shape().1), entry, is_parallel)
+    }
+
+    /// The matrix product `self * rhs`, as [`Mat::right_mul`] with the owned transpose, where the
+    /// `(i, j)`-th entry is the product of the `i`-th column of the matrix and the `j`-th column of `rhs`.
+    pub fn right_mul(&self, rhs: &Matrix<F>, is_parallel: bool) -> Matrix<F> {
+        if self.mat.is_empty_matrix() || rhs.is_empty_matrix() {
+            return vec![];
+        }
+        assert_eq!(self.shape().1, rhs.len());
+        let entry = |i: usize, j: usize| -> F {
+            self.mat
+                .iter()
+                .zip(rhs.iter())
+                .map(|(row, rhs_row)| row[i] * rhs_row[j])
+                .sum()
+        };
+        mul_entries((self.shape().0, rhs[0].len()), entry, is_parallel)
+    }
+}
+
+// Builds a matrix of the given shape from its entries, computing the rows on the rayon thread pool
+// with `is_parallel`
+fn mul_entries<F: Send, Fun: Fn(usize, usize) -> F + Sync>(
+    (rows, cols): (usize, usize),
+    entry: Fun,
+    is_parallel: bool,
+) -> Matrix<F> {
+    if is_parallel {
+        (0..rows)
+            .into_par_iter()
+            .map(|i| (0..cols).map(|j| entry(i, j)).collect())
+            .collect()
+    } else {
+        (0..rows)
+            .map(|i| (0..cols).map(|j| entry(i, j)).collect())
+            .collect()
+    }
+}
+
+// Moves the entries of each row onto the columns, so that no entry is cloned.
+fn into_transpose_rows<T>(mat: Matrix<T>) -> Matrix<T> {
+    if mat.is_empty() {
+        return vec![];
+    }
+    let (m, n) = (mat.len(), mat[0].len());
+    // A ragged row would otherwise be truncated (or leave the columns of different lengths)
+    assert!(
+        mat.iter().all(|row| row.len() == n),
+        "cannot transpose a matrix with rows of different lengths"
+    );
+    let mut trans: Matrix<T> = (0..n).map(|_| Vec::with_capacity(m)).collect();
+    for row in mat {
+        for (col, elem) in trans.iter_mut().zip(row) {
+            col.push(elem);
+        }
+    }
+    trans
+}
+
+/// Number of leading bytes of an element's compressed serialization shown when formatting.
+const FMT_PREFIX_BYTES: usize = 2;
+
+// Writes a short hex prefix of the element's compressed serialization, e.g. `0x8a1b…`.
+fn fmt_hex_prefix<T: CanonicalSerialize>(elem: &T, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut bytes = Vec::new();
+    elem.serialize_compressed(&mut bytes)
+        .map_err(|_| fmt::Error)?;
+    write!(f, "0x")?;
+    for byte in bytes.iter().take(FMT_PREFIX_BYTES) {
+        write!(f, "{:02x}", byte)?;
+    }
+    write!(f, "…")
+}
+
+/// Builds a [`Matrix`](self::Matrix) one row at a time, validating each row as it is pushed rather
+/// than collecting a vector of rows and validating it with [`try_from_vecs`](MatrixExt::try_from_vecs).
+///
+/// The rows are moved into the matrix as they are, so e.g. a prover that commits to variables
+/// incrementally does not need to keep a second vector of rows around.
+#[derive(Clone, Debug, Default)]
+pub struct MatrixBuilder<F> {
+    rows: Matrix<F>,
+}
+
+impl<F> MatrixBuilder<F> {
+    /// A builder without any rows yet.
+    pub fn new() -> Self {
+        Self { rows: vec![] }
+    }
+
+    /// A builder with room for `rows` rows before reallocating.
+    pub fn with_capacity(rows: usize) -> Self {
+        Self {
+            rows: Vec::with_capacity(rows),
+        }
+    }
+
+    /// Appends `row` as the next row, or returns an error (leaving the builder unchanged) if it does
+    /// not have as many entries as the first row.
+    pub fn push_row(&mut self, row: Vec<F>) -> Result<(), MatrixError> {
+        if let Some(first) = self.rows.first() {
+            if first.len() != row.len() {
+                return Err(MatrixError::Ragged {
+                    row: self.rows.len(),
+                    expected: first.len(),
+                    found: row.len(),
+                });
+            }
+        }
+        self.rows.push(row);
+        Ok(())
+    }
+
+    /// The number of rows pushed so far.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the matrix of the pushed rows, as [`from_vecs`](MatrixExt::from_vecs) would.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no row was pushed.
+    pub fn build(self) -> Matrix<F> {
+        if self.rows.is_empty() {
+            panic!("{}", MatrixError::Empty);
+        }
+        self.rows
+    }
+}
+
+/// Wraps a [`Matrix`](crate::data_structures::Matrix) to display it as a shape-labelled grid.
+///
+/// Entries are formatted with their own `Display` implementation, so that e.g. a `3 x 3` matrix of
+/// [`Com1`](crate::data_structures::Com1) elements only shows short prefixes of each point.
+pub struct MatrixDisplay<'a, F>(pub &'a Matrix<F>);
+
+impl<F: Display> Display for MatrixDisplay<'_, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows = self.0.len();
+        let cols = self.0.first().map_or(0, |row| row.len());
+        write!(f, "Matrix[{} x {}]", rows, cols)?;
+        if rows == 0 {
+            return write!(f, "[]");
+        }
+        writeln!(f, "[")?;
+        for row in self.0.iter() {
+            write!(f, "  [")?;
+            for (j, elem) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", elem)?;
+            }
+            writeln!(f, "]")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Display a [`Matrix`](crate::data_structures::Matrix) as a shape-labelled grid (see [`MatrixDisplay`](self::MatrixDisplay)).
+pub fn display_matrix<F: Display>(mat: &Matrix<F>) -> MatrixDisplay<'_, F> {
+    MatrixDisplay(mat)
+}
+
 macro_rules! impl_base_commit_groups {
     (
         $(
-            $com:ident
-        ),*
+            $com:ident, $proj:ident
+        );*
     ) => {
         // Repeat for each $com
         $(
@@ -176,6 +1015,36 @@ macro_rules! impl_base_commit_groups {
                 }
             }
             impl<E: Pairing> Eq for $com<E> {}
+            impl<E: Pairing> Hash for $com<E> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.hash(state);
+                    self.1.hash(state);
+                }
+            }
+
+            // Compact formatting for Com group, e.g. `Com1(0x8a1b…, 0x03fe…)`
+            impl<E: Pairing> Display for $com<E> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}(", stringify!($com))?;
+                    fmt_hex_prefix(&self.0, f)?;
+                    write!(f, ", ")?;
+                    fmt_hex_prefix(&self.1, f)?;
+                    write!(f, ")")
+                }
+            }
+            // The alternate flag (i.e. `{:#?}`) prints the full group elements
+            impl<E: Pairing> Debug for $com<E> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    if f.alternate() {
+                        f.debug_tuple(stringify!($com))
+                            .field(&self.0)
+                            .field(&self.1)
+                            .finish()
+                    } else {
+                        Display::fmt(self, f)
+                    }
+                }
+            }
 
             // Addition for Com group
             impl<E: Pairing> Add<$com<E>> for $com<E> {
@@ -193,6 +1062,13 @@ macro_rules! impl_base_commit_groups {
 
                 #[inline]
                 fn add_assign(&mut self, other: Self) {
+                    *self += &other;
+                }
+            }
+            impl<'a, E: Pairing> AddAssign<&'a $com<E>> for $com<E> {
+
+                #[inline]
+                fn add_assign(&mut self, other: &'a Self) {
                     *self = Self (
                         (self.0 + other.0).into(),
                         (self.1 + other.1).into()
@@ -222,7 +1098,17 @@ macro_rules! impl_base_commit_groups {
 
                 #[inline]
                 fn sub_assign(&mut self, other: Self) {
-                    *self += -other;
+                    *self -= &other;
+                }
+            }
+            impl<'a, E: Pairing> SubAssign<&'a $com<E>> for $com<E> {
+
+                #[inline]
+                fn sub_assign(&mut self, other: &'a Self) {
+                    *self = Self (
+                        (self.0 - other.0).into(),
+                        (self.1 - other.1).into()
+                    );
                 }
             }
             /*
@@ -241,64 +1127,756 @@ macro_rules! impl_base_commit_groups {
                 }
             }
             */
+            // Accumulates in projective coordinates and converts back to affine once
             impl<E: Pairing> Sum for $com<E> {
                 fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
-                    iter.fold(
-                        Self::zero(),
-                        |a,b| a + b,
-                    )
+                    iter.map($proj::<E>::from).sum::<$proj<E>>().into()
+                }
+            }
+            impl<'a, E: Pairing> Sum<&'a $com<E>> for $com<E> {
+                fn sum<I: Iterator<Item=&'a Self>>(iter: I) -> Self {
+                    iter.map(|com| $proj::<E>::from(*com)).sum::<$proj<E>>().into()
+                }
+            }
+
+            // Scalar multiplication for Com group, the same as `scalar_mul`
+            impl<'a, E: Pairing> Mul<&'a E::ScalarField> for $com<E> {
+                type Output = Self;
+
+                #[inline]
+                fn mul(self, rhs: &'a E::ScalarField) -> Self {
+                    Self((self.0 * rhs).into(), (self.1 * rhs).into())
                 }
             }
         )*
     }
 }
-impl_base_commit_groups!(Com1, Com2);
+impl_base_commit_groups!(Com1, Com1Projective; Com2, Com2Projective);
 
-impl<E: Pairing> Zero for Com1<E> {
-    #[inline]
-    fn zero() -> Self {
-        Self(E::G1Affine::zero(), E::G1Affine::zero())
-    }
+macro_rules! impl_base_commit_projective {
+    (
+        $(
+            $com:ident, $proj:ident, $group:ident
+        );*
+    ) => {
+        // Repeat for each $proj
+        $(
+            impl<E: Pairing> $proj<E> {
+                #[inline]
+                pub fn scalar_mul(&self, rhs: &E::ScalarField) -> Self {
+                    Self(self.0 * rhs, self.1 * rhs)
+                }
 
-    #[inline]
-    fn is_zero(&self) -> bool {
-        *self == Self::zero()
-    }
-}
-impl<E: Pairing> Zero for Com2<E> {
-    #[inline]
-    fn zero() -> Self {
-        Self(E::G2Affine::zero(), E::G2Affine::zero())
-    }
+                /// Converts all elements back to affine coordinates, sharing a single field inversion.
+                pub fn normalize_batch(v: &[Self]) -> Vec<$com<E>> {
+                    let flat: Vec<E::$group> = v.iter().flat_map(|elem| [elem.0, elem.1]).collect();
+                    E::$group::normalize_batch(&flat)
+                        .chunks_exact(2)
+                        .map(|pair| $com::<E>(pair[0], pair[1]))
+                        .collect()
+                }
+            }
 
-    #[inline]
-    fn is_zero(&self) -> bool {
-        *self == Self::zero()
-    }
-}
+            impl<E: Pairing> Zero for $proj<E> {
+                #[inline]
+                fn zero() -> Self {
+                    Self(E::$group::zero(), E::$group::zero())
+                }
 
-impl<E: Pairing> B<E> for Com1<E> {}
-impl<E: Pairing> B<E> for Com2<E> {}
+                #[inline]
+                fn is_zero(&self) -> bool {
+                    self.0.is_zero() && self.1.is_zero()
+                }
+            }
 
-impl<E: Pairing> From<Matrix<E::G1Affine>> for Com1<E> {
-    fn from(mat: Matrix<E::G1Affine>) -> Self {
-        assert_eq!(mat.len(), 2);
-        assert_eq!(mat[0].len(), 1);
-        assert_eq!(mat[1].len(), 1);
-        Self(mat[0][0], mat[1][0])
-    }
-}
-impl<E: Pairing> From<Matrix<E::G2Affine>> for Com2<E> {
-    fn from(mat: Matrix<E::G2Affine>) -> Self {
-        assert_eq!(mat.len(), 2);
-        assert_eq!(mat[0].len(), 1);
-        assert_eq!(mat[1].len(), 1);
-        Self(mat[0][0], mat[1][0])
-    }
-}
+            impl<E: Pairing> Add<$proj<E>> for $proj<E> {
+                type Output = Self;
 
-impl<E: Pairing> B1<E> for Com1<E> {
-    fn as_col_vec(&self) -> Matrix<E::G1Affine> {
+                #[inline]
+                fn add(self, other: Self) -> Self {
+                    Self(self.0 + other.0, self.1 + other.1)
+                }
+            }
+            impl<E: Pairing> AddAssign<$proj<E>> for $proj<E> {
+
+                #[inline]
+                fn add_assign(&mut self, other: Self) {
+                    self.0 += other.0;
+                    self.1 += other.1;
+                }
+            }
+            impl<E: Pairing> Sum for $proj<E> {
+                fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+                    iter.fold(
+                        Self::zero(),
+                        |a,b| a + b,
+                    )
+                }
+            }
+
+            impl<E: Pairing> From<$com<E>> for $proj<E> {
+                #[inline]
+                fn from(com: $com<E>) -> Self {
+                    Self(com.0.into_group(), com.1.into_group())
+                }
+            }
+            impl<E: Pairing> From<$proj<E>> for $com<E> {
+                #[inline]
+                fn from(proj: $proj<E>) -> Self {
+                    $proj::<E>::normalize_batch(&[proj])[0]
+                }
+            }
+        )*
+    }
+}
+impl_base_commit_projective!(Com1, Com1Projective, G1; Com2, Com2Projective, G2);
+
+macro_rules! impl_base_commit_mul_context {
+    (
+        $(
+            $com:ident, $ctx:ident
+        );*
+    ) => {
+        // Repeat for each $ctx
+        $(
+            impl<E: Pairing> $ctx<E> {
+                /// Precomputes the tables for `base`, sized for about `num_scalars` multiplications.
+                pub fn new(base: &$com<E>, num_scalars: usize) -> Self {
+                    Self(
+                        BatchMulPreprocessing::new(base.0.into_group(), num_scalars),
+                        BatchMulPreprocessing::new(base.1.into_group(), num_scalars),
+                    )
+                }
+
+                /// The product of the base with `k`, i.e. the same as `base.scalar_mul(k)`.
+                pub fn mul(&self, k: &E::ScalarField) -> $com<E> {
+                    self.batch_mul(ark_std::slice::from_ref(k))[0]
+                }
+
+                /// The products of the base with every scalar, converting back to affine coordinates
+                /// with a single field inversion per coordinate.
+                pub fn batch_mul(&self, scalars: &[E::ScalarField]) -> Vec<$com<E>> {
+                    self.0
+                        .batch_mul(scalars)
+                        .into_iter()
+                        .zip(self.1.batch_mul(scalars))
+                        .map(|(x, y)| $com::<E>(x, y))
+                        .collect()
+                }
+            }
+        )*
+    }
+}
+impl_base_commit_mul_context!(Com1, Com1MulContext; Com2, Com2MulContext);
+
+impl<E: Pairing> Zero for Com1<E> {
+    #[inline]
+    fn zero() -> Self {
+        Self(E::G1Affine::zero(), E::G1Affine::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+impl<E: Pairing> Zero for Com2<E> {
+    #[inline]
+    fn zero() -> Self {
+        Self(E::G2Affine::zero(), E::G2Affine::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+// The zero element, e.g. as the `Default` output of a commitment scheme
+impl<E: Pairing> Default for Com1<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+impl<E: Pairing> Default for Com2<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<E: Pairing> B<E> for Com1<E> {}
+impl<E: Pairing> B<E> for Com2<E> {}
+
+/// The error returned when a matrix does not have the shape required by a conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeError {
+    pub expected: (usize, usize),
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {} x {} matrix",
+            self.expected.0, self.expected.1
+        )
+    }
+}
+
+impl ark_std::error::Error for ShapeError {}
+
+/// The error returned when a vector of rows is not a valid matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// There are no rows.
+    Empty,
+    /// The row at index `row` has `found` entries, whereas the first row has `expected` entries.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::Empty => write!(f, "expected a matrix with at least one row"),
+            MatrixError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} entries, expected {} as in the first row",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl ark_std::error::Error for MatrixError {}
+
+/// The error returned when a list of indices is not a permutation of `0..n`, e.g. of the rows of a matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermError {
+    /// There are `found` indices rather than `expected`, i.e. one per row (or column).
+    Length { expected: usize, found: usize },
+    /// The index at position `pos` is out of range or repeats an earlier index.
+    Invalid { pos: usize, index: usize },
+}
+
+impl Display for PermError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PermError::Length { expected, found } => write!(
+                f,
+                "expected a permutation of {} indices, found {} indices",
+                expected, found
+            ),
+            PermError::Invalid { pos, index } => write!(
+                f,
+                "index {} at position {} is out of range or repeated, so this is not a permutation",
+                index, pos
+            ),
+        }
+    }
+}
+
+impl ark_std::error::Error for PermError {}
+
+/// Checks that `perm` is a permutation of `0..n`.
+pub(crate) fn check_permutation(perm: &[usize], n: usize) -> Result<(), PermError> {
+    if perm.len() != n {
+        return Err(PermError::Length {
+            expected: n,
+            found: perm.len(),
+        });
+    }
+    let mut seen = vec![false; n];
+    for (pos, &index) in perm.iter().enumerate() {
+        if index >= n || seen[index] {
+            return Err(PermError::Invalid { pos, index });
+        }
+        seen[index] = true;
+    }
+    Ok(())
+}
+
+/// The error, wrapped in [`SerializationError::IoError`], when deserializing a value of a
+/// serialization [format version](FORMAT_VERSION) that this crate cannot read.
+///
+/// The I/O error displays as this error, i.e. states the unsupported version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedVersion {
+    pub found: u8,
+}
+
+impl Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsupported serialization format version {}, expected {}",
+            self.found, FORMAT_VERSION
+        )
+    }
+}
+
+impl ark_std::error::Error for UnsupportedVersion {}
+
+impl From<UnsupportedVersion> for SerializationError {
+    fn from(err: UnsupportedVersion) -> Self {
+        SerializationError::IoError(ark_std::io::Error::new(
+            ark_std::io::ErrorKind::InvalidData,
+            err,
+        ))
+    }
+}
+
+/// The serialization format version of a [`CRS`], the commitments, proofs and statements, and a
+/// [`ProofBundle`](crate::verifier::ProofBundle).
+///
+/// Version `0` is the original encoding of their fields, without a header, which writes the length of
+/// a vector as a `u64` and a [`Matrix`] as a vector of rows. Later versions start with the 8 bytes of
+/// [`FORMAT_MAGIC`] and then the version byte. Version `1` writes the length of a vector as a `u32`
+/// and a matrix as by [`serialize_matrix`].
+///
+/// Both versions are read with the vectors bounded by [`MAX_SERIALIZED_LEN`] and the matrices checked
+/// against the dimensions they must have.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The bytes that start every encoding of a [`FORMAT_VERSION`] above `0`.
+///
+/// A version `0` encoding starts with the length of a vector as a little-endian `u64`, e.g. of the
+/// first vector of constants of a bundle's statement. The magic ends with `0xff`, so read as such a
+/// length it is at least `2^63`, which no vector can have: an encoding is of version `0` exactly when
+/// it does not start with the magic.
+pub const FORMAT_MAGIC: [u8; 8] = *b"\xffGrSahi\xff";
+
+// The size of the header that `serialize_version` writes
+pub(crate) const VERSION_HEADER_SIZE: usize = FORMAT_MAGIC.len() + 1;
+
+// Marks an encoding of the current version
+pub(crate) fn serialize_version<W: Write>(mut writer: W) -> Result<(), SerializationError> {
+    writer.write_all(&FORMAT_MAGIC)?;
+    FORMAT_VERSION.serialize_compressed(writer)
+}
+
+// Reads the header of an encoding, then the value that follows it with `deserialize`, which is passed
+// the version and a reader that starts at the first byte if the encoding has no header (i.e. is of version 0)
+pub(crate) fn deserialize_versioned<T, R: Read>(
+    mut reader: R,
+    deserialize: impl FnOnce(Unread<R>, u8) -> Result<T, SerializationError>,
+) -> Result<T, SerializationError> {
+    let mut magic = [0u8; FORMAT_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != FORMAT_MAGIC {
+        let reader = Unread {
+            read: magic,
+            pos: 0,
+            reader,
+        };
+        return deserialize(reader, 0);
+    }
+    let version = u8::deserialize_compressed(&mut reader)?;
+    if version != FORMAT_VERSION {
+        return Err(UnsupportedVersion { found: version }.into());
+    }
+    let reader = Unread {
+        read: magic,
+        pos: magic.len(),
+        reader,
+    };
+    deserialize(reader, version)
+}
+
+// A reader that yields the bytes already read from `reader` (from `pos` on) before the rest of it
+pub(crate) struct Unread<R> {
+    read: [u8; FORMAT_MAGIC.len()],
+    pos: usize,
+    reader: R,
+}
+
+impl<R: Read> Read for Unread<R> {
+    fn read(&mut self, buf: &mut [u8]) -> ark_std::io::Result<usize> {
+        if self.pos == self.read.len() {
+            return self.reader.read(buf);
+        }
+        let n = buf.len().min(self.read.len() - self.pos);
+        buf[..n].copy_from_slice(&self.read[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// The most entries a vector of a deserialized [`CRS`], [`CProof`](crate::prover::CProof), commitment or
+/// statement may claim to have, e.g. the number of variables.
+///
+/// A longer vector is rejected before anything is allocated for it, and memory for a shorter one is only
+/// allocated as its entries are read, so that a short input cannot make the deserializer allocate much
+/// more than its own size. The dimensions of their matrices are checked against those of their vectors.
+pub const MAX_SERIALIZED_LEN: usize = 1 << 24;
+
+// The most entries allocated for ahead of reading them
+const PREALLOC_LEN: usize = 1 << 10;
+
+// Writes a vector of a current version encoding as its length (as a `u32`) and its entries
+pub(crate) fn serialize_vec<T: CanonicalSerialize, W: Write>(
+    vec: &[T],
+    mut writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    let len = u32::try_from(vec.len()).map_err(|_| SerializationError::InvalidData)?;
+    len.serialize_with_mode(&mut writer, compress)?;
+    for elem in vec {
+        elem.serialize_with_mode(&mut writer, compress)?;
+    }
+    Ok(())
+}
+
+// The number of bytes `serialize_vec` writes for `vec`
+pub(crate) fn serialized_vec_size<T: CanonicalSerialize>(vec: &[T], compress: Compress) -> usize {
+    0u32.serialized_size(compress)
+        + vec
+            .iter()
+            .map(|elem| elem.serialized_size(compress))
+            .sum::<usize>()
+}
+
+// Reads a vector of an encoding of `version`, whose length is a `u64` in version 0 and a `u32` after it,
+// rejecting it before any allocation if it claims more than `max_len` entries
+pub(crate) fn deserialize_vec<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    version: u8,
+    compress: Compress,
+    validate: Validate,
+    max_len: usize,
+) -> Result<Vec<T>, SerializationError> {
+    let len = if version == 0 {
+        usize::try_from(u64::deserialize_with_mode(&mut reader, compress, validate)?)
+            .map_err(|_| SerializationError::InvalidData)?
+    } else {
+        u32::deserialize_with_mode(&mut reader, compress, validate)? as usize
+    };
+    if len > max_len {
+        return Err(SerializationError::InvalidData);
+    }
+    let vec = read_entries(&mut reader, len, compress, Validate::No)?;
+    if let Validate::Yes = validate {
+        T::batch_check(vec.iter())?;
+    }
+    Ok(vec)
+}
+
+// Reads a matrix of an encoding of `version`, in the `Vec<Vec<F>>` format in version 0 and that of
+// `serialize_matrix` after it, rejecting it before any allocation unless it is the empty matrix or has
+// `rows` rows of the same length, which is one of `cols`
+pub(crate) fn deserialize_matrix_of_shape<F: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    version: u8,
+    compress: Compress,
+    validate: Validate,
+    rows: usize,
+    cols: &[usize],
+) -> Result<Matrix<F>, SerializationError> {
+    if version != 0 {
+        let found = read_matrix_dims(&mut reader, compress, validate)?;
+        if found != (0, 0) && (found.0 != rows || !cols.contains(&found.1)) {
+            return Err(SerializationError::InvalidData);
+        }
+        return read_matrix_entries(reader, found, compress, validate);
+    }
+
+    let found_rows = deserialize_vec_len(&mut reader, compress, validate)?;
+    if found_rows != 0 && found_rows != rows {
+        return Err(SerializationError::InvalidData);
+    }
+    let mut mat: Matrix<F> = Vec::with_capacity(found_rows);
+    for _ in 0..found_rows {
+        let row_len = deserialize_vec_len(&mut reader, compress, validate)?;
+        if !cols.contains(&row_len) || mat.first().is_some_and(|row| row.len() != row_len) {
+            return Err(SerializationError::InvalidData);
+        }
+        mat.push(read_entries(&mut reader, row_len, compress, validate)?);
+    }
+    Ok(mat)
+}
+
+// The length of a vector in the `Vec<T>` format of `CanonicalSerialize`, or `usize::MAX` if it does not fit
+fn deserialize_vec_len<R: Read>(
+    reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<usize, SerializationError> {
+    let len = u64::deserialize_with_mode(reader, compress, validate)?;
+    Ok(usize::try_from(len).unwrap_or(usize::MAX))
+}
+
+// Reads `len` entries, allocating for them as they are read
+fn read_entries<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    len: usize,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<T>, SerializationError> {
+    let mut vec = Vec::with_capacity(len.min(PREALLOC_LEN));
+    for _ in 0..len {
+        vec.push(T::deserialize_with_mode(&mut reader, compress, validate)?);
+    }
+    Ok(vec)
+}
+
+// Checks that every row of the matrix has the expected number of columns.
+fn check_shape<F>(mat: &Matrix<F>, rows: usize, cols: usize) -> Result<(), ShapeError> {
+    if mat.len() != rows || mat.iter().any(|row| row.len() != cols) {
+        return Err(ShapeError {
+            expected: (rows, cols),
+        });
+    }
+    Ok(())
+}
+
+// Multiplies every row (or, with `by_cols`, every column) of `mat` by the corresponding scalar with `mul`
+fn scale_by<T, S>(
+    mat: &Matrix<T>,
+    scalars: &[S],
+    by_cols: bool,
+    mul: impl Fn(&T, &S) -> T,
+) -> Result<Matrix<T>, ShapeError> {
+    let n = if by_cols { mat.num_cols() } else { mat.len() };
+    if scalars.len() != n {
+        return Err(ShapeError { expected: (n, 1) });
+    }
+    Ok(mat
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, elem)| mul(elem, &scalars[if by_cols { j } else { i }]))
+                .collect()
+        })
+        .collect())
+}
+
+/// Serializes a matrix as its dimensions `(rows: u32, cols: u32)` followed by its entries in row-major order.
+///
+/// Unlike the `Vec<Vec<F>>` format of [`CanonicalSerialize`] on a [`Matrix`](self::Matrix), which writes
+/// a length before every row, the shape is written once up front, so that
+/// [`deserialize_matrix`](self::deserialize_matrix) can bound the size of the matrix before allocating
+/// it. The two formats are not compatible: this is the format of the matrices in a statement,
+/// commitment or proof of the current [`FORMAT_VERSION`], and the `Vec<Vec<F>>` format that of version `0`.
+///
+/// Returns [`InvalidData`](SerializationError::InvalidData) if the rows have different lengths or a
+/// dimension does not fit in a `u32`.
+pub fn serialize_matrix<F: CanonicalSerialize, W: Write>(
+    mat: &Matrix<F>,
+    mut writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    let cols = mat.num_cols();
+    if mat.iter().any(|row| row.len() != cols) {
+        return Err(SerializationError::InvalidData);
+    }
+    let rows = u32::try_from(mat.len()).map_err(|_| SerializationError::InvalidData)?;
+    let cols = u32::try_from(cols).map_err(|_| SerializationError::InvalidData)?;
+    rows.serialize_with_mode(&mut writer, compress)?;
+    cols.serialize_with_mode(&mut writer, compress)?;
+    for elem in mat.iter().flatten() {
+        elem.serialize_with_mode(&mut writer, compress)?;
+    }
+    Ok(())
+}
+
+/// The number of bytes [`serialize_matrix`](self::serialize_matrix) writes for `mat`, computed from its
+/// entries without serializing (or copying) them.
+pub fn serialized_matrix_size<F: CanonicalSerialize>(mat: &Matrix<F>, compress: Compress) -> usize {
+    2 * 0u32.serialized_size(compress)
+        + mat
+            .iter()
+            .flatten()
+            .map(|elem| elem.serialized_size(compress))
+            .sum::<usize>()
+}
+
+/// Deserializes a matrix written by [`serialize_matrix`](self::serialize_matrix), rejecting it before
+/// any allocation if it claims more than `max_elems` entries.
+///
+/// A `rows x 0` matrix is restored as `rows` rows without entries, whereas a `0 x cols` matrix is
+/// restored as the empty matrix `vec![]`, which cannot carry a number of columns.
+pub fn deserialize_matrix<F: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+    max_elems: usize,
+) -> Result<Matrix<F>, SerializationError> {
+    let (rows, cols) = read_matrix_dims(&mut reader, compress, validate)?;
+    match rows.checked_mul(cols) {
+        Some(num_elems) if num_elems <= max_elems => {}
+        _ => return Err(SerializationError::InvalidData),
+    }
+    // Rows without entries are not bounded by `max_elems`, but are still allocated
+    if rows > max_elems && cols == 0 {
+        return Err(SerializationError::InvalidData);
+    }
+    read_matrix_entries(reader, (rows, cols), compress, validate)
+}
+
+// The dimensions written by `serialize_matrix`
+fn read_matrix_dims<R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<(usize, usize), SerializationError> {
+    let rows = u32::deserialize_with_mode(&mut reader, compress, validate)? as usize;
+    let cols = u32::deserialize_with_mode(&mut reader, compress, validate)? as usize;
+    Ok((rows, cols))
+}
+
+// The row-major entries written by `serialize_matrix` for a matrix of the given shape
+fn read_matrix_entries<F: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    (rows, cols): (usize, usize),
+    compress: Compress,
+    validate: Validate,
+) -> Result<Matrix<F>, SerializationError> {
+    let mut mat: Matrix<F> = Vec::with_capacity(rows.min(PREALLOC_LEN));
+    for _ in 0..rows {
+        mat.push(read_entries(&mut reader, cols, compress, validate)?);
+    }
+    Ok(mat)
+}
+
+macro_rules! impl_base_commit_conversions {
+    (
+        $(
+            $com:ident, $affine:ident
+        );*
+    ) => {
+        // Repeat for each $com
+        $(
+            impl<E: Pairing> $com<E> {
+                /// Converts a `2 x 1` column vector, or returns an error if the matrix has any other shape.
+                ///
+                /// This is the fallible counterpart of the `From` conversion, which panics instead. It is not
+                /// a `TryFrom` implementation, since `From` already implies an (infallible) `TryFrom`.
+                pub fn try_from_matrix(mat: Matrix<E::$affine>) -> Result<Self, ShapeError> {
+                    check_shape(&mat, 2, 1)?;
+                    Ok(Self(mat[0][0], mat[1][0]))
+                }
+
+                #[inline]
+                pub fn as_tuple(&self) -> (E::$affine, E::$affine) {
+                    (self.0, self.1)
+                }
+            }
+
+            /// # Panics
+            ///
+            /// Panics if the matrix is not a `2 x 1` column vector (see `try_from_matrix`).
+            impl<E: Pairing> From<Matrix<E::$affine>> for $com<E> {
+                fn from(mat: Matrix<E::$affine>) -> Self {
+                    match Self::try_from_matrix(mat) {
+                        Ok(com) => com,
+                        Err(err) => panic!("{}", err),
+                    }
+                }
+            }
+
+            impl<E: Pairing> From<(E::$affine, E::$affine)> for $com<E> {
+                #[inline]
+                fn from(tuple: (E::$affine, E::$affine)) -> Self {
+                    Self(tuple.0, tuple.1)
+                }
+            }
+            impl<E: Pairing> From<$com<E>> for (E::$affine, E::$affine) {
+                #[inline]
+                fn from(com: $com<E>) -> Self {
+                    com.as_tuple()
+                }
+            }
+        )*
+    }
+}
+impl_base_commit_conversions!(Com1, G1Affine; Com2, G2Affine);
+
+// Selects each coordinate as `a + c (b - a)` for the field element `c` of the choice (i.e. 0 or 1), so
+// that the same field operations run for either choice.
+#[cfg(feature = "subtle")]
+fn conditional_select_affine<P: SWCurveConfig>(
+    a: &SWAffine<P>,
+    b: &SWAffine<P>,
+    choice: subtle::Choice,
+) -> SWAffine<P> {
+    use subtle::ConditionallySelectable;
+
+    let c = P::BaseField::from(u64::from(choice.unwrap_u8()));
+    let infinity = u8::conditional_select(&u8::from(a.infinity), &u8::from(b.infinity), choice);
+    SWAffine {
+        x: a.x + c * (b.x - a.x),
+        y: a.y + c * (b.y - a.y),
+        infinity: infinity == 1,
+    }
+}
+
+macro_rules! impl_base_commit_select {
+    (
+        $(
+            $com:ident, $affine:ident
+        );*
+    ) => {
+        // Repeat for each $com
+        $(
+            /// Selects `a` if `choice` is 0 and `b` if `choice` is 1, without branching on `choice`, for
+            /// groups in short Weierstrass form.
+            ///
+            /// The coordinates are selected with field arithmetic. Only the selection itself is
+            /// branch-free; arkworks' group arithmetic on the result is not guaranteed to run in
+            /// constant time.
+            #[cfg(feature = "subtle")]
+            impl<E, P> subtle::ConditionallySelectable for $com<E>
+            where
+                E: Pairing<$affine = SWAffine<P>>,
+                P: SWCurveConfig,
+            {
+                fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+                    Self(
+                        conditional_select_affine(&a.0, &b.0, choice),
+                        conditional_select_affine(&a.1, &b.1, choice),
+                    )
+                }
+            }
+        )*
+    }
+}
+impl_base_commit_select!(Com1, G1Affine; Com2, G2Affine);
+
+impl<E: Pairing> Com1<E> {
+    /// Lazily applies [`linear_map`](B1::linear_map) to each element, without allocating.
+    pub fn linear_map_iter<'a, I>(x_iter: I) -> impl Iterator<Item = Self> + 'a
+    where
+        I: IntoIterator<Item = &'a E::G1Affine>,
+        I::IntoIter: 'a,
+    {
+        x_iter
+            .into_iter()
+            .map(|elem| <Self as B1<E>>::linear_map(elem))
+    }
+
+    /// The parallel counterpart of [`linear_map_iter`](Com1::linear_map_iter), which preserves the order of `x_vec`.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    pub fn par_linear_map_iter<'a>(
+        x_vec: &'a [E::G1Affine],
+    ) -> impl IndexedParallelIterator<Item = Self> + 'a {
+        x_vec
+            .par_iter()
+            .map(|elem| <Self as B1<E>>::linear_map(elem))
+    }
+}
+
+impl<E: Pairing> B1<E> for Com1<E> {
+    fn as_col_vec(&self) -> Matrix<E::G1Affine> {
         vec![vec![self.0], vec![self.1]]
     }
 
@@ -322,14 +1900,26 @@ impl<E: Pairing> B1<E> for Com1<E> {
     #[inline]
     fn scalar_linear_map(x: &E::ScalarField, key: &CRS<E>) -> Self {
         // = xu, where u = u_2 + (O, P) is a commitment group element
-        (key.u[1] + Com1::<E>::linear_map(&key.g1_gen)).scalar_mul(x)
+        Self::scalar_linear_map_with_base(&key.u2().prepare_scalar_base(&key.g1_gen), x)
+    }
+
+    #[inline]
+    fn prepare_scalar_base(&self, p: &E::G1Affine) -> Self {
+        *self + Com1::<E>::linear_map(p)
+    }
+
+    #[inline]
+    fn scalar_linear_map_with_base(base: &Self, x: &E::ScalarField) -> Self {
+        base.scalar_mul(x)
     }
 
     #[inline]
     fn batch_scalar_linear_map(x_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self> {
+        // The base is independent of the scalars, so only compute it once
+        let base = key.u2().prepare_scalar_base(&key.g1_gen);
         x_vec
             .iter()
-            .map(|elem| Self::scalar_linear_map(elem, key))
+            .map(|elem| Self::scalar_linear_map_with_base(&base, elem))
             .collect::<Vec<Self>>()
     }
 
@@ -342,6 +1932,29 @@ impl<E: Pairing> B1<E> for Com1<E> {
     }
 }
 
+impl<E: Pairing> Com2<E> {
+    /// Lazily applies [`linear_map`](B2::linear_map) to each element, without allocating.
+    pub fn linear_map_iter<'a, I>(y_iter: I) -> impl Iterator<Item = Self> + 'a
+    where
+        I: IntoIterator<Item = &'a E::G2Affine>,
+        I::IntoIter: 'a,
+    {
+        y_iter
+            .into_iter()
+            .map(|elem| <Self as B2<E>>::linear_map(elem))
+    }
+
+    /// The parallel counterpart of [`linear_map_iter`](Com2::linear_map_iter), which preserves the order of `y_vec`.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    pub fn par_linear_map_iter<'a>(
+        y_vec: &'a [E::G2Affine],
+    ) -> impl IndexedParallelIterator<Item = Self> + 'a {
+        y_vec
+            .par_iter()
+            .map(|elem| <Self as B2<E>>::linear_map(elem))
+    }
+}
+
 impl<E: Pairing> B2<E> for Com2<E> {
     fn as_col_vec(&self) -> Matrix<E::G2Affine> {
         vec![vec![self.0], vec![self.1]]
@@ -367,14 +1980,26 @@ impl<E: Pairing> B2<E> for Com2<E> {
     #[inline]
     fn scalar_linear_map(y: &E::ScalarField, key: &CRS<E>) -> Self {
         // = yv, where v = v_2 + (O, P) is a commitment group element
-        (key.v[1] + Com2::<E>::linear_map(&key.g2_gen)).scalar_mul(y)
+        Self::scalar_linear_map_with_base(&key.v2().prepare_scalar_base(&key.g2_gen), y)
+    }
+
+    #[inline]
+    fn prepare_scalar_base(&self, p: &E::G2Affine) -> Self {
+        *self + Com2::<E>::linear_map(p)
+    }
+
+    #[inline]
+    fn scalar_linear_map_with_base(base: &Self, y: &E::ScalarField) -> Self {
+        base.scalar_mul(y)
     }
 
     #[inline]
     fn batch_scalar_linear_map(y_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self> {
+        // The base is independent of the scalars, so only compute it once
+        let base = key.v2().prepare_scalar_base(&key.g2_gen);
         y_vec
             .iter()
-            .map(|elem| Self::scalar_linear_map(elem, key))
+            .map(|elem| Self::scalar_linear_map_with_base(&base, elem))
             .collect::<Vec<Self>>()
     }
 
@@ -396,6 +2021,75 @@ impl<E: Pairing> PartialEq for ComT<E> {
 }
 impl<E: Pairing> Eq for ComT<E> {}
 
+/// Compares all four entries at the byte level, without short-circuiting on the first mismatch.
+///
+/// As for [`conditional_select`](subtle::ConditionallySelectable::conditional_select) on `Com1` and
+/// `Com2`, only the comparison itself is branch-free.
+#[cfg(feature = "subtle")]
+impl<E: Pairing> subtle::ConstantTimeEq for ComT<E> {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        ct_eq_serialized(&self.0, &other.0)
+            & ct_eq_serialized(&self.1, &other.1)
+            & ct_eq_serialized(&self.2, &other.2)
+            & ct_eq_serialized(&self.3, &other.3)
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<E: Pairing> ComT<E> {
+    /// Whether this is the zero element (i.e. the `2 x 2` matrix of `GT` identities), computed with
+    /// [`ct_eq`](subtle::ConstantTimeEq::ct_eq).
+    pub fn ct_is_zero(&self) -> subtle::Choice {
+        use subtle::ConstantTimeEq;
+
+        self.ct_eq(&Self::zero())
+    }
+}
+
+// Compares the canonical (compressed) serializations of two elements in constant time.
+#[cfg(feature = "subtle")]
+fn ct_eq_serialized<A: CanonicalSerialize>(a: &A, b: &A) -> subtle::Choice {
+    use subtle::ConstantTimeEq;
+
+    let mut a_bytes = Vec::with_capacity(a.compressed_size());
+    let mut b_bytes = Vec::with_capacity(b.compressed_size());
+    a.serialize_compressed(&mut a_bytes)
+        .expect("serializing into a Vec cannot fail");
+    b.serialize_compressed(&mut b_bytes)
+        .expect("serializing into a Vec cannot fail");
+    a_bytes.ct_eq(&b_bytes)
+}
+
+// Compact formatting for ComT, e.g. `ComT(0x8a1b…, 0x03fe…, 0x0000…, 0x1c2d…)`
+impl<E: Pairing> Display for ComT<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ComT(")?;
+        fmt_hex_prefix(&self.0, f)?;
+        write!(f, ", ")?;
+        fmt_hex_prefix(&self.1, f)?;
+        write!(f, ", ")?;
+        fmt_hex_prefix(&self.2, f)?;
+        write!(f, ", ")?;
+        fmt_hex_prefix(&self.3, f)?;
+        write!(f, ")")
+    }
+}
+// The alternate flag (i.e. `{:#?}`) prints the full target group elements
+impl<E: Pairing> Debug for ComT<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_tuple("ComT")
+                .field(&self.0)
+                .field(&self.1)
+                .field(&self.2)
+                .field(&self.3)
+                .finish()
+        } else {
+            Display::fmt(self, f)
+        }
+    }
+}
+
 impl<E: Pairing> Add<ComT<E>> for ComT<E> {
     type Output = Self;
 
@@ -428,6 +2122,12 @@ impl<E: Pairing> Zero for ComT<E> {
 impl<E: Pairing> AddAssign<ComT<E>> for ComT<E> {
     #[inline]
     fn add_assign(&mut self, other: Self) {
+        *self += &other;
+    }
+}
+impl<'a, E: Pairing> AddAssign<&'a ComT<E>> for ComT<E> {
+    #[inline]
+    fn add_assign(&mut self, other: &'a Self) {
         self.0 += other.0;
         self.1 += other.1;
         self.2 += other.2;
@@ -458,24 +2158,295 @@ impl<E: Pairing> Sub<ComT<E>> for ComT<E> {
 impl<E: Pairing> SubAssign<ComT<E>> for ComT<E> {
     #[inline]
     fn sub_assign(&mut self, other: Self) {
+        *self -= &other;
+    }
+}
+impl<'a, E: Pairing> SubAssign<&'a ComT<E>> for ComT<E> {
+    #[inline]
+    fn sub_assign(&mut self, other: &'a Self) {
         self.0 -= other.0;
         self.1 -= other.1;
         self.2 -= other.2;
         self.3 -= other.3;
     }
 }
-impl<E: Pairing> From<Matrix<PairingOutput<E>>> for ComT<E> {
-    fn from(mat: Matrix<PairingOutput<E>>) -> Self {
-        assert_eq!(mat.len(), 2);
-        assert_eq!(mat[0].len(), 2);
-        assert_eq!(mat[1].len(), 2);
-        Self(mat[0][0], mat[0][1], mat[1][0], mat[1][1])
+impl<E: Pairing> ComT<E> {
+    /// Converts a `2 x 2` matrix, or returns an error if the matrix has any other shape.
+    ///
+    /// This is the fallible counterpart of the `From` conversion, as for [`Com1::try_from_matrix`].
+    pub fn try_from_matrix(mat: Matrix<PairingOutput<E>>) -> Result<Self, ShapeError> {
+        check_shape(&mat, 2, 2)?;
+        Ok(Self(mat[0][0], mat[0][1], mat[1][0], mat[1][1]))
+    }
+
+    /// The entries of the `2 x 2` matrix in row-major order.
+    #[inline]
+    pub fn as_array(&self) -> [PairingOutput<E>; 4] {
+        [self.0, self.1, self.2, self.3]
+    }
+
+    /// Scalar multiplication (i.e. exponentiation in `GT`) of every entry.
+    pub fn scalar_mul(&self, rhs: &E::ScalarField) -> Self {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs, self.3 * rhs)
+    }
+
+    /// The canonical serializations of the four entries in row-major order, e.g. for absorbing into a
+    /// Fiat-Shamir transcript or for logging. The encoding is stable for a given `E` and `compress`.
+    pub fn to_bytes(&self, compress: Compress) -> Vec<u8> {
+        let entries = self.as_array();
+        let mut bytes = Vec::with_capacity(4 * entries[0].serialized_size(compress));
+        for entry in entries.iter() {
+            entry
+                .serialize_with_mode(&mut bytes, compress)
+                .expect("serializing into a Vec cannot fail");
+        }
+        bytes
+    }
+
+    /// The inverse of [`to_bytes`](ComT::to_bytes), validating every entry. Returns an error if the
+    /// entries are invalid or `bytes` is not exactly four serialized entries.
+    pub fn from_bytes(bytes: &[u8], compress: Compress) -> Result<Self, SerializationError> {
+        let mut reader = bytes;
+        let mut entry =
+            || PairingOutput::<E>::deserialize_with_mode(&mut reader, compress, Validate::Yes);
+        let com = Self(entry()?, entry()?, entry()?, entry()?);
+        if !reader.is_empty() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(com)
+    }
+
+    /// As [`from_bytes`](ComT::from_bytes), but without validating the entries, for bytes from a
+    /// trusted source (see the [module documentation](self#serialization)).
+    pub fn from_bytes_unchecked(
+        bytes: &[u8],
+        compress: Compress,
+    ) -> Result<Self, SerializationError> {
+        let mut reader = bytes;
+        let mut entry =
+            || PairingOutput::<E>::deserialize_with_mode(&mut reader, compress, Validate::No);
+        let com = Self(entry()?, entry()?, entry()?, entry()?);
+        if !reader.is_empty() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(com)
+    }
+}
+/// # Panics
+///
+/// Panics if the matrix is not `2 x 2` (see `try_from_matrix`).
+impl<E: Pairing> From<Matrix<PairingOutput<E>>> for ComT<E> {
+    fn from(mat: Matrix<PairingOutput<E>>) -> Self {
+        match Self::try_from_matrix(mat) {
+            Ok(com) => com,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+impl<E: Pairing> From<[PairingOutput<E>; 4]> for ComT<E> {
+    #[inline]
+    fn from(arr: [PairingOutput<E>; 4]) -> Self {
+        Self(arr[0], arr[1], arr[2], arr[3])
+    }
+}
+impl<E: Pairing> From<ComT<E>> for [PairingOutput<E>; 4] {
+    #[inline]
+    fn from(com: ComT<E>) -> Self {
+        com.as_array()
     }
 }
 impl<E: Pairing> Sum for ComT<E> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::zero(), |a, b| a + b)
+        iter.fold(Self::zero(), |mut acc, com| {
+            acc += &com;
+            acc
+        })
+    }
+}
+impl<'a, E: Pairing> Sum<&'a ComT<E>> for ComT<E> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |mut acc, com| {
+            acc += com;
+            acc
+        })
+    }
+}
+
+/// Accumulates a sum of pairings in [`GT`](ark_ec::Pairing::GT), deferring the (costly) final
+/// exponentiation until [`finalize`](MillerAccumulator::finalize) is called.
+#[derive(Clone, Debug)]
+pub struct MillerAccumulator<E: Pairing> {
+    g1_prep: Vec<E::G1Prepared>,
+    g2_prep: Vec<E::G2Prepared>,
+}
+
+impl<E: Pairing> Default for MillerAccumulator<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Pairing> MillerAccumulator<E> {
+    pub fn new() -> Self {
+        Self {
+            g1_prep: Vec::new(),
+            g2_prep: Vec::new(),
+        }
+    }
+
+    /// Adds `e(x, y)` to the accumulated sum.
+    #[inline]
+    pub fn add_pair(&mut self, x: E::G1Affine, y: E::G2Affine) {
+        self.g1_prep.push(x.into());
+        self.g2_prep.push(y.into());
+    }
+
+    /// Subtracts `e(x, y)` from the accumulated sum.
+    #[inline]
+    pub fn sub_pair(&mut self, x: E::G1Affine, y: E::G2Affine) {
+        self.add_pair((-x.into_group()).into_affine(), y);
+    }
+
+    /// Adds `e(x, y)` to the accumulated sum, for a `y` that was already prepared (and is only cloned).
+    #[inline]
+    pub fn add_prepared_pair(&mut self, x: E::G1Affine, y: &E::G2Prepared) {
+        self.g1_prep.push(x.into());
+        self.g2_prep.push(y.clone());
+    }
+
+    /// The number of pairings accumulated so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.g1_prep.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.g1_prep.is_empty()
+    }
+
+    /// Computes the accumulated sum of pairings with a single Miller loop and final exponentiation.
+    pub fn finalize(self) -> PairingOutput<E> {
+        E::final_exponentiation(E::multi_miller_loop(self.g1_prep, self.g2_prep))
+            .expect("the final exponentiation of a Miller loop output is well-defined")
+    }
+}
+
+/// A [`Com2`](crate::data_structures::Com2) with both entries prepared for pairing, e.g. to pair the
+/// same element in several verifications without preparing it again.
+#[derive(Clone, Debug)]
+pub struct Com2Prepared<E: Pairing>(pub E::G2Prepared, pub E::G2Prepared);
+
+impl<E: Pairing> From<Com2<E>> for Com2Prepared<E> {
+    #[inline]
+    fn from(com: Com2<E>) -> Self {
+        Self(com.0.into(), com.1.into())
+    }
+}
+
+/// Accumulates a sum of pairings over the GS commitment group in [`BT`](crate::data_structures::ComT),
+/// deferring the final exponentiation of each of its 4 entries until
+/// [`finalize`](ComMillerAccumulator::finalize) is called.
+///
+/// Checking a whole verification equation with one accumulator costs 4 final exponentiations, rather
+/// than 4 for every separate [`pairing_sum`](BT::pairing_sum).
+#[derive(Clone, Debug, Default)]
+pub struct ComMillerAccumulator<E: Pairing>([MillerAccumulator<E>; 4]);
+
+impl<E: Pairing> ComMillerAccumulator<E> {
+    pub fn new() -> Self {
+        Self(Default::default())
+    }
+
+    /// Adds the tensor product `x ⊗ y` (see [`pairing`](BT::pairing)) to the accumulated sum.
+    #[inline]
+    pub fn add_com(&mut self, x: Com1<E>, y: Com2<E>) {
+        self.0[0].add_pair(x.0, y.0);
+        self.0[1].add_pair(x.0, y.1);
+        self.0[2].add_pair(x.1, y.0);
+        self.0[3].add_pair(x.1, y.1);
     }
+
+    /// Subtracts the tensor product `x ⊗ y` from the accumulated sum.
+    #[inline]
+    pub fn sub_com(&mut self, x: Com1<E>, y: Com2<E>) {
+        self.add_com(-x, y);
+    }
+
+    /// Adds the entry-wise sum of pairings (see [`pairing_sum`](BT::pairing_sum)) to the accumulated sum,
+    /// or returns an error if `x_vec` and `y_vec` have different lengths.
+    pub fn add_com_sum(
+        &mut self,
+        x_vec: &[Com1<E>],
+        y_vec: &[Com2<E>],
+    ) -> Result<(), PairingSumError> {
+        check_pairing_lengths(x_vec, y_vec)?;
+        for (x, y) in x_vec.iter().zip(y_vec.iter()) {
+            self.add_com(*x, *y);
+        }
+        Ok(())
+    }
+
+    /// Subtracts the entry-wise sum of pairings from the accumulated sum, or returns an error if
+    /// `x_vec` and `y_vec` have different lengths.
+    pub fn sub_com_sum(
+        &mut self,
+        x_vec: &[Com1<E>],
+        y_vec: &[Com2<E>],
+    ) -> Result<(), PairingSumError> {
+        check_pairing_lengths(x_vec, y_vec)?;
+        for (x, y) in x_vec.iter().zip(y_vec.iter()) {
+            self.sub_com(*x, *y);
+        }
+        Ok(())
+    }
+
+    /// Subtracts the entry-wise sum of pairings with already prepared `y` elements from the accumulated
+    /// sum, or returns an error if `x_vec` and `y_vec` have different lengths.
+    pub fn sub_com_prepared_sum(
+        &mut self,
+        x_vec: &[Com1<E>],
+        y_vec: &[Com2Prepared<E>],
+    ) -> Result<(), PairingSumError> {
+        if x_vec.len() != y_vec.len() {
+            return Err(PairingSumError {
+                x_len: x_vec.len(),
+                y_len: y_vec.len(),
+            });
+        }
+        for (x, y) in x_vec.iter().zip(y_vec.iter()) {
+            let x = -*x;
+            self.0[0].add_prepared_pair(x.0, &y.0);
+            self.0[1].add_prepared_pair(x.0, &y.1);
+            self.0[2].add_prepared_pair(x.1, &y.0);
+            self.0[3].add_prepared_pair(x.1, &y.1);
+        }
+        Ok(())
+    }
+
+    /// Computes the accumulated sum with a single Miller loop and final exponentiation per entry.
+    pub fn finalize(self) -> ComT<E> {
+        let [acc0, acc1, acc2, acc3] = self.0;
+        ComT::<E>(
+            acc0.finalize(),
+            acc1.finalize(),
+            acc2.finalize(),
+            acc3.finalize(),
+        )
+    }
+}
+
+fn check_pairing_lengths<E: Pairing>(
+    x_vec: &[Com1<E>],
+    y_vec: &[Com2<E>],
+) -> Result<(), PairingSumError> {
+    if x_vec.len() != y_vec.len() {
+        return Err(PairingSumError {
+            x_len: x_vec.len(),
+            y_len: y_vec.len(),
+        });
+    }
+    Ok(())
 }
 
 impl<E: Pairing> B<E> for ComT<E> {}
@@ -491,14 +2462,14 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
     }
 
     #[inline]
-    fn pairing_sum(x_vec: &[Com1<E>], y_vec: &[Com2<E>]) -> Self {
-        assert_eq!(x_vec.len(), y_vec.len());
-        Self(
+    fn try_pairing_sum(x_vec: &[Com1<E>], y_vec: &[Com2<E>]) -> Result<Self, PairingSumError> {
+        check_pairing_lengths(x_vec, y_vec)?;
+        Ok(Self(
             E::multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.0)),
             E::multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.1)),
             E::multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.0)),
             E::multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.1)),
-        )
+        ))
     }
 
     fn as_matrix(&self) -> Matrix<PairingOutput<E>> {
@@ -515,6 +2486,14 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
         )
     }
 
+    #[inline]
+    fn batch_linear_map_PPE(z_vec: &[PairingOutput<E>]) -> Vec<Self> {
+        z_vec
+            .iter()
+            .map(|elem| Self::linear_map_PPE(elem))
+            .collect::<Vec<Self>>()
+    }
+
     #[inline]
     fn linear_map_MSMEG1(z: &E::G1Affine, key: &CRS<E>) -> Self {
         Self::pairing(
@@ -523,6 +2502,16 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
         )
     }
 
+    #[inline]
+    fn batch_linear_map_MSMEG1(z_vec: &[E::G1Affine], key: &CRS<E>) -> Vec<Self> {
+        // The scalar linear map of 1 is exactly its (target-independent) base
+        let one_v = key.v2().prepare_scalar_base(&key.g2_gen);
+        z_vec
+            .iter()
+            .map(|elem| Self::pairing(Com1::<E>::linear_map(elem), one_v))
+            .collect::<Vec<Self>>()
+    }
+
     #[inline]
     fn linear_map_MSMEG2(z: &E::G2Affine, key: &CRS<E>) -> Self {
         Self::pairing(
@@ -531,6 +2520,16 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
         )
     }
 
+    #[inline]
+    fn batch_linear_map_MSMEG2(z_vec: &[E::G2Affine], key: &CRS<E>) -> Vec<Self> {
+        // The scalar linear map of 1 is exactly its (target-independent) base
+        let one_u = key.u2().prepare_scalar_base(&key.g1_gen);
+        z_vec
+            .iter()
+            .map(|elem| Self::pairing(one_u, Com2::<E>::linear_map(elem)))
+            .collect::<Vec<Self>>()
+    }
+
     #[inline]
     fn linear_map_quad(z: &E::ScalarField, key: &CRS<E>) -> Self {
         Self::pairing(
@@ -545,8 +2544,8 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
 macro_rules! impl_base_commit_mats {
     (
         $(
-            $com:ident
-        ),*
+            $com:ident, $proj:ident, $affine:ident, $group:ident
+        );*
     ) => {
         // Repeat for each $com
         $(
@@ -588,18 +2587,44 @@ macro_rules! impl_base_commit_mats {
                 type Other = E::ScalarField;
 
                 fn add(&self, other: &Self) -> Self {
-                    assert_eq!(self.len(), other.len());
-                    assert_eq!(self[0].len(), other[0].len());
-                    let m = self.len();
-                    let n = self[0].len();
-                    let mut add = Vec::with_capacity(m);
-                    for i in 0..m {
-                        add.push(Vec::with_capacity(n));
-                        for j in 0..n {
-                            add[i].push(self[i][j].clone() + other[i][j].clone());
-                        }
+                    assert_same_shape("add", self, other);
+                    // Sum in projective coordinates, then convert the whole matrix back to affine with a
+                    // single (batched) field inversion rather than one per entry
+                    let sums: Vec<$proj<E>> = self
+                        .iter()
+                        .flatten()
+                        .zip(other.iter().flatten())
+                        .map(|(lhs, rhs)| $proj::<E>(lhs.0 + rhs.0, lhs.1 + rhs.1))
+                        .collect();
+                    let mut sums = $proj::<E>::normalize_batch(&sums).into_iter();
+                    self.iter()
+                        .map(|row| sums.by_ref().take(row.len()).collect())
+                        .collect()
+                }
+
+                fn add_assign(&mut self, other: &Self) {
+                    assert_same_shape("add", self, other);
+                    let sums: Vec<$proj<E>> = self
+                        .iter()
+                        .flatten()
+                        .zip(other.iter().flatten())
+                        .map(|(lhs, rhs)| $proj::<E>(lhs.0 + rhs.0, lhs.1 + rhs.1))
+                        .collect();
+                    // Write the normalized sums back into the existing rows of `self`
+                    for (elem, sum) in self
+                        .iter_mut()
+                        .flatten()
+                        .zip($proj::<E>::normalize_batch(&sums))
+                    {
+                        *elem = sum;
+                    }
+                }
+
+                fn scaled_add_assign(&mut self, k: &Self::Other, other: &Self) {
+                    assert_same_shape("add", self, other);
+                    for (lhs, rhs) in self.iter_mut().flatten().zip(other.iter().flatten()) {
+                        *lhs += rhs.scalar_mul(k);
                     }
-                    add
                 }
 
                 #[inline]
@@ -614,6 +2639,13 @@ macro_rules! impl_base_commit_mats {
                    .collect::<Vec<Vec<$com<E>>>>()
                 }
 
+                fn into_neg(mut self) -> Self {
+                    for elem in self.iter_mut().flatten() {
+                        *elem = -*elem;
+                    }
+                    self
+                }
+
                 fn scalar_mul(&self, other: &Self::Other) -> Self {
                     let m = self.len();
                     let n = self[0].len();
@@ -627,7 +2659,18 @@ macro_rules! impl_base_commit_mats {
                     smul
                 }
 
+                fn scale_rows(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+                    scale_by(self, scalars, false, |elem, k| elem.scalar_mul(k))
+                }
+
+                fn scale_cols(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+                    scale_by(self, scalars, true, |elem, k| elem.scalar_mul(k))
+                }
+
                 fn transpose(&self) -> Self {
+                    if self.is_empty() {
+                        return vec![];
+                    }
                     let mut trans = Vec::with_capacity(self[0].len());
                     for _ in 0..self[0].len() {
                         trans.push(Vec::with_capacity(self.len()));
@@ -642,6 +2685,10 @@ macro_rules! impl_base_commit_mats {
                     trans
                 }
 
+                fn into_transpose(self) -> Self {
+                    into_transpose_rows(self)
+                }
+
                 fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
                     if self.is_empty() || self[0].is_empty() {
                         return vec![];
@@ -651,46 +2698,38 @@ macro_rules! impl_base_commit_mats {
                     }
 
                     // Check that every row in a and column in b has the same length
-                    assert_eq!(self[0].len(), rhs.len());
+                    assert_mat_mul_shape(self, rhs);
                     let row_dim = self.len();
+                    let col_dim = rhs[0].len();
 
-                    if is_parallel {
-                        let rows: Vec<_> = (0..row_dim)
-                            .into_par_iter()
-                            .map( |i| {
-                                let row = &self[i];
-                                let dim = rhs.len();
-
-                                // Perform multiplication for single row
-                                // Assuming every column in b has the same length
-                                let cols: Vec<_> = (0..rhs[0].len())
-                                    .into_par_iter()
-                                    .map( |j| {
-                                        (0..dim).map( |k| row[k].scalar_mul(&rhs[k][j])).sum()
-                                    })
-                                    .collect();
-
-                                cols
-                            })
-                            .collect();
-
-                        rows
+                    // Each entry is the inner product of a row of a with a column of b, computed as
+                    // one MSM per coordinate over the (split) row of a
+                    let bases: Vec<(Vec<E::$affine>, Vec<E::$affine>)> = self
+                        .iter()
+                        .map(|row| row.iter().map(|com| (com.0, com.1)).unzip())
+                        .collect();
+                    let scalars: Vec<Vec<E::ScalarField>> = (0..col_dim)
+                        .map(|j| rhs.iter().map(|row| row[j]).collect())
+                        .collect();
+                    let entry = |idx: usize| {
+                        let (base, scalar) = (&bases[idx / col_dim], &scalars[idx % col_dim]);
+                        $proj::<E>(
+                            E::$group::msm_unchecked(&base.0, scalar),
+                            E::$group::msm_unchecked(&base.1, scalar),
+                        )
+                    };
+
+                    let prods: Vec<$proj<E>> = if is_parallel {
+                        (0..row_dim * col_dim).into_par_iter().map(entry).collect()
                     } else {
-                        (0..row_dim)
-                            .map( |i| {
-                                let row = &self[i];
-                                let dim = rhs.len();
-
-                                // Perform matrix multiplication for single row
-                                // Assuming every column in b has the same length
-                                (0..rhs[0].len())
-                                    .map( |j| {
-                                        (0..dim).map( |k| row[k].scalar_mul(&rhs[k][j]) ).sum()
-                                    })
-                                    .collect::<Vec<$com<E>>>()
-                            })
-                            .collect::<Vec<Vec<$com<E>>>>()
-                    }
+                        (0..row_dim * col_dim).map(entry).collect()
+                    };
+
+                    // Convert the whole product back to affine at once
+                    $proj::<E>::normalize_batch(&prods)
+                        .chunks(col_dim)
+                        .map(|row| row.to_vec())
+                        .collect()
                 }
 
                 fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
@@ -702,49 +2741,113 @@ macro_rules! impl_base_commit_mats {
                     }
 
                     // Check that every row in a and column in b has the same length
-                    assert_eq!(lhs[0].len(), self.len());
+                    assert_mat_mul_shape(lhs, self);
                     let row_dim = lhs.len();
+                    let col_dim = self[0].len();
 
-                    if is_parallel {
-                        let rows: Vec<_> = (0..row_dim)
-                            .into_par_iter()
-                            .map( |i| {
-                                let row = &lhs[i];
-                                let dim = self.len();
-
-                                // Perform matrix multiplication for single row
-                                let cols: Vec<_> = (0..self[0].len())
-                                    .into_par_iter()
-                                    .map(|j| {
-                                        (0..dim).map( |k| self[k][j].scalar_mul(&row[k])).sum()
-                                    })
-                                    .collect();
-
-                                cols
-                            })
-                            .collect();
+                    // Each entry is the inner product of a row of a with a column of b, computed as
+                    // one MSM per coordinate over the (split) column of b
+                    let bases: Vec<(Vec<E::$affine>, Vec<E::$affine>)> = (0..col_dim)
+                        .map(|j| self.iter().map(|row| (row[j].0, row[j].1)).unzip())
+                        .collect();
+                    let entry = |idx: usize| {
+                        let (scalar, base) = (&lhs[idx / col_dim], &bases[idx % col_dim]);
+                        $proj::<E>(
+                            E::$group::msm_unchecked(&base.0, scalar),
+                            E::$group::msm_unchecked(&base.1, scalar),
+                        )
+                    };
+
+                    let prods: Vec<$proj<E>> = if is_parallel {
+                        (0..row_dim * col_dim).into_par_iter().map(entry).collect()
+                    } else {
+                        (0..row_dim * col_dim).map(entry).collect()
+                    };
+
+                    // Convert the whole product back to affine at once
+                    $proj::<E>::normalize_batch(&prods)
+                        .chunks(col_dim)
+                        .map(|row| row.to_vec())
+                        .collect()
+                }
+            }
 
-                        rows
-                    }
-                    else {
-                        (0..row_dim)
-                            .map( |i| {
-                                let row = &lhs[i];
-                                let dim = self.len();
-                                (0..self[0].len())
-                                    .map( |j| {
-                                        (0..dim).map( |k| self[k][j].scalar_mul(&row[k]) ).sum()
-                                    })
-                                    .collect::<Vec<$com<E>>>()
-                            })
-                            .collect::<Vec<Vec<$com<E>>>>()
-                    }
+            impl<E: Pairing> MatVec<E::ScalarField> for Matrix<$com<E>> {
+                type Output = $com<E>;
+
+                fn mul_vec(&self, v: &[E::ScalarField]) -> Vec<$com<E>> {
+                    assert_mul_vec_shape(self, v);
+                    // One MSM per coordinate over each (split) row
+                    let prods: Vec<$proj<E>> = self
+                        .iter()
+                        .map(|row| {
+                            let base: (Vec<E::$affine>, Vec<E::$affine>) =
+                                row.iter().map(|com| (com.0, com.1)).unzip();
+                            $proj::<E>(
+                                E::$group::msm_unchecked(&base.0, v),
+                                E::$group::msm_unchecked(&base.1, v),
+                            )
+                        })
+                        .collect();
+                    $proj::<E>::normalize_batch(&prods)
+                }
+
+                fn vec_mul(&self, v: &[E::ScalarField]) -> Vec<$com<E>> {
+                    assert_vec_mul_shape(self, v);
+                    // One MSM per coordinate over each (split) column
+                    let prods: Vec<$proj<E>> = (0..self.num_cols())
+                        .map(|j| {
+                            let base: (Vec<E::$affine>, Vec<E::$affine>) =
+                                self.iter().map(|row| (row[j].0, row[j].1)).unzip();
+                            $proj::<E>(
+                                E::$group::msm_unchecked(&base.0, v),
+                                E::$group::msm_unchecked(&base.1, v),
+                            )
+                        })
+                        .collect();
+                    $proj::<E>::normalize_batch(&prods)
+                }
+            }
+
+            impl<E: Pairing> ScalarMatVec<$com<E>> for Matrix<E::ScalarField> {
+                fn mul_com_vec(&self, v: &[$com<E>]) -> Vec<$com<E>> {
+                    assert_mul_vec_shape(self, v);
+                    // The (split) vector is the base of every row's MSM
+                    let base: (Vec<E::$affine>, Vec<E::$affine>) =
+                        v.iter().map(|com| (com.0, com.1)).unzip();
+                    let prods: Vec<$proj<E>> = self
+                        .iter()
+                        .map(|row| {
+                            $proj::<E>(
+                                E::$group::msm_unchecked(&base.0, row),
+                                E::$group::msm_unchecked(&base.1, row),
+                            )
+                        })
+                        .collect();
+                    $proj::<E>::normalize_batch(&prods)
+                }
+
+                fn com_vec_mul(&self, v: &[$com<E>]) -> Vec<$com<E>> {
+                    assert_vec_mul_shape(self, v);
+                    // The (split) vector is the base of every column's MSM
+                    let base: (Vec<E::$affine>, Vec<E::$affine>) =
+                        v.iter().map(|com| (com.0, com.1)).unzip();
+                    let prods: Vec<$proj<E>> = (0..self.num_cols())
+                        .map(|j| {
+                            let scalars: Vec<E::ScalarField> = self.col(j).copied().collect();
+                            $proj::<E>(
+                                E::$group::msm_unchecked(&base.0, &scalars),
+                                E::$group::msm_unchecked(&base.1, &scalars),
+                            )
+                        })
+                        .collect();
+                    $proj::<E>::normalize_batch(&prods)
                 }
             }
         )*
     }
 }
-impl_base_commit_mats![Com1, Com2];
+impl_base_commit_mats![Com1, Com1Projective, G1Affine, G1; Com2, Com2Projective, G2Affine, G2];
 
 /*
 // Implements scalar point-multiplication for matrices of commitment group elements
@@ -765,25 +2868,50 @@ impl MulAssign<F> for Matrix<F> {
     }
 }
 */
+impl<F: Field> MatVec<F> for Matrix<F> {
+    type Output = F;
+
+    fn mul_vec(&self, v: &[F]) -> Vec<F> {
+        assert_mul_vec_shape(self, v);
+        self.iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(a, b)| *a * b).sum())
+            .collect()
+    }
+
+    fn vec_mul(&self, v: &[F]) -> Vec<F> {
+        assert_vec_mul_shape(self, v);
+        (0..self.num_cols())
+            .map(|j| self.col(j).zip(v.iter()).map(|(a, b)| *a * b).sum())
+            .collect()
+    }
+}
+
 impl<F: Field> Mat<F> for Matrix<F> {
     type Other = F;
 
     fn add(&self, other: &Self) -> Self {
-        assert_eq!(self.len(), other.len());
-        assert_eq!(self[0].len(), other[0].len());
-        let m = self.len();
-        let n = self[0].len();
-        let mut add = Vec::with_capacity(m);
-        for i in 0..m {
-            add.push(Vec::with_capacity(n));
-            for j in 0..n {
-                add[i].push(self[i][j] + other[i][j]);
-            }
-        }
-        add
+        assert_same_shape("add", self, other);
+        self.iter()
+            .zip(other)
+            .map(|(lhs, rhs)| lhs.iter().zip(rhs).map(|(a, b)| *a + b).collect())
+            .collect()
     }
 
     #[inline]
+    fn add_assign(&mut self, other: &Self) {
+        assert_same_shape("add", self, other);
+        for (lhs, rhs) in self.iter_mut().flatten().zip(other.iter().flatten()) {
+            *lhs += rhs;
+        }
+    }
+
+    fn scaled_add_assign(&mut self, k: &Self::Other, other: &Self) {
+        assert_same_shape("add", self, other);
+        for (lhs, rhs) in self.iter_mut().flatten().zip(other.iter().flatten()) {
+            *lhs += *rhs * k;
+        }
+    }
+
     fn neg(&self) -> Self {
         (0..self.len())
             .map(|i| {
@@ -793,6 +2921,14 @@ impl<F: Field> Mat<F> for Matrix<F> {
             .collect::<Vec<Vec<F>>>()
     }
 
+    fn into_neg(mut self) -> Self {
+        for elem in self.iter_mut().flatten() {
+            elem.neg_in_place();
+        }
+        self
+    }
+
+    #[allow(clippy::needless_range_loop)]
     fn scalar_mul(&self, other: &Self::Other) -> Self {
         let m = self.len();
         let n = self[0].len();
@@ -806,7 +2942,18 @@ impl<F: Field> Mat<F> for Matrix<F> {
         smul
     }
 
+    fn scale_rows(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+        scale_by(self, scalars, false, |elem, k| *elem * k)
+    }
+
+    fn scale_cols(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+        scale_by(self, scalars, true, |elem, k| *elem * k)
+    }
+
     fn transpose(&self) -> Self {
+        if self.is_empty() {
+            return vec![];
+        }
         let mut trans = Vec::with_capacity(self[0].len());
         for _ in 0..self[0].len() {
             trans.push(Vec::with_capacity(self.len()));
@@ -821,6 +2968,10 @@ impl<F: Field> Mat<F> for Matrix<F> {
         trans
     }
 
+    fn into_transpose(self) -> Self {
+        into_transpose_rows(self)
+    }
+
     fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
         if self.is_empty() || self[0].is_empty() {
             return vec![];
@@ -912,72 +3063,686 @@ impl<F: Field> Mat<F> for Matrix<F> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    #![allow(non_snake_case)]
-
-    use super::*;
+// Reduces the square matrix `a` to the identity by row operations, applying the same operations to the
+// rows of `b`, and returns the determinant of `a`. Stops early (and returns zero) if `a` is singular.
+fn gauss_jordan<F: Field>(a: &mut Matrix<F>, b: &mut Matrix<F>) -> F {
+    let n = a.len();
+    let mut det = F::one();
+    for col in 0..n {
+        let Some(pivot) = (col..n).find(|&row| !a[row][col].is_zero()) else {
+            return F::zero();
+        };
+        if pivot != col {
+            a.swap(pivot, col);
+            b.swap(pivot, col);
+            det = -det;
+        }
+        det *= a[col][col];
+        let inv = a[col][col].inverse().expect("the pivot is nonzero");
+        a[col].iter_mut().for_each(|x| *x *= inv);
+        b[col].iter_mut().for_each(|x| *x *= inv);
+
+        let (a_pivot, b_pivot) = (a[col].clone(), b[col].clone());
+        for row in (0..n).filter(|&row| row != col) {
+            let factor = a[row][col];
+            if factor.is_zero() {
+                continue;
+            }
+            a[row]
+                .iter_mut()
+                .zip(a_pivot.iter())
+                .for_each(|(x, p)| *x -= factor * p);
+            b[row]
+                .iter_mut()
+                .zip(b_pivot.iter())
+                .for_each(|(x, p)| *x -= factor * p);
+        }
+    }
+    det
+}
 
-    mod SXDH_com_group {
+impl<F: Field> SquareMat<F> for Matrix<F> {
+    fn determinant(&self) -> Result<F, ShapeError> {
+        check_shape(self, self.len(), self.len())?;
+        Ok(gauss_jordan(
+            &mut self.clone(),
+            &mut vec![vec![]; self.len()],
+        ))
+    }
 
-        use ark_bls12_381::Bls12_381 as F;
-        use ark_ec::{
-            pairing::{Pairing, PairingOutput},
-            AffineRepr, CurveGroup,
-        };
-        use ark_ff::UniformRand;
-        use ark_std::ops::Mul;
-        use ark_std::test_rng;
+    fn inverse(&self) -> Result<Option<Self>, ShapeError> {
+        let n = self.len();
+        check_shape(self, n, n)?;
+        let mut inv = MatrixExt::from_fn((n, n), |i, j| if i == j { F::one() } else { F::zero() });
+        if gauss_jordan(&mut self.clone(), &mut inv).is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(inv))
+    }
 
-        use crate::AbstractCrs;
+    fn solve(&self, rhs: &[F]) -> Result<Option<Vec<F>>, ShapeError> {
+        let n = self.len();
+        check_shape(self, n, n)?;
+        if rhs.len() != n {
+            return Err(ShapeError { expected: (n, 1) });
+        }
+        let mut x: Matrix<F> = rhs.iter().map(|b| vec![*b]).collect();
+        if gauss_jordan(&mut self.clone(), &mut x).is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(x.into_iter().map(|row| row[0]).collect()))
+    }
+}
 
-        use super::*;
+// Matrices of ComT, e.g. for accumulating the contributions of several equations, with the same
+// conventions as the matrices of Com1 and Com2
+impl<E: Pairing> Mat<ComT<E>> for Matrix<ComT<E>> {
+    type Other = E::ScalarField;
 
-        type G1Affine = <F as Pairing>::G1Affine;
-        type G1Projective = <F as Pairing>::G1;
-        type G2Affine = <F as Pairing>::G2Affine;
-        type G2Projective = <F as Pairing>::G2;
-        type GT = PairingOutput<F>;
-        type Fr = <F as Pairing>::ScalarField;
+    fn add(&self, other: &Self) -> Self {
+        let mut add = self.clone();
+        add.add_assign(other);
+        add
+    }
 
-        #[test]
-        fn test_B1_add_zero() {
-            let mut rng = test_rng();
-            let a = Com1::<F>(
-                G1Projective::rand(&mut rng).into_affine(),
-                G1Projective::rand(&mut rng).into_affine(),
-            );
-            let zero = Com1::<F>(G1Affine::zero(), G1Affine::zero());
-            let asub = a + zero;
+    fn add_assign(&mut self, other: &Self) {
+        assert_same_shape("add", self, other);
+        for (lhs, rhs) in self.iter_mut().flatten().zip(other.iter().flatten()) {
+            *lhs += rhs;
+        }
+    }
 
-            assert_eq!(zero, Com1::<F>::zero());
-            assert!(zero.is_zero());
-            assert_eq!(a, asub);
+    fn scaled_add_assign(&mut self, k: &Self::Other, other: &Self) {
+        assert_same_shape("add", self, other);
+        for (lhs, rhs) in self.iter_mut().flatten().zip(other.iter().flatten()) {
+            *lhs += rhs.scalar_mul(k);
         }
+    }
 
-        #[allow(non_snake_case)]
-        #[test]
-        fn test_B2_add_zero() {
-            let mut rng = test_rng();
-            let a = Com2::<F>(
-                G2Projective::rand(&mut rng).into_affine(),
-                G2Projective::rand(&mut rng).into_affine(),
-            );
-            let zero = Com2::<F>(G2Affine::zero(), G2Affine::zero());
-            let asub = a + zero;
+    #[inline]
+    fn neg(&self) -> Self {
+        self.map(|elem| -*elem)
+    }
 
-            assert_eq!(zero, Com2::<F>::zero());
-            assert!(zero.is_zero());
-            assert_eq!(a, asub);
+    fn into_neg(mut self) -> Self {
+        for elem in self.iter_mut().flatten() {
+            *elem = -*elem;
         }
+        self
+    }
 
-        #[allow(non_snake_case)]
-        #[test]
-        fn test_BT_add_zero() {
-            let mut rng = test_rng();
-            let a = ComT::<F>(
-                GT::rand(&mut rng),
-                GT::rand(&mut rng),
+    fn scalar_mul(&self, other: &Self::Other) -> Self {
+        self.map(|elem| elem.scalar_mul(other))
+    }
+
+    fn scale_rows(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+        scale_by(self, scalars, false, |elem, k| elem.scalar_mul(k))
+    }
+
+    fn scale_cols(&self, scalars: &[Self::Other]) -> Result<Self, ShapeError> {
+        scale_by(self, scalars, true, |elem, k| elem.scalar_mul(k))
+    }
+
+    fn transpose(&self) -> Self {
+        (0..self.num_cols())
+            .map(|j| self.col(j).copied().collect())
+            .collect()
+    }
+
+    fn into_transpose(self) -> Self {
+        into_transpose_rows(self)
+    }
+
+    fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
+        if self.is_empty() || self[0].is_empty() {
+            return vec![];
+        }
+        if rhs.is_empty() || rhs[0].is_empty() {
+            return vec![];
+        }
+
+        // Check that every row in a and column in b has the same length
+        assert_eq!(self[0].len(), rhs.len());
+        let (row_dim, col_dim) = (self.len(), rhs[0].len());
+        let entry = |idx: usize| {
+            let (i, j) = (idx / col_dim, idx % col_dim);
+            (0..rhs.len())
+                .map(|k| self[i][k].scalar_mul(&rhs[k][j]))
+                .sum()
+        };
+        let prods: Vec<ComT<E>> = if is_parallel {
+            (0..row_dim * col_dim).into_par_iter().map(entry).collect()
+        } else {
+            (0..row_dim * col_dim).map(entry).collect()
+        };
+        prods.chunks(col_dim).map(|row| row.to_vec()).collect()
+    }
+
+    fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
+        if lhs.is_empty() || lhs[0].is_empty() {
+            return vec![];
+        }
+        if self.is_empty() || self[0].is_empty() {
+            return vec![];
+        }
+
+        // Check that every row in a and column in b has the same length
+        assert_eq!(lhs[0].len(), self.len());
+        let (row_dim, col_dim) = (lhs.len(), self[0].len());
+        let entry = |idx: usize| {
+            let (i, j) = (idx / col_dim, idx % col_dim);
+            (0..self.len())
+                .map(|k| self[k][j].scalar_mul(&lhs[i][k]))
+                .sum()
+        };
+        let prods: Vec<ComT<E>> = if is_parallel {
+            (0..row_dim * col_dim).into_par_iter().map(entry).collect()
+        } else {
+            (0..row_dim * col_dim).map(entry).collect()
+        };
+        prods.chunks(col_dim).map(|row| row.to_vec()).collect()
+    }
+}
+
+/// A [`Matrix`](self::Matrix) of scalars with the `std::ops` operators, which cannot be implemented for
+/// the `Matrix` alias itself (see its documentation).
+///
+/// The operators delegate to the [`Mat`](self::Mat) methods, panicking with the shapes of the operands
+/// if they do not fit together. As in `Mat`, only matrices of the same shape can be added, and an empty
+/// matrix yields an empty product.
+///
+/// ```
+/// use ark_bls12_381::Fr;
+/// use ark_ff::One;
+/// use groth_sahai::data_structures::FieldMatrix;
+///
+/// let one = Fr::one();
+/// let two = one + one;
+/// let a = FieldMatrix(vec![vec![one, two]]);
+/// let b = FieldMatrix(vec![vec![one], vec![one]]);
+/// let c = FieldMatrix(vec![vec![two]]);
+///
+/// // [1 2] * [1 1]^T + [2] = [5]
+/// let res = &a * &b + &c;
+/// assert_eq!(res.0, vec![vec![two + two + one]]);
+/// assert_eq!((-&res * &two).0, vec![vec![-(two * (two + two + one))]]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FieldMatrix<F: Field>(pub Matrix<F>);
+
+impl<F: Field> From<Matrix<F>> for FieldMatrix<F> {
+    fn from(mat: Matrix<F>) -> Self {
+        Self(mat)
+    }
+}
+
+impl<F: Field> From<FieldMatrix<F>> for Matrix<F> {
+    fn from(mat: FieldMatrix<F>) -> Self {
+        mat.0
+    }
+}
+
+impl<F: Field> Deref for FieldMatrix<F> {
+    type Target = Matrix<F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Conversions to and from [`ndarray`] arrays, e.g. to run numerical code over GS scalar matrices.
+///
+/// A [`Matrix`](self::Matrix) keeps every row in its own vector, so its entries are not contiguous and
+/// it cannot be viewed as an [`ArrayView2`](ndarray::ArrayView2) without copying. Instead,
+/// [`to_array`](FieldMatrix::to_array) copies the entries once into an array in standard layout
+/// (i.e. row-major and contiguous), which can then be viewed and operated on by ndarray without
+/// further copies.
+#[cfg(feature = "ndarray")]
+impl<F: Field> FieldMatrix<F> {
+    /// Copies the entries into an array in standard (row-major) layout, or returns an error if the
+    /// rows have different lengths. The empty matrix becomes a `0 x 0` array.
+    pub fn to_array(&self) -> Result<ndarray::Array2<F>, MatrixError> {
+        let (rows, cols) = (self.0.len(), self.0.num_cols());
+        if let Some((row, found)) = self
+            .0
+            .iter()
+            .map(|row| row.len())
+            .enumerate()
+            .find(|(_, len)| *len != cols)
+        {
+            return Err(MatrixError::Ragged {
+                row,
+                expected: cols,
+                found,
+            });
+        }
+        let entries: Vec<F> = self.0.iter().flatten().copied().collect();
+        Ok(ndarray::Array2::from_shape_vec((rows, cols), entries)
+            .expect("the entries of a rectangular matrix fill its shape"))
+    }
+
+    /// Copies the entries of a view (in any memory layout) into a matrix.
+    pub fn from_view(view: ndarray::ArrayView2<'_, F>) -> Self {
+        Self(view.rows().into_iter().map(|row| row.to_vec()).collect())
+    }
+}
+
+/// Validates an array as a matrix as in [`try_from_vecs`](MatrixExt::try_from_vecs), i.e. it must
+/// have at least one row (its rows always have the same length).
+#[cfg(feature = "ndarray")]
+impl<F: Field> TryFrom<ndarray::Array2<F>> for FieldMatrix<F> {
+    type Error = MatrixError;
+
+    fn try_from(array: ndarray::Array2<F>) -> Result<Self, Self::Error> {
+        if array.nrows() == 0 {
+            return Err(MatrixError::Empty);
+        }
+        Ok(Self::from_view(array.view()))
+    }
+}
+
+// Panics unless both matrices have the same number of rows, and each row the same length in both.
+fn assert_same_shape<F>(op: &str, lhs: &Matrix<F>, rhs: &Matrix<F>) {
+    assert!(
+        lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.len() == r.len()),
+        "cannot {} a {} x {} matrix and a {} x {} matrix",
+        op,
+        lhs.len(),
+        lhs.num_cols(),
+        rhs.len(),
+        rhs.num_cols()
+    );
+}
+
+macro_rules! impl_field_matrix_ops {
+    (
+        $(
+            $lhs:ty
+        ),*
+    ) => {
+        // Repeat for both the owned and borrowed left-hand side
+        $(
+            impl<F: Field> Add<&FieldMatrix<F>> for $lhs {
+                type Output = FieldMatrix<F>;
+
+                fn add(self, rhs: &FieldMatrix<F>) -> FieldMatrix<F> {
+                    assert_same_shape("add", &self.0, &rhs.0);
+                    FieldMatrix(Mat::add(&self.0, &rhs.0))
+                }
+            }
+
+            impl<F: Field> Sub<&FieldMatrix<F>> for $lhs {
+                type Output = FieldMatrix<F>;
+
+                fn sub(self, rhs: &FieldMatrix<F>) -> FieldMatrix<F> {
+                    assert_same_shape("subtract", &self.0, &rhs.0);
+                    FieldMatrix(Mat::add(&self.0, &Mat::neg(&rhs.0)))
+                }
+            }
+
+            impl<F: Field> Neg for $lhs {
+                type Output = FieldMatrix<F>;
+
+                fn neg(self) -> FieldMatrix<F> {
+                    FieldMatrix(Mat::neg(&self.0))
+                }
+            }
+
+            /// The matrix product `self * rhs`.
+            impl<F: Field> Mul<&FieldMatrix<F>> for $lhs {
+                type Output = FieldMatrix<F>;
+
+                fn mul(self, rhs: &FieldMatrix<F>) -> FieldMatrix<F> {
+                    if !self.0.is_empty() && !rhs.0.is_empty() {
+                        assert!(
+                            self.0.num_cols() == rhs.0.len(),
+                            "cannot multiply a {} x {} matrix by a {} x {} matrix",
+                            self.0.len(),
+                            self.0.num_cols(),
+                            rhs.0.len(),
+                            rhs.0.num_cols()
+                        );
+                    }
+                    FieldMatrix(self.0.right_mul(&rhs.0, false))
+                }
+            }
+
+            /// Scales every entry by `rhs`.
+            impl<F: Field> Mul<&F> for $lhs {
+                type Output = FieldMatrix<F>;
+
+                fn mul(self, rhs: &F) -> FieldMatrix<F> {
+                    if self.0.is_empty() {
+                        return FieldMatrix(vec![]);
+                    }
+                    FieldMatrix(self.0.scalar_mul(rhs))
+                }
+            }
+        )*
+    }
+}
+impl_field_matrix_ops!(FieldMatrix<F>, &FieldMatrix<F>);
+
+/// A sparse scalar matrix, stored as `(row, col, value)` triplets in row-major order.
+///
+/// Only non-zero entries are stored, so that e.g. a mostly-empty `gamma` of a large statement can be
+/// scaled, transposed and multiplied against dense [`Matrix`](self::Matrix) operands in time proportional
+/// to its number of non-zero entries rather than its full `m x n` size. Products against dense matrices
+/// of commitment group elements go through the [`Mat`](self::Mat) implementation of the dense operand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMatrix<F> {
+    shape: (usize, usize),
+    entries: Vec<(usize, usize, F)>,
+}
+
+impl<F: Field> SparseMatrix<F> {
+    /// The all-zero matrix of the given shape.
+    pub fn zeros(shape: (usize, usize)) -> Self {
+        Self {
+            shape,
+            entries: vec![],
+        }
+    }
+
+    /// Builds a matrix of the given shape from `(row, col, value)` triplets in any order, summing the
+    /// values of repeated positions and dropping zeros, or returns an error if a triplet is out of range.
+    pub fn try_from_triplets(
+        shape: (usize, usize),
+        mut triplets: Vec<(usize, usize, F)>,
+    ) -> Result<Self, ShapeError> {
+        if triplets
+            .iter()
+            .any(|(i, j, _)| *i >= shape.0 || *j >= shape.1)
+        {
+            return Err(ShapeError { expected: shape });
+        }
+        triplets.sort_by_key(|(i, j, _)| (*i, *j));
+
+        let mut entries: Vec<(usize, usize, F)> = Vec::with_capacity(triplets.len());
+        for (i, j, value) in triplets {
+            match entries.last_mut() {
+                Some(last) if last.0 == i && last.1 == j => last.2 += value,
+                _ => entries.push((i, j, value)),
+            }
+        }
+        entries.retain(|(_, _, value)| !value.is_zero());
+        Ok(Self { shape, entries })
+    }
+
+    /// Keeps the non-zero entries of a dense matrix, which is assumed to have rows of equal length.
+    pub fn from_dense(mat: &Matrix<F>) -> Self {
+        let entries = mat
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(move |(j, value)| (i, j, *value))
+            })
+            .collect();
+        Self {
+            shape: (mat.len(), mat.num_cols()),
+            entries,
+        }
+    }
+
+    /// The equivalent dense matrix.
+    pub fn to_dense(&self) -> Matrix<F> {
+        let mut mat = vec![vec![F::zero(); self.shape.1]; self.shape.0];
+        for (i, j, value) in self.entries.iter() {
+            mat[*i][*j] = *value;
+        }
+        mat
+    }
+
+    /// The `(rows, cols)` shape of the matrix.
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// The number of stored (i.e. non-zero) entries.
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterates over the non-zero entries as `(row, col, value)` in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &F)> {
+        self.entries.iter().map(|(i, j, value)| (*i, *j, value))
+    }
+
+    pub fn scalar_mul(&self, other: &F) -> Self {
+        if other.is_zero() {
+            return Self::zeros(self.shape);
+        }
+        Self {
+            shape: self.shape,
+            entries: self
+                .entries
+                .iter()
+                .map(|(i, j, value)| (*i, *j, *value * other))
+                .collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut entries: Vec<(usize, usize, F)> = self
+            .entries
+            .iter()
+            .map(|(i, j, value)| (*j, *i, *value))
+            .collect();
+        entries.sort_by_key(|(i, j, _)| (*i, *j));
+        Self {
+            shape: (self.shape.1, self.shape.0),
+            entries,
+        }
+    }
+
+    /// The matrix product `self * rhs` against a dense matrix of scalars or commitment group elements.
+    ///
+    /// Each non-zero entry `self[i][k]` adds the row `rhs[k]`, scaled by it, to the `i`-th row of the product.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` does not have as many rows as `self` has columns.
+    pub fn right_mul<C>(&self, rhs: &Matrix<C>) -> Matrix<C>
+    where
+        C: Clone + Zero + AddAssign + for<'a> Mul<&'a F, Output = C>,
+        Matrix<C>: Mat<C, Other = F>,
+    {
+        assert_eq!(self.shape.1, rhs.len());
+        let mut prod: Matrix<C> = vec![vec![C::zero(); rhs.num_cols()]; self.shape.0];
+        for (i, k, value) in self.entries.iter() {
+            // Accumulated in place, entry by entry
+            for (acc, elem) in prod[*i].iter_mut().zip(rhs[*k].iter()) {
+                *acc += elem.clone() * value;
+            }
+        }
+        prod
+    }
+
+    /// The matrix product `lhs * self` against a dense matrix of scalars or commitment group elements,
+    /// computed as `(self^T * lhs^T)^T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` does not have as many columns as `self` has rows.
+    pub fn left_mul<C>(&self, lhs: &Matrix<C>) -> Matrix<C>
+    where
+        C: Clone + Zero + AddAssign + for<'a> Mul<&'a F, Output = C>,
+        Matrix<C>: Mat<C, Other = F>,
+    {
+        if lhs.is_empty() {
+            return vec![];
+        }
+        assert_eq!(lhs.num_cols(), self.shape.0);
+        if self.shape.0 == 0 || self.shape.1 == 0 {
+            // Transposing would lose the shape of the product
+            return vec![vec![C::zero(); self.shape.1]; lhs.len()];
+        }
+        self.transpose()
+            .right_mul(&lhs.transpose())
+            .into_transpose()
+    }
+}
+
+impl<F: Field> From<&Matrix<F>> for SparseMatrix<F> {
+    fn from(mat: &Matrix<F>) -> Self {
+        Self::from_dense(mat)
+    }
+}
+
+impl<F: Field> From<SparseMatrix<F>> for Matrix<F> {
+    fn from(mat: SparseMatrix<F>) -> Self {
+        mat.to_dense()
+    }
+}
+
+// Triplets must be in range, strictly increasing in row-major order and non-zero.
+impl<F: Field> Valid for SparseMatrix<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        let in_range = self
+            .entries
+            .iter()
+            .all(|(i, j, value)| *i < self.shape.0 && *j < self.shape.1 && !value.is_zero());
+        let ordered = self
+            .entries
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1));
+        if in_range && ordered {
+            Ok(())
+        } else {
+            Err(SerializationError::InvalidData)
+        }
+    }
+}
+
+impl<F: Field> CanonicalSerialize for SparseMatrix<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.shape.0.serialize_with_mode(&mut writer, compress)?;
+        self.shape.1.serialize_with_mode(&mut writer, compress)?;
+        self.entries
+            .len()
+            .serialize_with_mode(&mut writer, compress)?;
+        for (i, j, value) in self.entries.iter() {
+            i.serialize_with_mode(&mut writer, compress)?;
+            j.serialize_with_mode(&mut writer, compress)?;
+            value.serialize_with_mode(&mut writer, compress)?;
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        3 * 0usize.serialized_size(compress)
+            + self
+                .entries
+                .iter()
+                .map(|(i, j, value)| {
+                    i.serialized_size(compress)
+                        + j.serialized_size(compress)
+                        + value.serialized_size(compress)
+                })
+                .sum::<usize>()
+    }
+}
+
+impl<F: Field> CanonicalDeserialize for SparseMatrix<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let rows = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let cols = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let nnz = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let mut entries = Vec::new();
+        for _ in 0..nnz {
+            let i = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let j = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let value = F::deserialize_with_mode(&mut reader, compress, validate)?;
+            entries.push((i, j, value));
+        }
+        let mat = Self {
+            shape: (rows, cols),
+            entries,
+        };
+        if let Validate::Yes = validate {
+            mat.check()?;
+        }
+        Ok(mat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    mod SXDH_com_group {
+
+        use ark_bls12_381::Bls12_381 as F;
+        use ark_ec::{
+            pairing::{Pairing, PairingOutput},
+            AffineRepr, CurveGroup,
+        };
+        use ark_ff::UniformRand;
+        use ark_std::ops::Mul;
+        use ark_std::test_rng;
+
+        use crate::AbstractCrs;
+
+        use super::*;
+
+        type G1Affine = <F as Pairing>::G1Affine;
+        type G1Projective = <F as Pairing>::G1;
+        type G2Affine = <F as Pairing>::G2Affine;
+        type G2Projective = <F as Pairing>::G2;
+        type GT = PairingOutput<F>;
+        type Fr = <F as Pairing>::ScalarField;
+
+        #[test]
+        fn test_B1_add_zero() {
+            let mut rng = test_rng();
+            let a = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let zero = Com1::<F>(G1Affine::zero(), G1Affine::zero());
+            let asub = a + zero;
+
+            assert_eq!(zero, Com1::<F>::zero());
+            assert!(zero.is_zero());
+            assert_eq!(a, asub);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B2_add_zero() {
+            let mut rng = test_rng();
+            let a = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let zero = Com2::<F>(G2Affine::zero(), G2Affine::zero());
+            let asub = a + zero;
+
+            assert_eq!(zero, Com2::<F>::zero());
+            assert!(zero.is_zero());
+            assert_eq!(a, asub);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_add_zero() {
+            let mut rng = test_rng();
+            let a = ComT::<F>(
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
                 GT::rand(&mut rng),
                 GT::rand(&mut rng),
             );
@@ -1073,6 +3838,49 @@ mod tests {
             assert_eq!(abc, a + b + c);
         }
 
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B_projective() {
+            let mut rng = test_rng();
+            let a = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b = Com1::<F>(G1Affine::zero(), G1Projective::rand(&mut rng).into_affine());
+            let c = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let scalar = Fr::rand(&mut rng);
+
+            let a_proj = Com1Projective::<F>::from(a);
+            let b_proj = Com1Projective::<F>::from(b);
+            let c_proj = Com2Projective::<F>::from(c);
+            assert_eq!(Com1::<F>::from(a_proj), a);
+            assert_eq!(Com2::<F>::from(c_proj), c);
+            assert_eq!(Com1::<F>::from(a_proj + b_proj), a + b);
+            assert_eq!(
+                Com1::<F>::from(a_proj.scalar_mul(&scalar)),
+                a.scalar_mul(&scalar)
+            );
+            assert_eq!(
+                Com2::<F>::from(c_proj.scalar_mul(&scalar)),
+                c.scalar_mul(&scalar)
+            );
+
+            let mut acc = Com1Projective::<F>::zero();
+            assert!(acc.is_zero());
+            acc += a_proj;
+            acc += b_proj;
+            assert_eq!(Com1::<F>::from(acc), a + b);
+
+            assert_eq!(
+                Com1Projective::<F>::normalize_batch(&[a_proj, acc, Com1Projective::zero()]),
+                vec![a, a + b, Com1::<F>::zero()]
+            );
+            assert!(Com2Projective::<F>::normalize_batch(&[]).is_empty());
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B2_sum() {
@@ -1230,6 +4038,83 @@ mod tests {
             assert_eq!(ab, -ba);
         }
 
+        // Accumulates a slice by reference, as generic code bounded only by the reference operators would
+        fn sum_by_ref<T: Zero + for<'a> AddAssign<&'a T>>(elems: &[T]) -> T {
+            let mut acc = T::zero();
+            for elem in elems {
+                acc += elem;
+            }
+            acc
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B_assign_ref() {
+            let mut rng = test_rng();
+            let coms1: Vec<Com1<F>> = (0..3)
+                .map(|_| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+            let coms2: Vec<Com2<F>> = (0..3)
+                .map(|_| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+
+            let mut acc = coms1[0];
+            acc += &coms1[1];
+            assert_eq!(acc, coms1[0] + coms1[1]);
+            acc -= &coms1[2];
+            assert_eq!(acc, coms1[0] + coms1[1] - coms1[2]);
+            acc -= &acc.clone();
+            assert!(acc.is_zero());
+
+            let expected: Com1<F> = coms1.iter().copied().sum();
+            assert_eq!(sum_by_ref(&coms1), expected);
+            assert_eq!(coms1.iter().sum::<Com1<F>>(), expected);
+            let expected: Com2<F> = coms2.iter().copied().sum();
+            assert_eq!(sum_by_ref(&coms2), expected);
+            assert_eq!(coms2.iter().sum::<Com2<F>>(), expected);
+
+            let mut acc = coms2[0];
+            acc -= &coms2[1];
+            assert_eq!(acc, coms2[0] - coms2[1]);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_assign_ref() {
+            let mut rng = test_rng();
+            let coms: Vec<ComT<F>> = (0..3)
+                .map(|_| {
+                    ComT::<F>(
+                        GT::rand(&mut rng),
+                        GT::rand(&mut rng),
+                        GT::rand(&mut rng),
+                        GT::rand(&mut rng),
+                    )
+                })
+                .collect();
+
+            let mut acc = coms[0];
+            acc += &coms[1];
+            assert_eq!(acc, coms[0] + coms[1]);
+            acc -= &coms[2];
+            assert_eq!(acc, coms[0] + coms[1] - coms[2]);
+
+            let expected = coms[0] + coms[1] + coms[2];
+            assert_eq!(sum_by_ref(&coms), expected);
+            assert_eq!(coms.iter().sum::<ComT<F>>(), expected);
+            assert_eq!(coms.into_iter().sum::<ComT<F>>(), expected);
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B1_scalar_mul() {
@@ -1264,6 +4149,53 @@ mod tests {
             assert_eq!(bres, bexp);
         }
 
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B_mul_context() {
+            let mut rng = test_rng();
+            let b1 = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b2 = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let mut scalars = vec![Fr::zero(), Fr::one(), -Fr::one()];
+            scalars.extend((0..61).map(|_| Fr::rand(&mut rng)));
+
+            // Both below and above the threshold for a larger window
+            for num_scalars in [1, scalars.len()] {
+                let ctx1 = Com1MulContext::<F>::new(&b1, num_scalars);
+                let ctx2 = Com2MulContext::<F>::new(&b2, num_scalars);
+                let exp1: Vec<Com1<F>> = scalars.iter().map(|k| b1.scalar_mul(k)).collect();
+                let exp2: Vec<Com2<F>> = scalars.iter().map(|k| b2.scalar_mul(k)).collect();
+
+                assert_eq!(ctx1.batch_mul(&scalars), exp1);
+                assert_eq!(ctx2.batch_mul(&scalars), exp2);
+                for (k, (e1, e2)) in scalars.iter().zip(exp1.iter().zip(exp2.iter())) {
+                    assert_eq!(ctx1.mul(k), *e1);
+                    assert_eq!(ctx2.mul(k), *e2);
+                }
+            }
+            assert_eq!(
+                Com1MulContext::<F>::new(&b1, 1).mul(&Fr::zero()),
+                Com1::zero()
+            );
+            assert_eq!(Com1MulContext::<F>::new(&b1, 1).mul(&Fr::one()), b1);
+            assert!(Com2MulContext::<F>::new(&b2, 0).batch_mul(&[]).is_empty());
+
+            // A base with an identity entry, as for the linear map
+            let lin = Com1::<F>::linear_map(&b1.1);
+            assert_eq!(
+                Com1MulContext::<F>::new(&lin, 3).batch_mul(&scalars[3..6]),
+                scalars[3..6]
+                    .iter()
+                    .map(|k| lin.scalar_mul(k))
+                    .collect::<Vec<_>>()
+            );
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B1_serde() {
@@ -1406,6 +4338,137 @@ mod tests {
             assert_eq!(exp, res);
         }
 
+        #[test]
+        fn test_B_try_pairing_sum() {
+            let mut rng = test_rng();
+            let x = vec![
+                Com1::<F>(
+                    G1Projective::rand(&mut rng).into_affine(),
+                    G1Projective::rand(&mut rng).into_affine(),
+                ),
+                Com1::<F>(
+                    G1Projective::rand(&mut rng).into_affine(),
+                    G1Projective::rand(&mut rng).into_affine(),
+                ),
+            ];
+            let y = vec![Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            )];
+
+            assert_eq!(
+                ComT::<F>::try_pairing_sum(&x[..1], &y),
+                Ok(ComT::<F>::pairing_sum(&x[..1], &y))
+            );
+            assert_eq!(
+                ComT::<F>::try_pairing_sum(&x, &y),
+                Err(PairingSumError { x_len: 2, y_len: 1 })
+            );
+        }
+
+        #[test]
+        fn test_miller_accumulator() {
+            let mut rng = test_rng();
+            let vec_g1: Vec<G1Affine> = (0..3)
+                .map(|_| G1Projective::rand(&mut rng).into_affine())
+                .collect();
+            let vec_g2: Vec<G2Affine> = (0..3)
+                .map(|_| G2Projective::rand(&mut rng).into_affine())
+                .collect();
+
+            let mut acc = MillerAccumulator::<F>::new();
+            assert!(acc.is_empty());
+            acc.add_pair(vec_g1[0], vec_g2[0]);
+            acc.add_pair(vec_g1[1], vec_g2[1]);
+            acc.sub_pair(vec_g1[2], vec_g2[2]);
+            assert_eq!(acc.len(), 3);
+
+            let exp: GT = F::pairing(vec_g1[0], vec_g2[0]) + F::pairing(vec_g1[1], vec_g2[1])
+                - F::pairing(vec_g1[2], vec_g2[2]);
+            assert_eq!(acc.finalize(), exp);
+            assert_eq!(MillerAccumulator::<F>::new().finalize(), GT::zero());
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_com_miller_accumulator() {
+            let mut rng = test_rng();
+            let vec_b1: Vec<Com1<F>> = (0..3)
+                .map(|_| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+            let vec_b2: Vec<Com2<F>> = (0..3)
+                .map(|_| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+
+            let mut acc = ComMillerAccumulator::<F>::new();
+            acc.add_com_sum(&vec_b1[..2], &vec_b2[..2]).unwrap();
+            acc.sub_com(vec_b1[2], vec_b2[2]);
+            assert_eq!(
+                acc.add_com_sum(&vec_b1, &vec_b2[..1]),
+                Err(PairingSumError { x_len: 3, y_len: 1 })
+            );
+
+            let exp: ComT<F> = ComT::<F>::pairing(vec_b1[0], vec_b2[0])
+                + ComT::<F>::pairing(vec_b1[1], vec_b2[1])
+                - ComT::<F>::pairing(vec_b1[2], vec_b2[2]);
+            assert_eq!(acc.finalize(), exp);
+
+            let mut acc = ComMillerAccumulator::<F>::new();
+            acc.add_com_sum(&vec_b1, &vec_b2).unwrap();
+            acc.sub_com_sum(&vec_b1, &vec_b2).unwrap();
+            assert_eq!(acc.finalize(), ComT::<F>::zero());
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot pair 2 B1 elements with 1 B2 elements")]
+        fn test_B_pairing_sum_mismatch() {
+            let x = vec![Com1::<F>::zero(), Com1::<F>::zero()];
+            let y = vec![Com2::<F>::zero()];
+            let _ = ComT::<F>::pairing_sum(&x, &y);
+        }
+
+        #[test]
+        fn test_B_compact_fmt() {
+            let b1 = Com1::<F>(G1Affine::zero(), G1Affine::generator());
+            let b2 = Com2::<F>(G2Affine::zero(), G2Affine::generator());
+            let bt = ComT::pairing(b1, b2);
+
+            assert_eq!(format!("{}", b1), "Com1(0xc000…, 0x97f1…)");
+            assert_eq!(format!("{:?}", b1), "Com1(0xc000…, 0x97f1…)");
+            assert_eq!(format!("{}", b2), "Com2(0xc000…, 0x93e0…)");
+            assert_eq!(format!("{:?}", b2), "Com2(0xc000…, 0x93e0…)");
+            assert_eq!(
+                format!("{}", bt),
+                "ComT(0x0100…, 0x0100…, 0x0100…, 0xb689…)"
+            );
+            assert_eq!(format!("{}", bt), format!("{:?}", bt));
+        }
+
+        #[test]
+        fn test_B_full_fmt() {
+            let b1 = Com1::<F>(G1Affine::zero(), G1Affine::generator());
+            let b2 = Com2::<F>(G2Affine::zero(), G2Affine::generator());
+            let bt = ComT::pairing(b1, b2);
+
+            // The alternate flag falls back to the full representation of each element
+            assert_eq!(
+                format!("{:#?}", b1),
+                format!("Com1(\n    {:#?},\n    {:#?},\n)", b1.0, b1.1)
+            );
+            assert!(format!("{:#?}", b2).len() > format!("{:?}", b2).len());
+            assert!(format!("{:#?}", bt).len() > format!("{:?}", bt).len());
+        }
+
         #[test]
         fn test_B_into_matrix() {
             let mut rng = test_rng();
@@ -1463,6 +4526,121 @@ mod tests {
             assert_eq!(bt.3, bt_vec[1][1]);
         }
 
+        #[test]
+        fn test_B_tuple_conversions() {
+            let mut rng = test_rng();
+            let g1 = (
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let g2 = (
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+
+            let b1 = Com1::<F>::from(g1);
+            let b2: Com2<F> = g2.into();
+            assert_eq!(b1, Com1::<F>(g1.0, g1.1));
+            assert_eq!(b2, Com2::<F>(g2.0, g2.1));
+            assert_eq!(b1.as_tuple(), g1);
+            assert_eq!(<(G1Affine, G1Affine)>::from(b1), g1);
+            assert_eq!(<(G2Affine, G2Affine)>::from(b2), g2);
+
+            let bt = ComT::<F>::pairing(b1, b2);
+            let arr: [GT; 4] = bt.into();
+            assert_eq!(arr, [bt.0, bt.1, bt.2, bt.3]);
+            assert_eq!(bt.as_array(), arr);
+            assert_eq!(ComT::<F>::from(arr), bt);
+        }
+
+        #[test]
+        fn test_B_try_from_matrix() {
+            let mut rng = test_rng();
+            let b1 = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b2 = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let bt = ComT::<F>::pairing(b1, b2);
+
+            assert_eq!(Com1::<F>::try_from_matrix(b1.as_col_vec()), Ok(b1));
+            assert_eq!(Com2::<F>::try_from_matrix(b2.as_col_vec()), Ok(b2));
+            assert_eq!(ComT::<F>::try_from_matrix(bt.as_matrix()), Ok(bt));
+
+            // Wrong number of rows, wrong number of columns, and ragged matrices are rejected
+            let err1 = Err(ShapeError { expected: (2, 1) });
+            assert_eq!(Com1::<F>::try_from_matrix(vec![vec![b1.0]]), err1);
+            assert_eq!(Com1::<F>::try_from_matrix(vec![vec![b1.0, b1.1]]), err1);
+            assert_eq!(
+                Com2::<F>::try_from_matrix(vec![vec![b2.0], vec![b2.1, b2.0]]),
+                Err(ShapeError { expected: (2, 1) })
+            );
+            assert_eq!(
+                Com2::<F>::try_from_matrix(vec![]),
+                Err(ShapeError { expected: (2, 1) })
+            );
+            let errt = Err(ShapeError { expected: (2, 2) });
+            assert_eq!(ComT::<F>::try_from_matrix(vec![vec![bt.0, bt.1]]), errt);
+            assert_eq!(
+                ComT::<F>::try_from_matrix(vec![vec![bt.0, bt.1], vec![bt.2]]),
+                errt
+            );
+        }
+
+        #[test]
+        fn test_BT_bytes() {
+            let mut rng = test_rng();
+            let bt = ComT::<F>(
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+            );
+            for compress in [Compress::Yes, Compress::No] {
+                let bytes = bt.to_bytes(compress);
+                assert_eq!(bytes.len(), 4 * bt.0.serialized_size(compress));
+                assert_eq!(ComT::<F>::from_bytes(&bytes, compress).unwrap(), bt);
+                assert_eq!(
+                    ComT::<F>::from_bytes_unchecked(&bytes, compress).unwrap(),
+                    bt
+                );
+                assert_ne!(bytes, ComT::<F>::zero().to_bytes(compress));
+
+                assert!(ComT::<F>::from_bytes(&bytes[..bytes.len() - 1], compress).is_err());
+                let mut extended = bytes.clone();
+                extended.push(0);
+                assert!(ComT::<F>::from_bytes(&extended, compress).is_err());
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "expected a 2 x 2 matrix")]
+        fn test_BT_from_wrong_matrix() {
+            let _ = ComT::<F>::from(vec![vec![GT::zero()]]);
+        }
+
+        #[test]
+        fn test_batch_linear_map_both() {
+            let mut rng = test_rng();
+            let xs: Vec<G1Affine> = (0..3)
+                .map(|_| G1Projective::rand(&mut rng).into_affine())
+                .collect();
+            let ys: Vec<G2Affine> = (0..2)
+                .map(|_| G2Projective::rand(&mut rng).into_affine())
+                .collect();
+
+            let (lin_x, lin_y) = batch_linear_map_both::<F>(&xs, &ys);
+            assert_eq!(lin_x, Com1::<F>::batch_linear_map(&xs));
+            assert_eq!(lin_y, Com2::<F>::batch_linear_map(&ys));
+
+            let (lin_x, lin_y) = batch_linear_map_both::<F>(&[], &ys);
+            assert!(lin_x.is_empty());
+            assert_eq!(lin_y, Com2::<F>::batch_linear_map(&ys));
+        }
+
         #[test]
         fn test_batched_linear_maps() {
             let mut rng = test_rng();
@@ -1510,6 +4688,161 @@ mod tests {
             );
         }
 
+        #[cfg(feature = "subtle")]
+        #[test]
+        fn test_B_conditional_select() {
+            use subtle::{Choice, ConditionallySelectable};
+
+            let mut rng = test_rng();
+            let a1 = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b1 = Com1::<F>(G1Affine::zero(), G1Projective::rand(&mut rng).into_affine());
+            let a2 = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let b2 = Com2::<F>(G2Projective::rand(&mut rng).into_affine(), G2Affine::zero());
+
+            assert_eq!(Com1::<F>::conditional_select(&a1, &b1, Choice::from(0)), a1);
+            assert_eq!(Com1::<F>::conditional_select(&a1, &b1, Choice::from(1)), b1);
+            assert_eq!(Com2::<F>::conditional_select(&a2, &b2, Choice::from(0)), a2);
+            assert_eq!(Com2::<F>::conditional_select(&a2, &b2, Choice::from(1)), b2);
+
+            let mut c1 = a1;
+            c1.conditional_assign(&b1, Choice::from(1));
+            assert_eq!(c1, b1);
+        }
+
+        #[cfg(feature = "subtle")]
+        #[test]
+        fn test_BT_ct_eq() {
+            use subtle::ConstantTimeEq;
+
+            let mut rng = test_rng();
+            let a = ComT::<F>(
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+            );
+            // Differs from a only in the last entry
+            let b = ComT::<F>(a.0, a.1, a.2, GT::rand(&mut rng));
+
+            assert!(bool::from(a.ct_eq(&a)));
+            assert!(!bool::from(a.ct_eq(&b)));
+            assert!(bool::from(ComT::<F>::zero().ct_is_zero()));
+            assert!(!bool::from(a.ct_is_zero()));
+            assert!(bool::from((a - a).ct_is_zero()));
+        }
+
+        #[test]
+        fn test_linear_map_iters() {
+            let mut rng = test_rng();
+            let vec_g1 = vec![
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            ];
+            let vec_g2 = vec![
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            ];
+            let exp1 = Com1::<F>::batch_linear_map(&vec_g1);
+            let exp2 = Com2::<F>::batch_linear_map(&vec_g2);
+
+            assert_eq!(
+                Com1::<F>::linear_map_iter(&vec_g1).collect::<Vec<_>>(),
+                exp1
+            );
+            assert_eq!(
+                Com2::<F>::linear_map_iter(&vec_g2).collect::<Vec<_>>(),
+                exp2
+            );
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+            {
+                assert_eq!(
+                    Com1::<F>::par_linear_map_iter(&vec_g1).collect::<Vec<_>>(),
+                    exp1
+                );
+                assert_eq!(
+                    Com2::<F>::par_linear_map_iter(&vec_g2).collect::<Vec<_>>(),
+                    exp2
+                );
+            }
+            assert_eq!(Com1::<F>::linear_map_iter(&[]).count(), 0);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_batched_BT_linear_maps() {
+            let mut rng = test_rng();
+            let key = CRS::<F>::generate_crs(&mut rng);
+
+            let vec_gt = vec![GT::rand(&mut rng), GT::rand(&mut rng)];
+            let vec_g1 = vec![
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            ];
+            let vec_g2 = vec![
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            ];
+            let vec_ppe = ComT::<F>::batch_linear_map_PPE(&vec_gt);
+            let vec_msmeg1 = ComT::<F>::batch_linear_map_MSMEG1(&vec_g1, &key);
+            let vec_msmeg2 = ComT::<F>::batch_linear_map_MSMEG2(&vec_g2, &key);
+
+            assert_eq!(vec_ppe.len(), 2);
+            assert_eq!(vec_msmeg1.len(), 2);
+            assert_eq!(vec_msmeg2.len(), 2);
+            for i in 0..2 {
+                assert_eq!(vec_ppe[i], ComT::<F>::linear_map_PPE(&vec_gt[i]));
+                assert_eq!(
+                    vec_msmeg1[i],
+                    ComT::<F>::linear_map_MSMEG1(&vec_g1[i], &key)
+                );
+                assert_eq!(
+                    vec_msmeg2[i],
+                    ComT::<F>::linear_map_MSMEG2(&vec_g2[i], &key)
+                );
+            }
+            assert!(ComT::<F>::batch_linear_map_MSMEG1(&[], &key).is_empty());
+        }
+
+        #[test]
+        fn test_scalar_linear_maps_with_base() {
+            let mut rng = test_rng();
+            let key = CRS::<F>::generate_crs(&mut rng);
+
+            let base1 = key.u[1].prepare_scalar_base(&key.g1_gen);
+            let base2 = key.v[1].prepare_scalar_base(&key.g2_gen);
+            assert_eq!(base1, key.u[1] + Com1::<F>::linear_map(&key.g1_gen));
+            assert_eq!(base2, key.v[1] + Com2::<F>::linear_map(&key.g2_gen));
+
+            let vec_scalar = vec![
+                Fr::zero(),
+                Fr::one(),
+                Fr::rand(&mut rng),
+                Fr::rand(&mut rng),
+            ];
+            let vec_b1 = Com1::<F>::batch_scalar_linear_map(&vec_scalar, &key);
+            let vec_b2 = Com2::<F>::batch_scalar_linear_map(&vec_scalar, &key);
+            for (i, scalar) in vec_scalar.iter().enumerate() {
+                // Equal to recomputing the base for every scalar
+                let exp1 = (key.u[1] + Com1::<F>::linear_map(&key.g1_gen)).scalar_mul(scalar);
+                let exp2 = (key.v[1] + Com2::<F>::linear_map(&key.g2_gen)).scalar_mul(scalar);
+
+                assert_eq!(Com1::<F>::scalar_linear_map_with_base(&base1, scalar), exp1);
+                assert_eq!(Com2::<F>::scalar_linear_map_with_base(&base2, scalar), exp2);
+                assert_eq!(Com1::<F>::scalar_linear_map(scalar, &key), exp1);
+                assert_eq!(Com2::<F>::scalar_linear_map(scalar, &key), exp2);
+                assert_eq!(vec_b1[i], exp1);
+                assert_eq!(vec_b2[i], exp2);
+            }
+        }
+
         #[test]
         fn test_PPE_linear_maps() {
             let mut rng = test_rng();
@@ -1605,7 +4938,7 @@ mod tests {
     mod matrix {
 
         use ark_bls12_381::Bls12_381 as F;
-        use ark_ec::pairing::Pairing;
+        use ark_ec::pairing::{Pairing, PairingOutput};
         use ark_ff::UniformRand;
         use ark_std::ops::Mul;
         use ark_std::str::FromStr;
@@ -1617,6 +4950,7 @@ mod tests {
         type G1Projective = <F as Pairing>::G1;
         type G2Affine = <F as Pairing>::G2Affine;
         type G2Projective = <F as Pairing>::G2;
+        type GT = PairingOutput<F>;
         type Fr = <F as Pairing>::ScalarField;
 
         // Uses an affine group generator to produce an affine group element represented by the numeric string.
@@ -1674,6 +5008,25 @@ mod tests {
             assert_eq!(mat, exp);
         }
 
+        #[test]
+        fn test_matrix_display() {
+            let b1 = Com1::<F>(G1Affine::zero(), G1Affine::generator());
+            let mat: Matrix<Com1<F>> = vec![vec![b1, b1], vec![b1, -b1]];
+            let exp = "Matrix[2 x 2][\n  \
+                       [Com1(0xc000…, 0x97f1…), Com1(0xc000…, 0x97f1…)]\n  \
+                       [Com1(0xc000…, 0x97f1…), Com1(0xc000…, 0xb7f1…)]\n]";
+            assert_eq!(format!("{}", display_matrix(&mat)), exp);
+
+            let scalars: Matrix<Fr> = vec![vec![Fr::from_str("2").unwrap()]];
+            assert_eq!(
+                format!("{}", display_matrix(&scalars)),
+                "Matrix[1 x 1][\n  [2]\n]"
+            );
+
+            let empty: Matrix<Fr> = vec![];
+            assert_eq!(format!("{}", display_matrix(&empty)), "Matrix[0 x 0][]");
+        }
+
         #[test]
         fn test_field_matrix_left_mul_entry() {
             // 1 x 3 (row) vector
@@ -1924,87 +5277,1165 @@ mod tests {
                     Fr::from_str("18").unwrap(),
                 ],
             ];
-            let exp: Matrix<Fr> = vec![
+            let exp: Matrix<Fr> = vec![
+                vec![
+                    Fr::from_str("74").unwrap(),
+                    Fr::from_str("80").unwrap(),
+                    Fr::from_str("86").unwrap(),
+                    Fr::from_str("92").unwrap(),
+                ],
+                vec![
+                    Fr::from_str("173").unwrap(),
+                    Fr::from_str("188").unwrap(),
+                    Fr::from_str("203").unwrap(),
+                    Fr::from_str("218").unwrap(),
+                ],
+            ];
+            let res: Matrix<Fr> = lhs.right_mul(&rhs, true);
+
+            // 2 x 4 resulting matrix
+            assert_matrix_dimensions!(res, 2, 4);
+
+            assert_eq!(exp, res);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_left_mul_entry() {
+            // 1 x 3 (row) vector
+            let one = Fr::one();
+            let mut rng = test_rng();
+            let g1gen = G1Projective::rand(&mut rng).into_affine();
+
+            let lhs: Matrix<Fr> = vec![vec![
+                one,
+                Fr::from_str("2").unwrap(),
+                Fr::from_str("3").unwrap(),
+            ]];
+            // 3 x 1 (column) vector
+            let rhs: Matrix<Com1<F>> = vec![
+                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "4"))],
+                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "5"))],
+                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "6"))],
+            ];
+            let exp: Matrix<Com1<F>> = vec![vec![Com1::<F>(
+                G1Affine::zero(),
+                affine_group_new!(g1gen, "32"),
+            )]];
+            let res: Matrix<Com1<F>> = rhs.left_mul(&lhs, false);
+
+            // 1 x 1 resulting matrix
+            assert_matrix_dimensions!(res, 1, 1);
+
+            assert_eq!(exp, res);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_right_mul_entry() {
+            // 1 x 3 (row) vector
+            let mut rng = test_rng();
+            let g1gen = G1Projective::rand(&mut rng).into_affine();
+            let lhs: Matrix<Com1<F>> = vec![vec![
+                Com1::<F>(G1Affine::zero(), g1gen),
+                Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "2")),
+                Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "3")),
+            ]];
+            // 3 x 1 (column) vector
+            let rhs: Matrix<Fr> = vec![
+                vec![Fr::from_str("4").unwrap()],
+                vec![Fr::from_str("5").unwrap()],
+                vec![Fr::from_str("6").unwrap()],
+            ];
+            let exp: Matrix<Com1<F>> = vec![vec![Com1::<F>(
+                G1Affine::zero(),
+                affine_group_new!(g1gen, "32"),
+            )]];
+            let res: Matrix<Com1<F>> = lhs.right_mul(&rhs, false);
+
+            assert_matrix_dimensions!(res, 1, 1);
+
+            assert_eq!(exp, res);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_matrix_arith() {
+            let mut rng = test_rng();
+            let mat: Matrix<ComT<F>> = MatrixExt::from_fn((2, 3), |_, _| {
+                ComT::<F>::from([
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                ])
+            });
+            let three = Fr::from_str("3").unwrap();
+
+            let zeros = vec![vec![ComT::<F>::zero(); 3]; 2];
+            assert_eq!(mat.add(&mat.neg()), zeros);
+            assert_eq!(mat.clone().into_neg(), mat.neg());
+            assert_eq!(mat.add(&mat).add(&mat), mat.scalar_mul(&three));
+            assert_eq!(mat.add(&MatrixExt::zeros((2, 3))), mat);
+
+            let mut acc: Matrix<ComT<F>> = MatrixExt::zeros((2, 3));
+            acc.add_assign(&mat);
+            acc.scaled_add_assign(&Fr::from_str("2").unwrap(), &mat);
+            assert_eq!(acc, mat.scalar_mul(&three));
+
+            let trans = mat.transpose();
+            assert_matrix_dimensions!(trans, 3, 2);
+            assert_eq!(trans[2][1], mat[1][2]);
+            assert_eq!(mat.clone().into_transpose(), trans);
+            assert_eq!(trans.transpose(), mat);
+
+            // Scalar multiplication is exponentiation of every GT entry, as seen through as_matrix
+            let entry = mat.scalar_mul(&three)[1][2];
+            assert_eq!(
+                entry.as_matrix(),
+                mat[1][2].as_matrix().map(|gt| *gt * three)
+            );
+            assert_eq!(ComT::<F>::from(entry.as_matrix()), entry);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_matrix_mul_entry() {
+            let mut rng = test_rng();
+            let coms: Vec<ComT<F>> = (0..3)
+                .map(|_| {
+                    ComT::<F>::pairing(
+                        Com1::<F>(
+                            G1Projective::rand(&mut rng).into_affine(),
+                            G1Projective::rand(&mut rng).into_affine(),
+                        ),
+                        Com2::<F>(
+                            G2Projective::rand(&mut rng).into_affine(),
+                            G2Projective::rand(&mut rng).into_affine(),
+                        ),
+                    )
+                })
+                .collect();
+            let scalars: Vec<Fr> = vec![
+                Fr::one(),
+                Fr::from_str("2").unwrap(),
+                Fr::from_str("3").unwrap(),
+            ];
+            let exp: Matrix<ComT<F>> = vec![vec![
+                coms[0] + coms[1].scalar_mul(&scalars[1]) + coms[2].scalar_mul(&scalars[2]),
+            ]];
+
+            // 1 x 3 scalars times 3 x 1 ComT, and 1 x 3 ComT times 3 x 1 scalars
+            let left = vec_to_col_vec(&coms).left_mul(&vec![scalars.clone()], false);
+            let right = vec![coms.clone()].right_mul(&vec_to_col_vec(&scalars), false);
+            assert_matrix_dimensions!(left, 1, 1);
+            assert_eq!(left, exp);
+            assert_eq!(right, exp);
+            assert_eq!(
+                vec_to_col_vec(&coms).left_mul(&vec![scalars.clone()], true),
+                left
+            );
+            assert_eq!(vec![coms].right_mul(&vec_to_col_vec(&scalars), true), right);
+        }
+
+        // Reference matrix product accumulating entirely in affine coordinates
+        macro_rules! affine_left_mul {
+            ($com:ident, $mat:expr, $lhs:expr) => {
+                (0..$lhs.len())
+                    .map(|i| {
+                        (0..$mat[0].len())
+                            .map(|j| {
+                                (0..$mat.len()).fold($com::<F>::zero(), |acc, k| {
+                                    acc + $mat[k][j].scalar_mul(&$lhs[i][k])
+                                })
+                            })
+                            .collect::<Vec<$com<F>>>()
+                    })
+                    .collect::<Matrix<$com<F>>>()
+            };
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B_matrix_mul_projective_differential() {
+            let mut rng = test_rng();
+            // (m x k) scalars times (k x n) commitments, including a long inner dimension
+            for (m, k, n) in [(1, 1, 1), (2, 3, 4), (3, 2, 1), (1, 100, 1)] {
+                let lhs: Matrix<Fr> = (0..m)
+                    .map(|_| (0..k).map(|_| Fr::rand(&mut rng)).collect())
+                    .collect();
+                let mat1: Matrix<Com1<F>> = (0..k)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| {
+                                Com1::<F>(
+                                    G1Projective::rand(&mut rng).into_affine(),
+                                    G1Projective::rand(&mut rng).into_affine(),
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let exp1 = affine_left_mul!(Com1, mat1, lhs);
+                assert_eq!(mat1.left_mul(&lhs, false), exp1);
+                assert_eq!(mat1.left_mul(&lhs, true), exp1);
+                // (n x k) commitments times (k x m) scalars is the transposed product
+                let exp1_t = exp1.transpose();
+                assert_eq!(mat1.transpose().right_mul(&lhs.transpose(), false), exp1_t);
+                assert_eq!(mat1.transpose().right_mul(&lhs.transpose(), true), exp1_t);
+            }
+            for (m, k, n) in [(2, 3, 2), (1, 20, 1)] {
+                let lhs: Matrix<Fr> = (0..m)
+                    .map(|_| (0..k).map(|_| Fr::rand(&mut rng)).collect())
+                    .collect();
+                let mat2: Matrix<Com2<F>> = (0..k)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| {
+                                Com2::<F>(
+                                    G2Projective::rand(&mut rng).into_affine(),
+                                    G2Projective::rand(&mut rng).into_affine(),
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let exp2 = affine_left_mul!(Com2, mat2, lhs);
+                assert_eq!(mat2.left_mul(&lhs, false), exp2);
+                assert_eq!(mat2.left_mul(&lhs, true), exp2);
+                let exp2_t = exp2.transpose();
+                assert_eq!(mat2.transpose().right_mul(&lhs.transpose(), false), exp2_t);
+                assert_eq!(mat2.transpose().right_mul(&lhs.transpose(), true), exp2_t);
+            }
+        }
+
+        #[test]
+        fn test_matrix_map() {
+            // 2 x 3 matrix
+            let mat: Matrix<Fr> = vec![
+                vec![
+                    Fr::one(),
+                    Fr::from_str("2").unwrap(),
+                    Fr::from_str("3").unwrap(),
+                ],
+                vec![
+                    Fr::from_str("4").unwrap(),
+                    Fr::from_str("5").unwrap(),
+                    Fr::zero(),
+                ],
+            ];
+            let exp: Matrix<Fr> = vec![
+                vec![
+                    Fr::from_str("2").unwrap(),
+                    Fr::from_str("4").unwrap(),
+                    Fr::from_str("6").unwrap(),
+                ],
+                vec![
+                    Fr::from_str("8").unwrap(),
+                    Fr::from_str("10").unwrap(),
+                    Fr::zero(),
+                ],
+            ];
+            let res: Matrix<Fr> = mat.map(|elem| *elem + elem);
+
+            assert_matrix_dimensions!(res, 2, 3);
+            assert_eq!(exp, res);
+
+            // Entries may change type
+            let g1gen = G1Projective::rand(&mut test_rng()).into_affine();
+            let coms: Matrix<Com1<F>> =
+                mat.map(|elem| Com1::<F>::linear_map(&g1gen.mul(elem).into_affine()));
+            assert_matrix_dimensions!(coms, 2, 3);
+            assert_eq!(
+                coms[1][0],
+                Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "4"))
+            );
+
+            let empty: Matrix<Fr> = vec![];
+            assert!(empty.map(|elem| *elem + elem).is_empty());
+        }
+
+        #[test]
+        fn test_matrix_try_from_vecs() {
+            let mat: Matrix<Fr> = vec![vec![Fr::one(), Fr::zero()], vec![Fr::zero(), Fr::one()]];
+            assert_eq!(
+                <Matrix<Fr> as MatrixExt<Fr>>::try_from_vecs(mat.clone()),
+                Ok(mat.clone())
+            );
+            assert_eq!(<Matrix<Fr> as MatrixExt<Fr>>::from_vecs(mat.clone()), mat);
+            // Rows without entries are uniform too
+            assert!(<Matrix<Fr> as MatrixExt<Fr>>::try_from_vecs(vec![vec![]]).is_ok());
+
+            assert_eq!(
+                <Matrix<Fr> as MatrixExt<Fr>>::try_from_vecs(vec![]),
+                Err(MatrixError::Empty)
+            );
+            assert_eq!(
+                <Matrix<Fr> as MatrixExt<Fr>>::try_from_vecs(vec![
+                    vec![Fr::one(); 2],
+                    vec![Fr::one(); 2],
+                    vec![Fr::one(); 3]
+                ]),
+                Err(MatrixError::Ragged {
+                    row: 2,
+                    expected: 2,
+                    found: 3
+                })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "row 1 has 0 entries")]
+        fn test_matrix_from_vecs_ragged() {
+            let _ = <Matrix<Fr> as MatrixExt<Fr>>::from_vecs(vec![vec![Fr::one()], vec![]]);
+        }
+
+        #[test]
+        fn test_matrix_from_rows() {
+            let rows = vec![vec![Fr::one(), Fr::zero()], vec![Fr::zero(), Fr::one()]];
+            assert_eq!(Matrix::<Fr>::from_rows(rows.clone()), rows);
+        }
+
+        #[test]
+        #[should_panic(expected = "row 2 has 1 entries, expected 2")]
+        fn test_matrix_from_rows_ragged() {
+            let _ = Matrix::<Fr>::from_rows(vec![
+                vec![Fr::one(); 2],
+                vec![Fr::one(); 2],
+                vec![Fr::one()],
+            ]);
+        }
+
+        #[test]
+        fn test_matrix_builder() {
+            let rows = vec![
+                vec![Fr::one(), Fr::zero(), Fr::from(2u64)],
+                vec![Fr::zero(), Fr::one(), Fr::from(3u64)],
+            ];
+            let mut builder = MatrixBuilder::with_capacity(rows.len());
+            for row in rows.clone() {
+                builder.push_row(row).unwrap();
+            }
+            assert_eq!(builder.num_rows(), 2);
+            assert_eq!(builder.build(), Matrix::<Fr>::from_vecs(rows));
+
+            // A ragged row is rejected, and the rows before it are kept
+            let mut builder = MatrixBuilder::new();
+            builder.push_row(vec![Fr::one(); 2]).unwrap();
+            assert_eq!(
+                builder.push_row(vec![Fr::one(); 3]),
+                Err(MatrixError::Ragged {
+                    row: 1,
+                    expected: 2,
+                    found: 3
+                })
+            );
+            assert_eq!(builder.build(), vec![vec![Fr::one(); 2]]);
+        }
+
+        #[test]
+        #[should_panic(expected = "expected a matrix with at least one row")]
+        fn test_matrix_builder_empty() {
+            let _ = MatrixBuilder::<Fr>::new().build();
+        }
+
+        #[cfg(feature = "ndarray")]
+        #[test]
+        fn test_matrix_ndarray_conversions() {
+            let mut rng = test_rng();
+            let mat: FieldMatrix<Fr> =
+                FieldMatrix(MatrixExt::from_fn((2, 3), |_, _| Fr::rand(&mut rng)));
+
+            // The copy is row-major, and mutating it leaves the matrix untouched
+            let mut array = mat.to_array().unwrap();
+            assert!(array.is_standard_layout());
+            assert_eq!(array.dim(), (2, 3));
+            assert_eq!(array[[1, 2]], mat[1][2]);
+            array.map_inplace(|x| *x += Fr::one());
+            array.row_mut(0).fill(Fr::zero());
+            assert_eq!(array[[1, 2]], mat[1][2] + Fr::one());
+            assert_ne!(array[[0, 0]], mat[0][0]);
+
+            // Round trips, including through a transposed (non-standard) view
+            let array = mat.to_array().unwrap();
+            assert_eq!(FieldMatrix::from_view(array.view()), mat);
+            assert_eq!(
+                FieldMatrix::from_view(array.t()),
+                FieldMatrix(mat.transpose())
+            );
+            assert_eq!(FieldMatrix::try_from(array).unwrap(), mat);
+
+            assert_eq!(
+                FieldMatrix::<Fr>::try_from(ndarray::Array2::default((0, 3))),
+                Err(MatrixError::Empty)
+            );
+            let ragged = FieldMatrix(vec![vec![Fr::one(); 2], vec![Fr::one()]]);
+            assert_eq!(
+                ragged.to_array(),
+                Err(MatrixError::Ragged {
+                    row: 1,
+                    expected: 2,
+                    found: 1
+                })
+            );
+        }
+
+        #[test]
+        fn test_matrix_inverse() {
+            let mut rng = test_rng();
+            let id: Matrix<Fr> = MatrixExt::from_rows(vec![
+                vec![Fr::one(), Fr::zero()],
+                vec![Fr::zero(), Fr::one()],
+            ]);
+            for _ in 0..10 {
+                let a: Matrix<Fr> = MatrixExt::from_fn((2, 2), |_, _| Fr::rand(&mut rng));
+                let det = a[0][0] * a[1][1] - a[0][1] * a[1][0];
+                assert_eq!(a.determinant(), Ok(det));
+
+                let inv = a.inverse().unwrap().unwrap();
+                assert_eq!(a.right_mul(&inv, false), id);
+                assert_eq!(inv.right_mul(&a, false), id);
+
+                let b = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+                let x = a.solve(&b).unwrap().unwrap();
+                assert_eq!(a.mul_vec(&x), b);
+            }
+
+            // Needs a row swap, which negates the determinant
+            let swap: Matrix<Fr> = MatrixExt::from_rows(vec![
+                vec![Fr::zero(), Fr::one()],
+                vec![Fr::one(), Fr::zero()],
+            ]);
+            assert_eq!(swap.determinant(), Ok(-Fr::one()));
+            assert_eq!(swap.inverse(), Ok(Some(swap.clone())));
+
+            let a: Matrix<Fr> = MatrixExt::from_fn((4, 4), |_, _| Fr::rand(&mut rng));
+            let inv = a.inverse().unwrap().unwrap();
+            let id: Matrix<Fr> =
+                MatrixExt::from_fn((4, 4), |i, j| if i == j { Fr::one() } else { Fr::zero() });
+            assert_eq!(a.right_mul(&inv, false), id);
+
+            assert_eq!(Matrix::<Fr>::new().determinant(), Ok(Fr::one()));
+            assert_eq!(Matrix::<Fr>::new().inverse(), Ok(Some(vec![])));
+        }
+
+        #[test]
+        fn test_matrix_inverse_singular() {
+            let mut rng = test_rng();
+            let (x, y) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+            // The second row is a multiple of the first
+            let a: Matrix<Fr> = MatrixExt::from_rows(vec![vec![x, y], vec![x + x, y + y]]);
+            assert_eq!(a.determinant(), Ok(Fr::zero()));
+            assert_eq!(a.inverse(), Ok(None));
+            assert_eq!(a.solve(&[x, y]), Ok(None));
+
+            let zero: Matrix<Fr> = vec![vec![Fr::zero(); 2]; 2];
+            assert_eq!(zero.inverse(), Ok(None));
+        }
+
+        #[test]
+        fn test_matrix_inverse_not_square() {
+            let a: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            assert_eq!(a.determinant(), Err(ShapeError { expected: (2, 2) }));
+            assert_eq!(a.inverse(), Err(ShapeError { expected: (2, 2) }));
+
+            let id: Matrix<Fr> = vec![vec![Fr::one(), Fr::zero()], vec![Fr::zero(), Fr::one()]];
+            assert_eq!(id.solve(&[Fr::one()]), Err(ShapeError { expected: (2, 1) }));
+        }
+
+        #[test]
+        fn test_matrix_transposed_view() {
+            let mut rng = test_rng();
+            let mat: Matrix<Fr> = MatrixExt::from_fn((3, 2), |_, _| Fr::rand(&mut rng));
+            let view = mat.transposed_view();
+            let trans = mat.transpose();
+            assert_eq!(view.shape(), (2, 3));
+            assert_eq!(view.entry((1, 2)), Some(&mat[2][1]));
+            assert_eq!(view.entry((2, 1)), None);
+            assert_eq!(view.row(1).copied().collect::<Vec<_>>(), trans[1]);
+            assert_eq!(view.col(2), trans.col(2).copied().collect::<Vec<_>>());
+            assert_eq!(view.to_matrix(), trans);
+
+            // Multiplying via the view equals multiplying via the owned transpose
+            let lhs: Matrix<Fr> = MatrixExt::from_fn((4, 3), |_, _| Fr::rand(&mut rng));
+            let rhs: Matrix<Fr> = MatrixExt::from_fn((3, 4), |_, _| Fr::rand(&mut rng));
+            let lhs_trans: Matrix<Fr> = MatrixExt::from_fn((4, 2), |_, _| Fr::rand(&mut rng));
+            for is_parallel in [false, true] {
+                assert_eq!(
+                    mat.transposed_view().right_mul(&rhs, is_parallel),
+                    trans.right_mul(&rhs, is_parallel)
+                );
+                assert_eq!(
+                    mat.transposed_view().left_mul(&lhs_trans, is_parallel),
+                    trans.left_mul(&lhs_trans, is_parallel)
+                );
+                assert_eq!(
+                    lhs.transposed_view()
+                        .left_mul(&rhs.transpose(), is_parallel),
+                    rhs.transpose().right_mul(&lhs.transpose(), is_parallel)
+                );
+            }
+
+            // An empty matrix has an empty product, as for the owned transpose
+            let empty: Matrix<Fr> = vec![];
+            assert!(empty.transposed_view().right_mul(&rhs, false).is_empty());
+            assert!(empty.transposed_view().left_mul(&lhs, false).is_empty());
+        }
+
+        #[test]
+        fn test_matrix_swap_permute() {
+            let mat: Matrix<Fr> = MatrixExt::from_fn((3, 2), |i, j| Fr::from((2 * i + j) as u64));
+
+            let mut swapped = mat.clone();
+            swapped.swap_rows(0, 2);
+            assert_eq!(swapped, mat.permute_rows(&[2, 1, 0]).unwrap());
+            swapped.swap_cols(0, 1);
+            assert_eq!(
+                swapped,
+                mat.permute_rows(&[2, 1, 0])
+                    .unwrap()
+                    .permute_cols(&[1, 0])
+                    .unwrap()
+            );
+
+            // Permuting columns is permuting the rows of the transpose
+            let perm = [1, 2, 0];
+            assert_eq!(mat.permute_rows(&perm).unwrap()[0], mat[1]);
+            assert_eq!(
+                mat.transpose().permute_cols(&perm).unwrap(),
+                mat.permute_rows(&perm).unwrap().transpose()
+            );
+            assert_eq!(mat.permute_rows(&[0, 1, 2]).unwrap(), mat);
+
+            assert_eq!(
+                mat.permute_rows(&[0, 1]),
+                Err(PermError::Length {
+                    expected: 3,
+                    found: 2
+                })
+            );
+            assert_eq!(
+                mat.permute_rows(&[0, 2, 0]),
+                Err(PermError::Invalid { pos: 2, index: 0 })
+            );
+            assert_eq!(
+                mat.permute_cols(&[0, 2]),
+                Err(PermError::Invalid { pos: 1, index: 2 })
+            );
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_matrix_scale_rows_cols() {
+            let mut rng = test_rng();
+            let (m, n) = (3, 2);
+            let row_scalars: Vec<Fr> = (0..m).map(|_| Fr::rand(&mut rng)).collect();
+            let col_scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+            let diag = |scalars: &[Fr]| -> Matrix<Fr> {
+                MatrixExt::from_fn((scalars.len(), scalars.len()), |i, j| {
+                    if i == j {
+                        scalars[i]
+                    } else {
+                        Fr::zero()
+                    }
+                })
+            };
+
+            // Against the products with the explicit diagonal matrices
+            let field: Matrix<Fr> = MatrixExt::from_fn((m, n), |_, _| Fr::rand(&mut rng));
+            assert_eq!(
+                field.scale_rows(&row_scalars).unwrap(),
+                field.left_mul(&diag(&row_scalars), false)
+            );
+            assert_eq!(
+                field.scale_cols(&col_scalars).unwrap(),
+                field.right_mul(&diag(&col_scalars), false)
+            );
+            let com1: Matrix<Com1<F>> = MatrixExt::from_fn((m, n), |_, _| {
+                Com1::<F>(
+                    G1Projective::rand(&mut rng).into_affine(),
+                    G1Projective::rand(&mut rng).into_affine(),
+                )
+            });
+            assert_eq!(
+                com1.scale_rows(&row_scalars).unwrap(),
+                com1.left_mul(&diag(&row_scalars), false)
+            );
+            assert_eq!(
+                com1.scale_cols(&col_scalars).unwrap(),
+                com1.right_mul(&diag(&col_scalars), false)
+            );
+            let com2: Matrix<Com2<F>> = MatrixExt::from_fn((m, n), |_, _| {
+                Com2::<F>(
+                    G2Projective::rand(&mut rng).into_affine(),
+                    G2Projective::rand(&mut rng).into_affine(),
+                )
+            });
+            assert_eq!(
+                com2.scale_rows(&row_scalars).unwrap(),
+                com2.left_mul(&diag(&row_scalars), false)
+            );
+            assert_eq!(
+                com2.scale_cols(&col_scalars).unwrap(),
+                com2.right_mul(&diag(&col_scalars), false)
+            );
+            let comt: Matrix<ComT<F>> = MatrixExt::from_fn((m, n), |_, _| {
+                ComT::<F>(
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                )
+            });
+            assert_eq!(
+                comt.scale_rows(&row_scalars).unwrap(),
+                comt.left_mul(&diag(&row_scalars), false)
+            );
+            assert_eq!(
+                comt.scale_cols(&col_scalars).unwrap(),
+                comt.right_mul(&diag(&col_scalars), false)
+            );
+
+            // One scalar per row (resp. column) is required
+            assert_eq!(
+                field.scale_rows(&col_scalars),
+                Err(ShapeError { expected: (m, 1) })
+            );
+            assert_eq!(
+                com1.scale_cols(&row_scalars),
+                Err(ShapeError { expected: (n, 1) })
+            );
+            let empty: Matrix<Fr> = vec![];
+            assert_eq!(empty.scale_rows(&[]), Ok(vec![]));
+        }
+
+        #[test]
+        #[should_panic(expected = "at least one row")]
+        fn test_matrix_from_vecs_empty() {
+            let _ = <Matrix<Fr> as MatrixExt<Fr>>::from_vecs(vec![]);
+        }
+
+        #[test]
+        fn test_empty_matrix_equality() {
+            let zero_by_zero: Matrix<Fr> = vec![];
+            let three_by_zero: Matrix<Fr> = vec![vec![]; 3];
+            let one_by_one: Matrix<Fr> = vec![vec![Fr::zero()]];
+
+            assert!(zero_by_zero.is_empty_matrix());
+            assert!(three_by_zero.is_empty_matrix());
+            assert!(!one_by_one.is_empty_matrix());
+
+            // Structural equality tells the shapes apart, equality of entries does not
+            assert_ne!(zero_by_zero, three_by_zero);
+            assert!(zero_by_zero.eq_entries(&three_by_zero));
+            assert!(three_by_zero.eq_entries(&zero_by_zero));
+            assert!(!zero_by_zero.eq_entries(&one_by_one));
+            assert!(one_by_one.eq_entries(&one_by_one.clone()));
+            assert!(!one_by_one.eq_entries(&vec![vec![Fr::one()]]));
+
+            // Serialization round-trips preserve the shape
+            for mat in [zero_by_zero, three_by_zero] {
+                let mut bytes = Vec::new();
+                mat.serialize_compressed(&mut bytes).unwrap();
+                let de = Matrix::<Fr>::deserialize_compressed(&bytes[..]).unwrap();
+                assert_eq!(de, mat);
+            }
+        }
+
+        #[test]
+        fn test_matrix_bounded_serde() {
+            let mut rng = test_rng();
+            let shapes: [(usize, usize); 3] = [(2, 3), (3, 0), (0, 0)];
+            for shape in shapes {
+                let mat: Matrix<Fr> =
+                    <Matrix<Fr> as MatrixExt<Fr>>::from_fn(shape, |_, _| Fr::rand(&mut rng));
+                for compress in [Compress::Yes, Compress::No] {
+                    let mut bytes = Vec::new();
+                    serialize_matrix(&mat, &mut bytes, compress).unwrap();
+                    // Dimensions, then the entries without any per-row length
+                    assert_eq!(
+                        bytes.len(),
+                        8 + shape.0 * shape.1 * Fr::one().serialized_size(compress)
+                    );
+                    assert_eq!(bytes.len(), serialized_matrix_size(&mat, compress));
+                    let de: Matrix<Fr> =
+                        deserialize_matrix(&bytes[..], compress, Validate::Yes, 6).unwrap();
+                    assert_eq!(de, mat);
+                }
+            }
+        }
+
+        #[test]
+        fn test_matrix_serde_fixtures() {
+            // The little-endian encoding of a small scalar, padded to the 32 bytes of an Fr
+            let fr_bytes = |x: u8| {
+                let mut bytes = vec![0u8; 32];
+                bytes[0] = x;
+                bytes
+            };
+            let mat: Matrix<Fr> = vec![
+                vec![Fr::from(1u64), Fr::from(2u64)],
+                vec![Fr::from(3u64), Fr::zero()],
+            ];
+
+            // The `Vec<Vec<F>>` format writes a u64 length before the matrix and before every row
+            let mut fixture = 2u64.to_le_bytes().to_vec();
+            for row in [[1u8, 2], [3, 0]] {
+                fixture.extend(2u64.to_le_bytes());
+                fixture.extend(row.iter().flat_map(|x| fr_bytes(*x)));
+            }
+            let mut bytes = Vec::new();
+            mat.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes, fixture);
+            assert_eq!(mat.compressed_size(), fixture.len());
+            assert_eq!(
+                Matrix::<Fr>::deserialize_compressed(&fixture[..]).unwrap(),
+                mat
+            );
+
+            // The dimension-prefixed format writes u32 dimensions once
+            let mut fixture = 2u32.to_le_bytes().to_vec();
+            fixture.extend(2u32.to_le_bytes());
+            fixture.extend([1u8, 2, 3, 0].iter().flat_map(|x| fr_bytes(*x)));
+            let mut bytes = Vec::new();
+            serialize_matrix(&mat, &mut bytes, Compress::Yes).unwrap();
+            assert_eq!(bytes, fixture);
+            assert_eq!(serialized_matrix_size(&mat, Compress::Yes), fixture.len());
+            let de: Matrix<Fr> =
+                deserialize_matrix(&fixture[..], Compress::Yes, Validate::Yes, 4).unwrap();
+            assert_eq!(de, mat);
+        }
+
+        #[test]
+        fn test_matrix_bounded_serde_hostile() {
+            let mat: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let mut bytes = Vec::new();
+            serialize_matrix(&mat, &mut bytes, Compress::Yes).unwrap();
+
+            // More entries than allowed
+            assert!(
+                deserialize_matrix::<Fr, _>(&bytes[..], Compress::Yes, Validate::Yes, 5).is_err()
+            );
+            // Truncated entries
+            assert!(deserialize_matrix::<Fr, _>(
+                &bytes[..bytes.len() - 1],
+                Compress::Yes,
+                Validate::Yes,
+                6
+            )
+            .is_err());
+
+            // Absurd dimensions are rejected before allocating, including rows without entries
+            for (rows, cols) in [(u32::MAX, u32::MAX), (u32::MAX, 1), (u32::MAX, 0)] {
+                let mut bytes = Vec::new();
+                rows.serialize_compressed(&mut bytes).unwrap();
+                cols.serialize_compressed(&mut bytes).unwrap();
+                assert!(deserialize_matrix::<Fr, _>(
+                    &bytes[..],
+                    Compress::Yes,
+                    Validate::Yes,
+                    1 << 20
+                )
+                .is_err());
+            }
+
+            // Ragged matrices cannot be serialized
+            let ragged: Matrix<Fr> = vec![vec![Fr::one(); 3], vec![Fr::one(); 2]];
+            assert!(serialize_matrix(&ragged, &mut Vec::new(), Compress::Yes).is_err());
+        }
+
+        #[test]
+        fn test_empty_col_vec() {
+            // The empty vector is the empty matrix, and back
+            let empty: Matrix<Fr> = vec_to_col_vec(&[]);
+            assert!(empty.is_empty());
+            assert!(col_vec_to_vec(&empty).is_empty());
+
+            // which is only added to other empty matrices
+            assert_eq!(empty.add(&empty), empty);
+        }
+
+        #[test]
+        fn test_matrix_vector_products() {
+            let mut rng = test_rng();
+            let mat: Matrix<Fr> = MatrixExt::from_fn((3, 4), |_, _| Fr::rand(&mut rng));
+            let v: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+            let w: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+
+            // Field matrices with field vectors
+            assert_eq!(
+                mat.mul_vec(&v),
+                col_vec_to_vec(&mat.right_mul(&vec_to_col_vec(&v), false))
+            );
+            assert_eq!(
+                mat.vec_mul(&w),
+                col_vec_to_vec(&vec![w.clone()].right_mul(&mat, false))
+            );
+
+            // Commitment group matrices with scalar vectors
+            let coms: Matrix<Com1<F>> = MatrixExt::from_fn((3, 4), |_, _| {
+                Com1::<F>(
+                    G1Projective::rand(&mut rng).into_affine(),
+                    G1Projective::rand(&mut rng).into_affine(),
+                )
+            });
+            assert_eq!(
+                coms.mul_vec(&v),
+                col_vec_to_vec(&coms.right_mul(&vec_to_col_vec(&v), false))
+            );
+            assert_eq!(
+                coms.vec_mul(&w),
+                col_vec_to_vec(&coms.left_mul(&vec![w.clone()], false))
+            );
+
+            // Scalar matrices with commitment group vectors
+            let d: Vec<Com2<F>> = (0..4)
+                .map(|_| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+            let e: Vec<Com2<F>> = d[..3].to_vec();
+            assert_eq!(
+                mat.mul_com_vec(&d),
+                col_vec_to_vec(&vec_to_col_vec(&d).left_mul(&mat, false))
+            );
+            assert_eq!(
+                mat.com_vec_mul(&e),
+                col_vec_to_vec(&vec![e].right_mul(&mat, false))
+            );
+
+            // A matrix without rows has an empty product, one without columns a zero product
+            assert!(Matrix::<Fr>::new().mul_vec(&v).is_empty());
+            assert!(Matrix::<Fr>::new().mul_com_vec(&d).is_empty());
+            assert_eq!(
+                vec![Vec::<Fr>::new(); 2].mul_com_vec(&Vec::<Com2<F>>::new()),
+                vec![Com2::<F>::zero(); 2]
+            );
+            assert_eq!(vec![Vec::<Fr>::new(); 2].vec_mul(&w[..2]), vec![]);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot multiply a 3 x 4 matrix by a vector of length 3")]
+        fn test_matrix_mul_vec_mismatch() {
+            let mat: Matrix<Fr> = vec![vec![Fr::one(); 4]; 3];
+            let _ = mat.mul_vec(&[Fr::one(); 3]);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot multiply a vector of length 4 by a 3 x 4 matrix")]
+        fn test_matrix_vec_mul_mismatch() {
+            let mat: Matrix<Com1<F>> = vec![vec![Com1::<F>::zero(); 4]; 3];
+            let _ = mat.vec_mul(&[Fr::one(); 4]);
+        }
+
+        #[test]
+        fn test_matrix_rand_sparse_density() {
+            let mut rng = test_rng();
+
+            // 2000 entries at density 0.3: ~600 non-zeros, with a standard deviation of ~20
+            let sparse: Matrix<Fr> = MatrixExt::rand_sparse(&mut rng, 40, 50, 0.3);
+            assert_matrix_dimensions!(sparse, 40, 50);
+            let nnz = sparse.iter().flatten().filter(|x| !x.is_zero()).count();
+            assert!((500..=700).contains(&nnz), "{} non-zeros", nnz);
+            assert_eq!(sparse.density(), nnz as f64 / 2000.0);
+
+            // The extremes are exactly all-zero and all-random
+            let zeros: Matrix<Fr> = MatrixExt::rand_sparse(&mut rng, 3, 4, 0.0);
+            assert_eq!(zeros, vec![vec![Fr::zero(); 4]; 3]);
+            assert_eq!(zeros.density(), 0.0);
+
+            let mut rng1 = test_rng();
+            let mut rng2 = test_rng();
+            let full: Matrix<Fr> = MatrixExt::rand_sparse(&mut rng1, 3, 4, 1.0);
+            let dense: Matrix<Fr> = MatrixExt::from_fn((3, 4), |_, _| Fr::rand(&mut rng2));
+            assert_eq!(full, dense);
+            assert_eq!(full.density(), dense.density());
+            assert_eq!(full.density(), 1.0);
+
+            // A matrix without entries has no density
+            assert_eq!(Matrix::<Fr>::new().density(), 0.0);
+            assert_eq!(vec![Vec::<Fr>::new(); 2].density(), 0.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "density must be in [0, 1]")]
+        fn test_matrix_rand_sparse_invalid_density() {
+            let _: Matrix<Fr> = MatrixExt::rand_sparse(&mut test_rng(), 2, 2, 1.5);
+        }
+
+        #[test]
+        fn test_matrix_from_fn_zip_with() {
+            // 2 x 3 matrix with entries i + j
+            let mat: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn((2, 3), |i, j| Fr::from((i + j) as u64));
+            let exp: Matrix<Fr> = vec![
+                vec![Fr::zero(), Fr::one(), Fr::from_str("2").unwrap()],
+                vec![
+                    Fr::one(),
+                    Fr::from_str("2").unwrap(),
+                    Fr::from_str("3").unwrap(),
+                ],
+            ];
+            assert_matrix_dimensions!(mat, 2, 3);
+            assert_eq!(exp, mat);
+            assert!(<Matrix<Fr> as MatrixExt<Fr>>::from_fn((0, 3), |_, _| Fr::one()).is_empty());
+
+            // Entry-wise product preserves the shape
+            let prod: Matrix<Fr> = mat.zip_with(&exp, |x, y| *x * y).unwrap();
+            assert_matrix_dimensions!(prod, 2, 3);
+            assert_eq!(prod[1][2], Fr::from_str("9").unwrap());
+
+            // Com-from-scalar mapping, with a fixed base
+            let g1gen = G1Projective::rand(&mut test_rng()).into_affine();
+            let coms: Matrix<Com1<F>> =
+                mat.map(|x| Com1::<F>::linear_map(&g1gen.mul(x).into_affine()));
+            let shifted: Matrix<Com1<F>> = coms
+                .zip_with(&mat, |com, x| {
+                    *com + Com1::<F>(g1gen.mul(x).into_affine(), G1Affine::zero())
+                })
+                .unwrap();
+            assert_eq!(
+                shifted[1][1],
+                Com1::<F>(affine_group_new!(g1gen, "2"), affine_group_new!(g1gen, "2"))
+            );
+
+            // Mismatched shapes, including ragged rows, are rejected
+            let wide: Matrix<Fr> = <Matrix<Fr> as MatrixExt<Fr>>::from_fn((2, 4), |_, _| Fr::one());
+            assert_eq!(
+                mat.zip_with(&wide, |x, y| *x + y),
+                Err(ShapeError { expected: (2, 3) })
+            );
+            let ragged: Matrix<Fr> = vec![vec![Fr::one(); 3], vec![Fr::one(); 2]];
+            assert_eq!(
+                mat.zip_with(&ragged, |x, y| *x + y),
+                Err(ShapeError { expected: (2, 3) })
+            );
+        }
+
+        #[test]
+        fn test_matrix_accessors() {
+            // 2 x 3 matrix
+            let mat: Matrix<Fr> = vec![
                 vec![
-                    Fr::from_str("74").unwrap(),
-                    Fr::from_str("80").unwrap(),
-                    Fr::from_str("86").unwrap(),
-                    Fr::from_str("92").unwrap(),
+                    Fr::one(),
+                    Fr::from_str("2").unwrap(),
+                    Fr::from_str("3").unwrap(),
                 ],
                 vec![
-                    Fr::from_str("173").unwrap(),
-                    Fr::from_str("188").unwrap(),
-                    Fr::from_str("203").unwrap(),
-                    Fr::from_str("218").unwrap(),
+                    Fr::from_str("4").unwrap(),
+                    Fr::from_str("5").unwrap(),
+                    Fr::from_str("6").unwrap(),
                 ],
             ];
-            let res: Matrix<Fr> = lhs.right_mul(&rhs, true);
 
-            // 2 x 4 resulting matrix
-            assert_matrix_dimensions!(res, 2, 4);
+            assert_eq!(mat.num_cols(), 3);
+            assert_eq!(mat.entry((1, 2)), Some(&Fr::from_str("6").unwrap()));
+            assert_eq!(mat.entry((2, 0)), None);
+            assert_eq!(mat.entry((0, 3)), None);
+            assert_eq!(mat.row(1), &mat[1][..]);
+            assert_eq!(
+                mat.col(1).copied().collect::<Vec<Fr>>(),
+                vec![Fr::from_str("2").unwrap(), Fr::from_str("5").unwrap()]
+            );
+            assert_eq!(
+                mat.rows().collect::<Vec<&[Fr]>>(),
+                vec![&mat[0][..], &mat[1][..]]
+            );
 
-            assert_eq!(exp, res);
+            // Iterating over the columns is the same as iterating over the rows of the transpose
+            let cols: Matrix<Fr> = mat.cols().map(|col| col.copied().collect()).collect();
+            assert_eq!(cols, mat.transpose());
+
+            let empty: Matrix<Fr> = vec![];
+            assert_eq!(empty.num_cols(), 0);
+            assert_eq!(empty.entry((0, 0)), None);
+            assert_eq!(empty.rows().count(), 0);
+            assert_eq!(empty.cols().count(), 0);
         }
 
         #[allow(non_snake_case)]
         #[test]
-        fn test_B1_matrix_left_mul_entry() {
-            // 1 x 3 (row) vector
-            let one = Fr::one();
+        fn test_B1_matrix_accessors() {
             let mut rng = test_rng();
             let g1gen = G1Projective::rand(&mut rng).into_affine();
-
-            let lhs: Matrix<Fr> = vec![vec![
-                one,
-                Fr::from_str("2").unwrap(),
-                Fr::from_str("3").unwrap(),
-            ]];
             // 3 x 1 (column) vector
-            let rhs: Matrix<Com1<F>> = vec![
-                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "4"))],
-                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "5"))],
-                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "6"))],
+            let mat: Matrix<Com1<F>> = vec![
+                vec![Com1::<F>(G1Affine::zero(), g1gen)],
+                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "2"))],
+                vec![Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "3"))],
             ];
-            let exp: Matrix<Com1<F>> = vec![vec![Com1::<F>(
-                G1Affine::zero(),
-                affine_group_new!(g1gen, "32"),
-            )]];
-            let res: Matrix<Com1<F>> = rhs.left_mul(&lhs, false);
 
-            // 1 x 1 resulting matrix
-            assert_matrix_dimensions!(res, 1, 1);
+            assert_eq!(mat.num_cols(), 1);
+            assert_eq!(mat.entry((2, 0)), Some(&mat[2][0]));
+            assert_eq!(mat.entry((0, 1)), None);
+            assert_eq!(mat.row(0), &[Com1::<F>(G1Affine::zero(), g1gen)]);
+            assert_eq!(
+                mat.col(0).copied().collect::<Vec<Com1<F>>>(),
+                col_vec_to_vec(&mat)
+            );
+            assert_eq!(mat.cols().count(), 1);
+            assert_eq!(mat.rows().count(), 3);
+        }
 
+        #[test]
+        fn test_field_matrix_kron() {
+            // 2 x 2 matrix
+            let lhs: Matrix<Fr> = vec![
+                vec![Fr::one(), Fr::from_str("2").unwrap()],
+                vec![Fr::from_str("3").unwrap(), Fr::zero()],
+            ];
+            // 2 x 1 (column) vector
+            let rhs: Matrix<Fr> = vec![
+                vec![Fr::from_str("4").unwrap()],
+                vec![Fr::from_str("5").unwrap()],
+            ];
+            let exp: Matrix<Fr> = vec![
+                vec![Fr::from_str("4").unwrap(), Fr::from_str("8").unwrap()],
+                vec![Fr::from_str("5").unwrap(), Fr::from_str("10").unwrap()],
+                vec![Fr::from_str("12").unwrap(), Fr::zero()],
+                vec![Fr::from_str("15").unwrap(), Fr::zero()],
+            ];
+            let res: Matrix<Fr> = kron(&lhs, &rhs);
+
+            // 4 x 2 resulting matrix
+            assert_matrix_dimensions!(res, 4, 2);
             assert_eq!(exp, res);
+
+            // (1 x 3) ⊗ (2 x 2) is 2 x 6
+            let row: Matrix<Fr> = vec![vec![Fr::one(); 3]];
+            let row_lhs = kron(&row, &lhs);
+            let lhs_row = kron(&lhs, &row);
+            assert_matrix_dimensions!(row_lhs, 2, 6);
+            assert_matrix_dimensions!(lhs_row, 2, 6);
+            assert!(kron(&lhs, &vec![]).is_empty());
+        }
+
+        #[test]
+        fn test_field_matrix_kron_mixed_product() {
+            let mut rng = test_rng();
+            let mut rand_mat = |m: usize, n: usize| -> Matrix<Fr> {
+                (0..m)
+                    .map(|_| (0..n).map(|_| Fr::rand(&mut rng)).collect())
+                    .collect()
+            };
+            // (A ⊗ B)(C ⊗ D) = (AC) ⊗ (BD) for compatible dimensions
+            for (a, b, c, d) in [
+                ((2, 3), (2, 2), (3, 1), (2, 2)),
+                ((1, 2), (3, 2), (2, 2), (2, 1)),
+            ] {
+                let a = rand_mat(a.0, a.1);
+                let b = rand_mat(b.0, b.1);
+                let c = rand_mat(c.0, c.1);
+                let d = rand_mat(d.0, d.1);
+
+                let lhs = kron(&c, &d).left_mul(&kron(&a, &b), false);
+                let rhs = kron(&c.left_mul(&a, false), &d.left_mul(&b, false));
+                assert_eq!(lhs, rhs);
+            }
         }
 
         #[allow(non_snake_case)]
         #[test]
-        fn test_B1_matrix_right_mul_entry() {
-            // 1 x 3 (row) vector
+        fn test_B1_matrix_kron() {
             let mut rng = test_rng();
             let g1gen = G1Projective::rand(&mut rng).into_affine();
-            let lhs: Matrix<Com1<F>> = vec![vec![
-                Com1::<F>(G1Affine::zero(), g1gen),
-                Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "2")),
-                Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "3")),
-            ]];
-            // 3 x 1 (column) vector
-            let rhs: Matrix<Fr> = vec![
-                vec![Fr::from_str("4").unwrap()],
-                vec![Fr::from_str("5").unwrap()],
-                vec![Fr::from_str("6").unwrap()],
+            // 1 x 2 scalar (row) vector
+            let scalars: Matrix<Fr> =
+                vec![vec![Fr::from_str("2").unwrap(), Fr::from_str("3").unwrap()]];
+            // 2 x 1 (column) vector
+            let coms: Matrix<Com1<F>> = vec![
+                vec![Com1::<F>(G1Affine::zero(), g1gen)],
+                vec![Com1::<F>(g1gen, affine_group_new!(g1gen, "4"))],
             ];
-            let exp: Matrix<Com1<F>> = vec![vec![Com1::<F>(
-                G1Affine::zero(),
-                affine_group_new!(g1gen, "32"),
-            )]];
-            let res: Matrix<Com1<F>> = lhs.right_mul(&rhs, false);
-
-            assert_matrix_dimensions!(res, 1, 1);
+            let exp: Matrix<Com1<F>> = vec![
+                vec![
+                    Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "2")),
+                    Com1::<F>(G1Affine::zero(), affine_group_new!(g1gen, "3")),
+                ],
+                vec![
+                    Com1::<F>(affine_group_new!(g1gen, "2"), affine_group_new!(g1gen, "8")),
+                    Com1::<F>(
+                        affine_group_new!(g1gen, "3"),
+                        affine_group_new!(g1gen, "12"),
+                    ),
+                ],
+            ];
+            let res: Matrix<Com1<F>> = kron_scalar_com::<F, _>(&scalars, &coms);
 
+            assert_matrix_dimensions!(res, 2, 2);
             assert_eq!(exp, res);
         }
 
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_mul_par_matches_serial() {
+            let mut rng = test_rng();
+            // 8 x 8 scalars and 8 x 8 commitments
+            let dim = 8;
+            let scalars: Matrix<Fr> = (0..dim)
+                .map(|_| (0..dim).map(|_| Fr::rand(&mut rng)).collect())
+                .collect();
+            let coms: Matrix<Com1<F>> = (0..dim)
+                .map(|_| {
+                    (0..dim)
+                        .map(|_| {
+                            Com1::<F>(
+                                G1Projective::rand(&mut rng).into_affine(),
+                                G1Projective::rand(&mut rng).into_affine(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let left_serial = coms.left_mul(&scalars, false);
+            let right_serial = coms.right_mul(&scalars, false);
+            assert_matrix_dimensions!(left_serial, dim, dim);
+            assert_matrix_dimensions!(right_serial, dim, dim);
+            assert_eq!(coms.left_mul(&scalars, true), left_serial);
+            assert_eq!(coms.right_mul(&scalars, true), right_serial);
+        }
+
+        // Large enough for the thread pool to split the work unevenly. The product is cheap enough to
+        // run by default because the scalars are sparse and small, so most MSM buckets stay empty, and
+        // the commitments are small multiples of two random points
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_mul_par_matches_serial_64() {
+            let mut rng = test_rng();
+            let dim = 64;
+            let scalars: Matrix<Fr> =
+                MatrixExt::from_fn((dim, dim), |_, _| match rng.gen_range(0..32u64) {
+                    small @ 1..=3 => Fr::from(small),
+                    _ => Fr::zero(),
+                });
+            let (p, q) = (G1Projective::rand(&mut rng), G1Projective::rand(&mut rng));
+            let multiples = |base: G1Projective| {
+                let mut acc = base;
+                let points: Vec<G1Projective> = (0..dim * dim)
+                    .map(|_| {
+                        acc += base;
+                        acc
+                    })
+                    .collect();
+                G1Projective::normalize_batch(&points)
+            };
+            let (ps, qs) = (multiples(p), multiples(q));
+            let coms: Matrix<Com1<F>> = MatrixExt::from_fn((dim, dim), |i, j| {
+                Com1::<F>(ps[i * dim + j], qs[i * dim + j])
+            });
+
+            let left_serial = coms.left_mul(&scalars, false);
+            let right_serial = coms.right_mul(&scalars, false);
+            assert_matrix_dimensions!(left_serial, dim, dim);
+            assert_matrix_dimensions!(right_serial, dim, dim);
+            assert_eq!(coms.left_mul(&scalars, true), left_serial);
+            assert_eq!(coms.right_mul(&scalars, true), right_serial);
+        }
+
         #[test]
         fn test_field_matrix_scalar_mul() {
             // 3 x 3 matrices
@@ -2499,6 +6930,247 @@ mod tests {
             assert_eq!(lr, rl);
         }
 
+        #[test]
+        fn test_matrix_into_transpose_neg() {
+            let mut rng = test_rng();
+            for shape in [(2, 3), (1, 4), (4, 1)] {
+                let field: Matrix<Fr> =
+                    <Matrix<Fr> as MatrixExt<Fr>>::from_fn(shape, |_, _| Fr::rand(&mut rng));
+                assert_eq!(field.clone().into_transpose(), field.transpose());
+                assert_eq!(field.clone().into_neg(), field.neg());
+
+                let com1: Matrix<Com1<F>> =
+                    <Matrix<Com1<F>> as MatrixExt<Com1<F>>>::from_fn(shape, |_, _| {
+                        Com1::<F>(
+                            G1Projective::rand(&mut rng).into_affine(),
+                            G1Projective::rand(&mut rng).into_affine(),
+                        )
+                    });
+                assert_eq!(com1.clone().into_transpose(), com1.transpose());
+                assert_eq!(com1.clone().into_neg(), com1.neg());
+
+                let com2: Matrix<Com2<F>> =
+                    <Matrix<Com2<F>> as MatrixExt<Com2<F>>>::from_fn(shape, |_, _| {
+                        Com2::<F>(
+                            G2Projective::rand(&mut rng).into_affine(),
+                            G2Projective::rand(&mut rng).into_affine(),
+                        )
+                    });
+                assert_eq!(com2.clone().into_transpose(), com2.transpose());
+                assert_eq!(com2.clone().into_neg(), com2.neg());
+            }
+
+            let empty: Matrix<Fr> = vec![];
+            assert!(empty.clone().into_transpose().is_empty());
+            assert!(empty.into_neg().is_empty());
+        }
+
+        #[test]
+        fn test_field_matrix_ops() {
+            let mut rng = test_rng();
+            let k = Fr::rand(&mut rng);
+            let a: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn((2, 3), |_, _| Fr::rand(&mut rng));
+            let b: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn((2, 3), |_, _| Fr::rand(&mut rng));
+            let c: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn((3, 2), |_, _| Fr::rand(&mut rng));
+            let (fa, fb, fc) = (
+                FieldMatrix(a.clone()),
+                FieldMatrix(b.clone()),
+                FieldMatrix(c.clone()),
+            );
+
+            assert_eq!((&fa + &fb).0, a.add(&b));
+            assert_eq!((&fa - &fb).0, a.add(&b.neg()));
+            assert_eq!((-&fa).0, a.neg());
+            assert_eq!((&fa * &k).0, a.scalar_mul(&k));
+            assert_eq!((&fa * &fc).0, a.right_mul(&c, false));
+            // Owned operands chain
+            assert_eq!(
+                (&fa * &fc + &(&fb * &fc)).0,
+                a.right_mul(&c, false).add(&b.right_mul(&c, false))
+            );
+            assert_eq!(fa.clone() - &fa, FieldMatrix(vec![vec![Fr::zero(); 3]; 2]));
+            assert_eq!(Matrix::from(fa.clone()), a);
+            assert_eq!(fa.len(), 2);
+
+            let empty = FieldMatrix::<Fr>(vec![]);
+            assert_eq!(&empty + &empty, empty);
+            assert_eq!(&empty * &k, empty);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot add a 2 x 3 matrix and a 3 x 2 matrix")]
+        fn test_field_matrix_add_mismatch() {
+            let a = FieldMatrix(vec![vec![Fr::one(); 3]; 2]);
+            let b = FieldMatrix(vec![vec![Fr::one(); 2]; 3]);
+            let _ = &a + &b;
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot multiply a 2 x 3 matrix by a 2 x 3 matrix")]
+        fn test_field_matrix_mul_mismatch() {
+            let a = FieldMatrix(vec![vec![Fr::one(); 3]; 2]);
+            let _ = &a * &a;
+        }
+
+        #[test]
+        fn test_matrix_add_assign() {
+            let mut rng = test_rng();
+            let k = Fr::rand(&mut rng);
+            let shape = (2, 3);
+
+            let field_a: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn(shape, |_, _| Fr::rand(&mut rng));
+            let field_b: Matrix<Fr> =
+                <Matrix<Fr> as MatrixExt<Fr>>::from_fn(shape, |_, _| Fr::rand(&mut rng));
+            let mut sum = field_a.clone();
+            sum.add_assign(&field_b);
+            assert_eq!(sum, field_a.add(&field_b));
+            let mut scaled = field_a.clone();
+            scaled.scaled_add_assign(&k, &field_b);
+            assert_eq!(scaled, field_a.add(&field_b.scalar_mul(&k)));
+
+            let com1_a: Matrix<Com1<F>> =
+                <Matrix<Com1<F>> as MatrixExt<Com1<F>>>::from_fn(shape, |_, _| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+            let com1_b: Matrix<Com1<F>> = com1_a.scalar_mul(&Fr::rand(&mut rng));
+            let mut sum = com1_a.clone();
+            let row = sum[1].as_ptr();
+            sum.add_assign(&com1_b);
+            assert_eq!(sum, com1_a.add(&com1_b));
+            // The sums are written into the existing rows
+            assert_eq!(sum[1].as_ptr(), row);
+            let mut scaled = com1_a.clone();
+            scaled.scaled_add_assign(&k, &com1_b);
+            assert_eq!(scaled, com1_a.add(&com1_b.scalar_mul(&k)));
+
+            let com2_a: Matrix<Com2<F>> =
+                <Matrix<Com2<F>> as MatrixExt<Com2<F>>>::from_fn(shape, |_, _| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+            let com2_b: Matrix<Com2<F>> = com2_a.neg().scalar_mul(&Fr::rand(&mut rng));
+            let mut sum = com2_a.clone();
+            sum.add_assign(&com2_b);
+            assert_eq!(sum, com2_a.add(&com2_b));
+            let mut scaled = com2_a.clone();
+            scaled.scaled_add_assign(&k, &com2_b);
+            assert_eq!(scaled, com2_a.add(&com2_b.scalar_mul(&k)));
+
+            // Accumulating from the zero matrix of the shape
+            let mut acc: Matrix<Fr> = MatrixExt::zeros(shape);
+            acc.scaled_add_assign(&k, &field_b);
+            assert_eq!(acc, field_b.scalar_mul(&k));
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_matrix_add_assign_mismatch() {
+            let mut lhs: Matrix<Fr> = vec![vec![Fr::one(); 2]; 2];
+            lhs.add_assign(&vec![vec![Fr::one(); 3]; 2]);
+        }
+
+        #[test]
+        fn test_com_matrix_add_matches_entries() {
+            let mut rng = test_rng();
+            for shape in [(4, 1), (3, 2), (2, 0)] {
+                let com1_a: Matrix<Com1<F>> = MatrixExt::from_fn(shape, |_, _| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+                let mut com1_b: Matrix<Com1<F>> = MatrixExt::from_fn(shape, |_, _| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+                // Including an entry that sums to zero
+                if shape.1 > 0 {
+                    com1_b[0][0] = -com1_a[0][0];
+                }
+                let exp: Matrix<Com1<F>> =
+                    MatrixExt::from_fn(shape, |i, j| com1_a[i][j] + com1_b[i][j]);
+                assert_eq!(com1_a.add(&com1_b), exp);
+                let mut sum = com1_a.clone();
+                sum.add_assign(&com1_b);
+                assert_eq!(sum, exp);
+
+                let com2_a: Matrix<Com2<F>> = MatrixExt::from_fn(shape, |_, _| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+                let com2_b: Matrix<Com2<F>> = MatrixExt::from_fn(shape, |_, _| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                });
+                let exp: Matrix<Com2<F>> =
+                    MatrixExt::from_fn(shape, |i, j| com2_a[i][j] + com2_b[i][j]);
+                assert_eq!(com2_a.add(&com2_b), exp);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot transpose a matrix with rows of different lengths")]
+        fn test_matrix_into_transpose_ragged() {
+            let _ = vec![vec![Fr::one(); 2], vec![Fr::one(); 3]].into_transpose();
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot multiply by a matrix with rows of different lengths")]
+        fn test_com_matrix_mul_ragged() {
+            let com = Com1::<F>::zero();
+            let _ = vec![vec![com; 2], vec![com]].right_mul(&vec![vec![Fr::one()]; 2], false);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot add a 2 x 2 matrix and a 2 x 2 matrix")]
+        fn test_com_matrix_add_ragged() {
+            let com = Com1::<F>::zero();
+            let _ = vec![vec![com; 2], vec![com]].add(&vec![vec![com; 2], vec![com; 2]]);
+        }
+
+        #[test]
+        fn test_empty_matrix_add_transpose() {
+            let mut rng = test_rng();
+            let empty: Matrix<Fr> = vec![];
+            let mat: Matrix<Fr> = vec![vec![Fr::rand(&mut rng), Fr::rand(&mut rng)]];
+
+            // The empty matrix is not the zero matrix of another shape
+            assert_eq!(mat.add(&MatrixExt::zeros((1, 2))), mat);
+            assert_eq!(empty.add(&empty), empty);
+            assert_eq!(empty.transpose(), empty);
+
+            let empty_com: Matrix<Com1<F>> = vec![];
+            let mat_com: Matrix<Com1<F>> = vec![vec![Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            )]];
+            assert_eq!(mat_com.add(&MatrixExt::zeros((1, 1))), mat_com);
+            assert_eq!(empty_com.add(&empty_com), empty_com);
+            assert_eq!(empty_com.transpose(), empty_com);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot add a 0 x 0 matrix and a 1 x 2 matrix")]
+        fn test_empty_matrix_add_mismatch() {
+            let empty: Matrix<Fr> = vec![];
+            let _ = empty.add(&vec![vec![Fr::one(); 2]]);
+        }
+
         #[test]
         fn test_B1_matrix_add() {
             // 3 x 3 matrices
@@ -2631,4 +7303,192 @@ mod tests {
             assert_eq!(lr, rl);
         }
     }
+
+    mod sparse {
+
+        use ark_bls12_381::Bls12_381 as F;
+        use ark_ec::pairing::Pairing;
+        use ark_ff::UniformRand;
+        use ark_std::rand::Rng;
+        use ark_std::test_rng;
+
+        use super::*;
+
+        type G1Projective = <F as Pairing>::G1;
+        type G2Projective = <F as Pairing>::G2;
+        type Fr = <F as Pairing>::ScalarField;
+
+        // Shapes to compare against, including empty and single-row / single-column ones.
+        const SHAPES: [(usize, usize); 6] = [(0, 0), (1, 1), (1, 4), (4, 1), (3, 5), (6, 6)];
+
+        // A random scalar matrix where roughly a third of the entries are non-zero.
+        fn rand_sparse_dense<R: Rng>(shape: (usize, usize), rng: &mut R) -> Matrix<Fr> {
+            <Matrix<Fr> as MatrixExt<Fr>>::from_fn(shape, |_, _| {
+                if rng.gen_ratio(1, 3) {
+                    Fr::rand(rng)
+                } else {
+                    Fr::zero()
+                }
+            })
+        }
+
+        fn rand_com1_dense<R: Rng>(shape: (usize, usize), rng: &mut R) -> Matrix<Com1<F>> {
+            <Matrix<Com1<F>> as MatrixExt<Com1<F>>>::from_fn(shape, |_, _| {
+                Com1::<F>(
+                    G1Projective::rand(rng).into_affine(),
+                    G1Projective::rand(rng).into_affine(),
+                )
+            })
+        }
+
+        fn rand_com2_dense<R: Rng>(shape: (usize, usize), rng: &mut R) -> Matrix<Com2<F>> {
+            <Matrix<Com2<F>> as MatrixExt<Com2<F>>>::from_fn(shape, |_, _| {
+                Com2::<F>(
+                    G2Projective::rand(rng).into_affine(),
+                    G2Projective::rand(rng).into_affine(),
+                )
+            })
+        }
+
+        #[test]
+        fn test_sparse_dense_round_trip() {
+            let mut rng = test_rng();
+            for shape in SHAPES {
+                let dense = rand_sparse_dense(shape, &mut rng);
+                let sparse = SparseMatrix::from_dense(&dense);
+                assert_eq!(
+                    sparse.nnz(),
+                    dense.iter().flatten().filter(|x| !x.is_zero()).count()
+                );
+                assert!(sparse.iter().all(|(i, j, value)| dense[i][j] == *value));
+                if shape.0 > 0 {
+                    assert_eq!(sparse.shape(), shape);
+                    assert_eq!(Matrix::from(sparse), dense);
+                }
+            }
+        }
+
+        #[test]
+        fn test_sparse_from_triplets() {
+            let mut rng = test_rng();
+            let (a, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+            // Out of order, with a repeated position and an explicit zero
+            let sparse = SparseMatrix::try_from_triplets(
+                (2, 3),
+                vec![(1, 2, a), (0, 1, b), (1, 2, b), (0, 0, Fr::zero())],
+            )
+            .unwrap();
+            assert_eq!(sparse.nnz(), 2);
+            assert_eq!(
+                sparse.to_dense(),
+                vec![
+                    vec![Fr::zero(), b, Fr::zero()],
+                    vec![Fr::zero(), Fr::zero(), a + b]
+                ]
+            );
+            // Cancelling entries are dropped
+            let cancel =
+                SparseMatrix::try_from_triplets((2, 3), vec![(1, 2, a), (1, 2, -a)]).unwrap();
+            assert_eq!(cancel, SparseMatrix::zeros((2, 3)));
+
+            assert_eq!(
+                SparseMatrix::try_from_triplets((2, 3), vec![(2, 0, a)]),
+                Err(ShapeError { expected: (2, 3) })
+            );
+            assert_eq!(
+                SparseMatrix::try_from_triplets((2, 3), vec![(0, 3, a)]),
+                Err(ShapeError { expected: (2, 3) })
+            );
+        }
+
+        #[test]
+        fn test_sparse_scalar_mul_transpose() {
+            let mut rng = test_rng();
+            for shape in SHAPES {
+                let dense = rand_sparse_dense(shape, &mut rng);
+                let sparse = SparseMatrix::from_dense(&dense);
+                let scalar = Fr::rand(&mut rng);
+
+                assert_eq!(sparse.scalar_mul(&Fr::zero()).nnz(), 0);
+                assert_eq!(sparse.transpose().transpose(), sparse);
+                if shape.0 == 0 {
+                    continue;
+                }
+                assert_eq!(
+                    sparse.scalar_mul(&scalar),
+                    SparseMatrix::from_dense(&dense.scalar_mul(&scalar))
+                );
+                assert_eq!(sparse.transpose().to_dense(), dense.transpose());
+            }
+        }
+
+        #[test]
+        fn test_sparse_products_scalar() {
+            let mut rng = test_rng();
+            for shape in SHAPES {
+                let dense = rand_sparse_dense(shape, &mut rng);
+                let sparse = SparseMatrix::from_dense(&dense);
+                if shape.0 == 0 {
+                    continue;
+                }
+                let rhs = rand_sparse_dense((shape.1, 3), &mut rng);
+                let lhs = rand_sparse_dense((2, shape.0), &mut rng);
+
+                assert_eq!(sparse.right_mul(&rhs), rhs.left_mul(&dense, false));
+                assert_eq!(sparse.left_mul(&lhs), lhs.right_mul(&dense, false));
+            }
+        }
+
+        #[test]
+        fn test_sparse_products_com() {
+            let mut rng = test_rng();
+            for shape in SHAPES {
+                let dense = rand_sparse_dense(shape, &mut rng);
+                let sparse = SparseMatrix::from_dense(&dense);
+                if shape.0 == 0 {
+                    continue;
+                }
+
+                let rhs1 = rand_com1_dense((shape.1, 2), &mut rng);
+                let lhs1 = rand_com1_dense((2, shape.0), &mut rng);
+                assert_eq!(sparse.right_mul(&rhs1), rhs1.left_mul(&dense, false));
+                assert_eq!(sparse.left_mul(&lhs1), lhs1.right_mul(&dense, false));
+
+                let rhs2 = rand_com2_dense((shape.1, 2), &mut rng);
+                let lhs2 = rand_com2_dense((2, shape.0), &mut rng);
+                assert_eq!(sparse.right_mul(&rhs2), rhs2.left_mul(&dense, false));
+                assert_eq!(sparse.left_mul(&lhs2), lhs2.right_mul(&dense, false));
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_sparse_right_mul_mismatch() {
+            let sparse = SparseMatrix::<Fr>::zeros((2, 3));
+            let rhs: Matrix<Fr> = vec![vec![Fr::one()]; 2];
+            let _ = sparse.right_mul(&rhs);
+        }
+
+        #[test]
+        fn test_sparse_serde() {
+            let mut rng = test_rng();
+            let sparse = SparseMatrix::from_dense(&rand_sparse_dense((4, 5), &mut rng));
+
+            let mut bytes = Vec::new();
+            sparse.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), sparse.compressed_size());
+            let de = SparseMatrix::<Fr>::deserialize_compressed(&bytes[..]).unwrap();
+            assert_eq!(de, sparse);
+
+            // A triplet out of range is rejected
+            let bad = SparseMatrix {
+                shape: (1, 1),
+                entries: vec![(0, 1, Fr::one())],
+            };
+            let mut bytes = Vec::new();
+            bad.serialize_compressed(&mut bytes).unwrap();
+            assert!(SparseMatrix::<Fr>::deserialize_compressed(&bytes[..]).is_err());
+            assert!(SparseMatrix::<Fr>::deserialize_compressed_unchecked(&bytes[..]).is_ok());
+        }
+    }
 }
@@ -0,0 +1,135 @@
+//! The crate-wide [`Error`](self::Error), which the focused error of each module converts into, e.g. to
+//! propagate errors from several modules with `?` in a single function.
+//!
+//! Each module keeps its own error type ([`ShapeError`], [`CrsError`], [`CommitError`],
+//! [`VerificationError`], [`WireError`], ...) for the APIs that can only fail in its ways, so matching
+//! on those stays exhaustive; [`Error`] wraps them as its [`source`](ark_std::error::Error::source).
+
+use ark_serialize::SerializationError;
+use ark_std::fmt::{self, Display, Formatter};
+
+use crate::data_structures::{
+    MatrixError, PairingSumError, PermError, ShapeError, UnsupportedVersion,
+};
+use crate::generator::CrsError;
+use crate::prover::CommitError;
+use crate::verifier::VerificationError;
+use crate::wire::WireError;
+
+/// Any error of this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A matrix or vector does not have the expected dimensions.
+    Shape(ShapeError),
+    /// A list of rows is not a matrix.
+    Matrix(MatrixError),
+    /// A list of indices is not a permutation.
+    Perm(PermError),
+    /// Two vectors that are paired together have different lengths.
+    PairingSum(PairingSumError),
+    /// The CRS, or the generators it is generated over, is invalid.
+    Crs(CrsError),
+    /// The variables could not be committed.
+    Commit(CommitError),
+    /// The proof is malformed with respect to the equation it is verified against.
+    Verification(VerificationError),
+    /// The proof is well-formed, but the committed variables do not satisfy the equation.
+    VerificationFailed,
+    /// Encoding or decoding in the [`wire`](crate::wire) format failed.
+    Wire(WireError),
+    /// Canonical serialization or deserialization failed.
+    Serialization(SerializationError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Shape(err) => write!(f, "{}", err),
+            Error::Matrix(err) => write!(f, "{}", err),
+            Error::Perm(err) => write!(f, "{}", err),
+            Error::PairingSum(err) => write!(f, "{}", err),
+            Error::Crs(err) => write!(f, "invalid CRS: {}", err),
+            Error::Commit(err) => write!(f, "cannot commit: {}", err),
+            Error::Verification(err) => write!(f, "malformed proof: {}", err),
+            Error::VerificationFailed => write!(f, "the proof does not verify"),
+            Error::Wire(err) => write!(f, "wire format: {}", err),
+            Error::Serialization(err) => write!(f, "serialization: {}", err),
+        }
+    }
+}
+
+impl ark_std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn ark_std::error::Error + 'static)> {
+        match self {
+            Error::Shape(err) => Some(err),
+            Error::Matrix(err) => Some(err),
+            Error::Perm(err) => Some(err),
+            Error::PairingSum(err) => Some(err),
+            Error::Crs(err) => Some(err),
+            Error::Commit(err) => Some(err),
+            Error::Verification(err) => Some(err),
+            Error::VerificationFailed => None,
+            Error::Wire(err) => Some(err),
+            Error::Serialization(err) => Some(err),
+        }
+    }
+}
+
+macro_rules! impl_from_errors {
+    ($($err:ty => $variant:ident),*) => {
+        $(
+            impl From<$err> for Error {
+                fn from(err: $err) -> Self {
+                    Error::$variant(err)
+                }
+            }
+        )*
+    }
+}
+impl_from_errors!(
+    ShapeError => Shape,
+    MatrixError => Matrix,
+    PermError => Perm,
+    PairingSumError => PairingSum,
+    CrsError => Crs,
+    CommitError => Commit,
+    VerificationError => Verification,
+    WireError => Wire,
+    SerializationError => Serialization
+);
+
+/// An unsupported version is a deserialization error, as in its conversion into [`SerializationError`].
+impl From<UnsupportedVersion> for Error {
+    fn from(err: UnsupportedVersion) -> Self {
+        Error::Serialization(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::error::Error as _;
+
+    #[test]
+    fn test_error_conversions() {
+        let err: Error = ShapeError { expected: (1, 2) }.into();
+        assert!(matches!(err, Error::Shape(ShapeError { expected: (1, 2) })));
+        assert!(err.source().is_some());
+
+        let err: Error = CommitError::NotIdentity(3).into();
+        assert_eq!(
+            err.to_string(),
+            "cannot commit: variable 3 is marked as the identity, but is not"
+        );
+
+        let err: Error = UnsupportedVersion { found: 9 }.into();
+        assert!(matches!(err, Error::Serialization(_)));
+
+        assert!(Error::VerificationFailed.source().is_none());
+        assert_eq!(
+            Error::VerificationFailed.to_string(),
+            "the proof does not verify"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Proof that a committed scalar `b` is a bit, i.e. `b (b - 1) = 0`.
+//!
+//! The scalar is committed to both [`B1`](crate::data_structures::B1) (as `x`) and
+//! [`B2`](crate::data_structures::B2) (as `y`), and the proof consists of
+//!
+//! - a proof of the [`bit`](crate::statement::QuadEqu::bit) equation `x y - x = 0`, and
+//! - a [`scalar_equality`](crate::statement::QuadEqu::scalar_equality) proof that `x = y`,
+//!
+//! since the first equation alone also holds for `x = 0` or `y = 1` with any other committed scalar.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+
+use crate::generator::CRS;
+use crate::prover::{
+    commit_scalar_to_B1, commit_scalar_to_B2, prove_scalar_equality, CProof, Commit1, Commit2,
+    EquProof, Provable,
+};
+use crate::statement::QuadEqu;
+use crate::verifier::scalar_equality_holds;
+
+/// A proof from [`prove`], which holds no randomness and is safe to publish.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BitProof<E: Pairing> {
+    /// The proof of `x y - x = 0`.
+    pub bit_proof: EquProof<E>,
+    /// The proof that `x = y`.
+    pub equality_proof: EquProof<E>,
+}
+
+/// Commits to the bit `b` as a scalar in both [`B1`](crate::data_structures::B1) and
+/// [`B2`](crate::data_structures::B2), and proves that it is a bit.
+///
+/// The commitments keep their randomness, e.g. to also use `b` in other equations; publish them with
+/// [`from_coms`](Commit1::from_coms).
+pub fn prove<E, CR>(b: bool, crs: &CRS<E>, rng: &mut CR) -> (Commit1<E>, Commit2<E>, BitProof<E>)
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let b = if b {
+        E::ScalarField::one()
+    } else {
+        E::ScalarField::zero()
+    };
+    let com1: Commit1<E> = commit_scalar_to_B1(&b, crs, rng);
+    let com2: Commit2<E> = commit_scalar_to_B2(&b, crs, rng);
+    let bit_proof = QuadEqu::<E>::bit().prove(&[b], &[b], &com1, &com2, crs, rng);
+    let equality_proof = prove_scalar_equality(&com1, &com2, &b, crs, rng);
+    let proof = BitProof::<E> {
+        bit_proof: bit_proof.without_randomness(),
+        equality_proof: equality_proof.without_randomness(),
+    };
+    (com1, com2, proof)
+}
+
+/// Verifies a proof from [`prove`] that `com1` and `com2` commit to the same bit.
+pub fn verify<E: Pairing>(
+    com1: &Commit1<E>,
+    com2: &Commit2<E>,
+    proof: &BitProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    let crs_fingerprint = crs.fingerprint();
+    let com_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(com1.coms.clone()),
+        ycoms: Commit2::<E>::from_coms(com2.coms.clone()),
+        equ_proofs: vec![proof.bit_proof.clone()],
+        crs_fingerprint,
+    };
+    QuadEqu::<E>::bit().verify_assembled(&com_proof, crs)
+        && scalar_equality_holds(com1, com2, &proof.equality_proof, crs, crs_fingerprint)
+}
@@ -0,0 +1,78 @@
+//! Proof of possession of a valid BLS signature `σ` on a message hash `H(m)` under a public key `pk`,
+//! without revealing the signature.
+//!
+//! BLS verification `e(σ, g2) = e(H(m), pk)` is a single [`PPE`](crate::statement::PPE) with `σ` as its
+//! only (hidden) `x` variable, the generator `g2` of [`G2`](ark_ec::Pairing::G2Affine) as its constant
+//! `B` and `e(H(m), pk)` as its target. The equation is linear in `x` only, so its proof leaves out `θ`.
+//! A BLS signature is unique, so there is no other witness to be indistinguishable from: the proof
+//! hides `σ` itself, in that the commitment to it reveals nothing about `σ` under SXDH.
+
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+
+use crate::data_structures::Com1;
+use crate::generator::CRS;
+use crate::prover::{commit_G1, CProof, Commit1, Commit2, EquProof, Provable};
+use crate::statement::PPE;
+
+/// A proof from [`prove_possession`], which holds no randomness and is safe to publish.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PossessionProof<E: Pairing> {
+    /// The commitment to the signature `σ` in [`B1`](crate::data_structures::B1).
+    pub sig_com: Com1<E>,
+    /// The proof of `e(σ, g2) = e(H(m), pk)`.
+    pub proof: EquProof<E>,
+}
+
+// e(σ, g2) = e(H(m), pk), with σ as the only x variable and no y variables
+fn verification_equ<E: Pairing>(msg_hash: &E::G1Affine, pk: &E::G2Affine) -> PPE<E> {
+    PPE::<E> {
+        a_consts: vec![],
+        b_consts: vec![E::G2Affine::generator()],
+        gamma: vec![vec![]],
+        target: E::pairing(*msg_hash, *pk),
+    }
+}
+
+/// Proves possession of a BLS signature `sig` on `msg_hash` (i.e. `H(m)` in `G1`) under `pk`, for
+/// public keys `pk = sk g2` over the standard generator `g2` of `G2`.
+///
+/// The proof only verifies if `sig` is a valid signature.
+pub fn prove_possession<E, CR>(
+    sig: E::G1Affine,
+    msg_hash: E::G1Affine,
+    pk: E::G2Affine,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> PossessionProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let sig_com: Commit1<E> = commit_G1(&sig, crs, rng);
+    let no_ycoms = Commit2::<E>::from_coms(vec![]);
+    let proof = verification_equ(&msg_hash, &pk).prove(&[sig], &[], &sig_com, &no_ycoms, crs, rng);
+    PossessionProof::<E> {
+        sig_com: sig_com.coms[0],
+        proof: proof.without_randomness(),
+    }
+}
+
+/// Verifies a proof from [`prove_possession`] that the prover holds a valid BLS signature on `msg_hash`
+/// under `pk`.
+pub fn verify_possession<E: Pairing>(
+    msg_hash: E::G1Affine,
+    pk: E::G2Affine,
+    bundle: &PossessionProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    let com_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(vec![bundle.sig_com]),
+        ycoms: Commit2::<E>::from_coms(vec![]),
+        equ_proofs: vec![bundle.proof.clone()],
+        crs_fingerprint: crs.fingerprint(),
+    };
+    verification_equ(&msg_hash, &pk).verify_assembled(&com_proof, crs)
+}
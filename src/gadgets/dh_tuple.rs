@@ -0,0 +1,121 @@
+//! Proof of knowledge of a Diffie-Hellman tuple, i.e. of a scalar `x` such that `U = x g1` and
+//! `V = x g2` for public `g1, U` in [`G1`](ark_ec::Pairing::G1Affine) and `g2, V` in
+//! [`G2`](ark_ec::Pairing::G2Affine).
+//!
+//! The scalar is committed to both [`B1`](crate::data_structures::B1) and [`B2`](crate::data_structures::B2),
+//! and the proof consists of
+//!
+//! - an [`MSMEG1`](crate::statement::MSMEG1) proof of `x g1 = U` over the commitment in `B2`,
+//! - an [`MSMEG2`](crate::statement::MSMEG2) proof of `x g2 = V` over the commitment in `B1`, and
+//! - a [`scalar_equality`](crate::statement::QuadEqu::scalar_equality) proof that both commitments
+//!   are to the same scalar.
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+
+use crate::data_structures::{Com1, Com2};
+use crate::generator::CRS;
+use crate::prover::{
+    commit_scalar_to_B1, commit_scalar_to_B2, prove_scalar_equality, CProof, Commit1, Commit2,
+    EquProof, Provable,
+};
+use crate::statement::{MSMEG1, MSMEG2};
+use crate::verifier::scalar_equality_holds;
+
+/// A proof from [`prove`], which holds no randomness and is safe to publish.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DhTupleProof<E: Pairing> {
+    /// The commitment to `x` in [`B1`](crate::data_structures::B1).
+    pub xcom1: Com1<E>,
+    /// The commitment to `x` in [`B2`](crate::data_structures::B2).
+    pub xcom2: Com2<E>,
+    /// The proof of `x g1 = U`.
+    pub g1_proof: EquProof<E>,
+    /// The proof of `x g2 = V`.
+    pub g2_proof: EquProof<E>,
+    /// The proof that `xcom1` and `xcom2` commit to the same scalar.
+    pub equality_proof: EquProof<E>,
+}
+
+// x g1 = U, with x as the only (scalar) y variable
+fn g1_equ<E: Pairing>(g1: &E::G1Affine, u: &E::G1Affine) -> MSMEG1<E> {
+    MSMEG1::<E> {
+        a_consts: vec![*g1],
+        b_consts: vec![],
+        gamma: vec![],
+        target: *u,
+    }
+}
+
+// x g2 = V, with x as the only (scalar) x variable
+fn g2_equ<E: Pairing>(g2: &E::G2Affine, v: &E::G2Affine) -> MSMEG2<E> {
+    MSMEG2::<E> {
+        a_consts: vec![],
+        b_consts: vec![*g2],
+        gamma: vec![vec![]],
+        target: *v,
+    }
+}
+
+/// Proves knowledge of `x` such that `U = x g1` and `V = x g2`.
+///
+/// The proof only verifies if `(g1, U, g2, V)` is such a tuple for `x`.
+pub fn prove<E, CR>(
+    x: &E::ScalarField,
+    g1: &E::G1Affine,
+    u: &E::G1Affine,
+    g2: &E::G2Affine,
+    v: &E::G2Affine,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> DhTupleProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let xcom1: Commit1<E> = commit_scalar_to_B1(x, crs, rng);
+    let xcom2: Commit2<E> = commit_scalar_to_B2(x, crs, rng);
+    let no_xcoms = Commit1::<E>::from_coms(vec![]);
+    let no_ycoms = Commit2::<E>::from_coms(vec![]);
+
+    let g1_proof = g1_equ(g1, u).prove(&[], &[*x], &no_xcoms, &xcom2, crs, rng);
+    let g2_proof = g2_equ(g2, v).prove(&[*x], &[], &xcom1, &no_ycoms, crs, rng);
+    let equality_proof = prove_scalar_equality(&xcom1, &xcom2, x, crs, rng);
+    DhTupleProof::<E> {
+        xcom1: xcom1.coms[0],
+        xcom2: xcom2.coms[0],
+        g1_proof: g1_proof.without_randomness(),
+        g2_proof: g2_proof.without_randomness(),
+        equality_proof: equality_proof.without_randomness(),
+    }
+}
+
+/// Verifies a proof from [`prove`] that the prover knows `x` such that `U = x g1` and `V = x g2`.
+pub fn verify<E: Pairing>(
+    g1: &E::G1Affine,
+    u: &E::G1Affine,
+    g2: &E::G2Affine,
+    v: &E::G2Affine,
+    proof: &DhTupleProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    let crs_fingerprint = crs.fingerprint();
+    let xcom1 = Commit1::<E>::from_coms(vec![proof.xcom1]);
+    let xcom2 = Commit2::<E>::from_coms(vec![proof.xcom2]);
+    let g1_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(vec![]),
+        ycoms: xcom2.clone(),
+        equ_proofs: vec![proof.g1_proof.clone()],
+        crs_fingerprint,
+    };
+    let g2_proof = CProof::<E> {
+        xcoms: xcom1.clone(),
+        ycoms: Commit2::<E>::from_coms(vec![]),
+        equ_proofs: vec![proof.g2_proof.clone()],
+        crs_fingerprint,
+    };
+    g1_equ(g1, u).verify_assembled(&g1_proof, crs)
+        && g2_equ(g2, v).verify_assembled(&g2_proof, crs)
+        && scalar_equality_holds(&xcom1, &xcom2, &proof.equality_proof, crs, crs_fingerprint)
+}
@@ -0,0 +1,136 @@
+//! Proof that an ElGamal ciphertext `(C1, C2) = (r g, M + r PK)` in [`G1`](ark_ec::Pairing::G1Affine)
+//! encrypts the element `M` committed in [`B1`](crate::data_structures::B1), without revealing `M` or `r`.
+//!
+//! The generator `g` of the encryption scheme is the CRS generator
+//! [`g1_gen`](crate::generator::CRS::g1_gen), so a key pair is `(sk, PK = sk g)`. The randomness `r` is
+//! committed as a scalar in [`B2`](crate::data_structures::B2), and the proof consists of
+//!
+//! - an [`MSMEG1`](crate::statement::MSMEG1) proof of `r g = C1`, and
+//! - an [`MSMEG1`](crate::statement::MSMEG1) proof of `M + r PK = C2` over the commitment to `M`,
+//!
+//! both over the same commitment to `r`.
+
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::{One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{ops::Mul, rand::Rng};
+
+use crate::data_structures::Com2;
+use crate::generator::CRS;
+use crate::prover::{commit_scalar_to_B2, CProof, Commit1, Commit2, EquProof, Provable};
+use crate::statement::MSMEG1;
+
+/// An ElGamal ciphertext `(C1, C2) = (r g, M + r PK)` of `M` under the public key `PK`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ciphertext<E: Pairing> {
+    pub c1: E::G1Affine,
+    pub c2: E::G1Affine,
+}
+
+impl<E: Pairing> Ciphertext<E> {
+    /// Encrypts `m` under the public key `pk` with the randomness `r`.
+    pub fn encrypt(m: &E::G1Affine, r: &E::ScalarField, pk: &E::G1Affine, crs: &CRS<E>) -> Self {
+        Self {
+            c1: crs.g1_gen.mul(*r).into_affine(),
+            c2: (pk.mul(*r) + m).into_affine(),
+        }
+    }
+}
+
+/// A proof from [`prove_plaintext_equality`], which holds no randomness and is safe to publish.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PlaintextEqualityProof<E: Pairing> {
+    /// The commitment to `r` in [`B2`](crate::data_structures::B2).
+    pub r_com: Com2<E>,
+    /// The proof of `r g = C1`.
+    pub c1_proof: EquProof<E>,
+    /// The proof of `M + r PK = C2`.
+    pub c2_proof: EquProof<E>,
+}
+
+// r g = C1, with r as the only (scalar) y variable
+fn c1_equ<E: Pairing>(cipher: &Ciphertext<E>, crs: &CRS<E>) -> MSMEG1<E> {
+    MSMEG1::<E> {
+        a_consts: vec![crs.g1_gen],
+        b_consts: vec![],
+        gamma: vec![],
+        target: cipher.c1,
+    }
+}
+
+// M + r PK = C2, with M as the only x variable and r as the only (scalar) y variable
+fn c2_equ<E: Pairing>(pk: &E::G1Affine, cipher: &Ciphertext<E>) -> MSMEG1<E> {
+    MSMEG1::<E> {
+        a_consts: vec![*pk],
+        b_consts: vec![E::ScalarField::one()],
+        gamma: vec![vec![E::ScalarField::zero()]],
+        target: cipher.c2,
+    }
+}
+
+/// Proves that `cipher` is the encryption of `m` under `pk` with the randomness `r`, for the commitment
+/// `m_com` to `m` alone (e.g. from [`commit_G1`](crate::prover::commit_G1)).
+///
+/// The proof only verifies if `cipher` is such an encryption of the committed `m`.
+///
+/// # Panics
+///
+/// Panics if `m_com` is not a commitment to a single variable, with its randomness.
+pub fn prove_plaintext_equality<E, CR>(
+    m: &E::G1Affine,
+    r: &E::ScalarField,
+    pk: &E::G1Affine,
+    cipher: &Ciphertext<E>,
+    m_com: &Commit1<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> PlaintextEqualityProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    assert_eq!(m_com.coms.len(), 1);
+    let r_com: Commit2<E> = commit_scalar_to_B2(r, crs, rng);
+    let no_xcoms = Commit1::<E>::from_coms(vec![]);
+
+    let c1_proof = c1_equ(cipher, crs).prove(&[], &[*r], &no_xcoms, &r_com, crs, rng);
+    let c2_proof = c2_equ(pk, cipher).prove(&[*m], &[*r], m_com, &r_com, crs, rng);
+    PlaintextEqualityProof::<E> {
+        r_com: r_com.coms[0],
+        c1_proof: c1_proof.without_randomness(),
+        c2_proof: c2_proof.without_randomness(),
+    }
+}
+
+/// Verifies a proof from [`prove_plaintext_equality`] that `cipher` encrypts, under `pk`, the element
+/// committed in `m_com`.
+///
+/// Returns false if `m_com` is not a commitment to a single variable.
+pub fn verify_plaintext_equality<E: Pairing>(
+    pk: &E::G1Affine,
+    cipher: &Ciphertext<E>,
+    m_com: &Commit1<E>,
+    proof: &PlaintextEqualityProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    if m_com.coms.len() != 1 {
+        return false;
+    }
+    let crs_fingerprint = crs.fingerprint();
+    let r_com = Commit2::<E>::from_coms(vec![proof.r_com]);
+    let c1_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(vec![]),
+        ycoms: r_com.clone(),
+        equ_proofs: vec![proof.c1_proof.clone()],
+        crs_fingerprint,
+    };
+    let c2_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(m_com.coms.clone()),
+        ycoms: r_com,
+        equ_proofs: vec![proof.c2_proof.clone()],
+        crs_fingerprint,
+    };
+    c1_equ(cipher, crs).verify_assembled(&c1_proof, crs)
+        && c2_equ(pk, cipher).verify_assembled(&c2_proof, crs)
+}
@@ -0,0 +1,74 @@
+//! Proof that scalars committed in [`B2`](crate::data_structures::B2) satisfy a linear relation
+//! `Σ a_i y_i = c`, e.g. that committed amounts add up to a budget.
+//!
+//! The relation is the quadratic equation without `x` variables whose constants `a_i` pair with the
+//! committed `y_i` and whose target is `c`, so a commitment (e.g. from
+//! [`batch_commit_scalar_to_B2`](crate::prover::batch_commit_scalar_to_B2)) can be reused across
+//! several relations, each with its own proof.
+
+use ark_ec::pairing::Pairing;
+use ark_std::rand::Rng;
+
+use crate::generator::CRS;
+use crate::prover::{CProof, Commit1, Commit2, EquProof, Provable};
+use crate::statement::QuadEqu;
+
+// The equation `Σ a_i y_i = c`, with an empty Γ (i.e. 0 x n) as there are no x variables
+fn relation<E: Pairing>(coeffs: &[E::ScalarField], constant: E::ScalarField) -> QuadEqu<E> {
+    QuadEqu::<E> {
+        a_consts: coeffs.to_vec(),
+        b_consts: vec![],
+        gamma: vec![],
+        target: constant,
+    }
+}
+
+/// Proves that the `scalars` committed in `scalar_coms` satisfy `Σ coeffs_i scalars_i = constant`.
+///
+/// The returned proof holds no randomness, and only verifies if the relation holds.
+///
+/// # Panics
+///
+/// Panics if `coeffs`, `scalars` and the commitments do not all have the same length.
+pub fn prove_relation<E, CR>(
+    coeffs: &[E::ScalarField],
+    scalars: &[E::ScalarField],
+    scalar_coms: &Commit2<E>,
+    constant: E::ScalarField,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    assert_eq!(coeffs.len(), scalars.len());
+    assert_eq!(scalars.len(), scalar_coms.coms.len());
+    let xcoms = Commit1::<E>::from_coms(vec![]);
+    relation::<E>(coeffs, constant)
+        .prove(&[], scalars, &xcoms, scalar_coms, crs, rng)
+        .without_randomness()
+}
+
+/// Verifies a proof from [`prove_relation`] that the scalars committed in `scalar_coms` satisfy
+/// `Σ coeffs_i y_i = constant`.
+///
+/// Returns false if `coeffs` and the commitments have different lengths.
+pub fn verify_relation<E: Pairing>(
+    coeffs: &[E::ScalarField],
+    constant: E::ScalarField,
+    scalar_coms: &Commit2<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    if coeffs.len() != scalar_coms.coms.len() {
+        return false;
+    }
+    let com_proof = CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(vec![]),
+        ycoms: Commit2::<E>::from_coms(scalar_coms.coms.clone()),
+        equ_proofs: vec![proof.clone()],
+        crs_fingerprint: crs.fingerprint(),
+    };
+    relation::<E>(coeffs, constant).verify_assembled(&com_proof, crs)
+}
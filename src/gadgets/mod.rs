@@ -0,0 +1,14 @@
+//! Ready-made proofs for common statements, built from Groth-Sahai equations whose variables and
+//! consistency are handled internally.
+//!
+//! Each gadget is a module with a function to prove its statement, returning a serializable proof that
+//! holds its commitments and equation proofs (never the prover's randomness), and one to verify it.
+
+pub mod bit;
+pub mod bls;
+pub mod dh_tuple;
+pub mod elgamal;
+pub mod linear;
+pub mod scalar_link;
+pub mod sps;
+pub mod venc;
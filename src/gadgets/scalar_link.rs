@@ -0,0 +1,61 @@
+//! Proof that a scalar committed in [`B1`](crate::data_structures::B1) equals a scalar committed in
+//! [`B2`](crate::data_structures::B2), i.e. the equality of their discrete logarithms across `G1` and
+//! `G2`, e.g. to use the same exponent on both sides of a pairing.
+//!
+//! The scalars may be any entries of larger commitments: the proof is of the
+//! [`scalar_equality`](crate::statement::QuadEqu::scalar_equality) equation `x - y = 0` over the
+//! [selected](crate::prover::Commit1::select) commitments to `x` and `y` only.
+
+use ark_ec::pairing::Pairing;
+use ark_std::rand::Rng;
+
+use crate::generator::CRS;
+use crate::prover::{prove_scalar_equality, Commit1, Commit2, EquProof};
+use crate::verifier::verify_scalar_equality;
+
+/// Proves that the scalar committed at `idx1` of `com_b1` equals the one committed at `idx2` of `com_b2`,
+/// given that common scalar `x`.
+///
+/// Both must be commitments to scalars, with [`commit_scalar_to_B1`](crate::prover::commit_scalar_to_B1)
+/// and [`commit_scalar_to_B2`](crate::prover::commit_scalar_to_B2) (or their batch versions). The
+/// returned proof holds no randomness, and only verifies if both scalars are `x`.
+///
+/// # Panics
+///
+/// Panics if either index is not that of a committed scalar.
+pub fn prove_same_scalar<E, CR>(
+    x: &E::ScalarField,
+    com_b1: &Commit1<E>,
+    com_b2: &Commit2<E>,
+    idx1: usize,
+    idx2: usize,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let (com1, com2) = (com_b1.select(&[idx1]), com_b2.select(&[idx2]));
+    prove_scalar_equality(&com1, &com2, x, crs, rng).without_randomness()
+}
+
+/// Verifies a proof from [`prove_same_scalar`] that the scalar committed at `idx1` of `com_b1` equals
+/// the one committed at `idx2` of `com_b2`, given only the public commitments.
+///
+/// Returns false if either index is not that of a commitment.
+pub fn verify_same_scalar<E: Pairing>(
+    com_b1: &Commit1<E>,
+    com_b2: &Commit2<E>,
+    idx1: usize,
+    idx2: usize,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    if idx1 >= com_b1.coms.len() || idx2 >= com_b2.coms.len() {
+        return false;
+    }
+    let com1 = Commit1::<E>::from_coms(vec![com_b1.coms[idx1]]);
+    let com2 = Commit2::<E>::from_coms(vec![com_b2.coms[idx2]]);
+    verify_scalar_equality(&com1, &com2, proof, crs)
+}
@@ -0,0 +1,231 @@
+//! Proof of knowledge of a structure-preserving signature on a committed message, e.g. for anonymous
+//! credentials.
+//!
+//! The signature scheme is the optimal Type III scheme of [[AGHO '11]](https://eprint.iacr.org/2011/161),
+//! signing messages `M = (M_1, ..., M_l)` in [`G1`](ark_ec::Pairing::G1Affine) with the generators `G` of
+//! `G1` and `H` of [`G2`](ark_ec::Pairing::G2Affine):
+//!
+//! - the signing key is `(v, w_1, ..., w_l, z)` and the verifying key is `(V, W_1, ..., W_l, Z) = (vH, w_1 H, ..., w_l H, zH)`,
+//! - a signature is `(R, S, T) = (rG, (z - rv)G - Σ w_i M_i, r^-1 H)` for a random `r`, and
+//! - it is valid if `e(S, H) e(R, V) Π e(M_i, W_i) = e(G, Z)` and `e(R, T) = e(G, H)`.
+//!
+//! Both verification equations are pairing-product equations over the `x` variables `(R, S, M_1, ..., M_l)`
+//! and the `y` variable `T` (see [`VerifyingKey::equations`]), which are proven over shared commitments.
+//! The proofs are witness-indistinguishable, so they do not tell which valid signature the prover holds.
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{ops::Mul, rand::Rng};
+
+use crate::data_structures::{Com1, Com2};
+use crate::generator::CRS;
+use crate::prover::{
+    batch_commit_G1, commit_G2, CProof, Commit, Commit1, Commit2, EquProof, Provable,
+};
+use crate::statement::PPE;
+
+/// A signing key `(v, w_1, ..., w_l, z)` for messages of `l` elements.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigningKey<E: Pairing> {
+    pub v: E::ScalarField,
+    pub w: Vec<E::ScalarField>,
+    pub z: E::ScalarField,
+}
+
+/// A verifying key `(V, W_1, ..., W_l, Z)` in [`G2`](ark_ec::Pairing::G2Affine).
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifyingKey<E: Pairing> {
+    pub v: E::G2Affine,
+    pub w: Vec<E::G2Affine>,
+    pub z: E::G2Affine,
+}
+
+/// A signature `(R, S, T)`.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Signature<E: Pairing> {
+    pub r: E::G1Affine,
+    pub s: E::G1Affine,
+    pub t: E::G2Affine,
+}
+
+/// A proof from [`prove_signature_knowledge`], which holds no randomness and is safe to publish.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SignatureProof<E: Pairing> {
+    /// The commitments to `R` and `S` in [`B1`](crate::data_structures::B1).
+    pub rs_coms: Vec<Com1<E>>,
+    /// The commitment to `T` in [`B2`](crate::data_structures::B2).
+    pub t_com: Com2<E>,
+    /// The proofs of the [`equations`](VerifyingKey::equations), in order.
+    pub equ_proofs: Vec<EquProof<E>>,
+}
+
+/// Samples a key pair for messages of `l` elements.
+pub fn keygen<E: Pairing, R: Rng>(l: usize, rng: &mut R) -> (SigningKey<E>, VerifyingKey<E>) {
+    let sk = SigningKey::<E> {
+        v: E::ScalarField::rand(rng),
+        w: (0..l).map(|_| E::ScalarField::rand(rng)).collect(),
+        z: E::ScalarField::rand(rng),
+    };
+    let h = E::G2Affine::generator();
+    let vk = VerifyingKey::<E> {
+        v: h.mul(sk.v).into_affine(),
+        w: E::G2::normalize_batch(&sk.w.iter().map(|w| h.mul(*w)).collect::<Vec<_>>()),
+        z: h.mul(sk.z).into_affine(),
+    };
+    (sk, vk)
+}
+
+/// Signs the message `msg`.
+///
+/// # Panics
+///
+/// Panics if `msg` does not have as many elements as the key.
+pub fn sign<E: Pairing, R: Rng>(
+    sk: &SigningKey<E>,
+    msg: &[E::G1Affine],
+    rng: &mut R,
+) -> Signature<E> {
+    assert_eq!(
+        msg.len(),
+        sk.w.len(),
+        "expected a message of {} elements",
+        sk.w.len()
+    );
+    let g = E::G1Affine::generator();
+    // r is nonzero except with negligible probability, and T = r^-1 H needs it to be
+    let r = loop {
+        let r = E::ScalarField::rand(rng);
+        if !r.is_zero() {
+            break r;
+        }
+    };
+    let s = msg
+        .iter()
+        .zip(sk.w.iter())
+        .fold(g.mul(sk.z - r * sk.v), |acc, (m, w)| acc - m.mul(*w));
+    Signature::<E> {
+        r: g.mul(r).into_affine(),
+        s: s.into_affine(),
+        t: E::G2Affine::generator()
+            .mul(r.inverse().expect("r is nonzero"))
+            .into_affine(),
+    }
+}
+
+/// Whether `sig` is a valid signature on `msg`, i.e. satisfies both verification equations.
+pub fn verify<E: Pairing>(vk: &VerifyingKey<E>, msg: &[E::G1Affine], sig: &Signature<E>) -> bool {
+    if msg.len() != vk.w.len() {
+        return false;
+    }
+    let (g, h) = (E::G1Affine::generator(), E::G2Affine::generator());
+    let lhs = E::multi_pairing(
+        [sig.s, sig.r].into_iter().chain(msg.iter().copied()),
+        [h, vk.v].into_iter().chain(vk.w.iter().copied()),
+    );
+    lhs == E::pairing(g, vk.z) && E::pairing(sig.r, sig.t) == E::pairing(g, h)
+}
+
+impl<E: Pairing> VerifyingKey<E> {
+    /// The verification equations as pairing-product equations over the `x` variables
+    /// `(R, S, M_1, ..., M_l)` and the `y` variable `T`:
+    ///
+    /// 1. `e(S, H) e(R, V) Π e(M_i, W_i) = e(G, Z)`, which is linear, and
+    /// 2. `e(R, T) = e(G, H)`.
+    pub fn equations(&self) -> Vec<PPE<E>> {
+        let (g, h) = (E::G1Affine::generator(), E::G2Affine::generator());
+        let m = 2 + self.w.len();
+        let zero_gamma = vec![vec![E::ScalarField::zero()]; m];
+
+        let mut b_consts = vec![self.v, h];
+        b_consts.extend_from_slice(&self.w);
+        let linear = PPE::<E> {
+            a_consts: vec![E::G1Affine::zero()],
+            b_consts,
+            gamma: zero_gamma.clone(),
+            target: E::pairing(g, self.z),
+        };
+
+        let mut gamma = zero_gamma;
+        gamma[0][0] = E::ScalarField::one();
+        let quadratic = PPE::<E> {
+            a_consts: vec![E::G1Affine::zero()],
+            b_consts: vec![E::G2Affine::zero(); m],
+            gamma,
+            target: E::pairing(g, h),
+        };
+        vec![linear, quadratic]
+    }
+}
+
+/// Proves knowledge of a signature `sig` on the message `msg` committed in `msg_coms`, without revealing
+/// either.
+///
+/// `msg_coms` must be the commitments to `msg`, e.g. from [`batch_commit_G1`], so that the message can
+/// also be used in other equations. The proof only verifies if `sig` is a valid signature on `msg`.
+pub fn prove_signature_knowledge<E, CR>(
+    vk: &VerifyingKey<E>,
+    msg: &[E::G1Affine],
+    msg_coms: &Commit1<E>,
+    sig: &Signature<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> SignatureProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let mut xvars = vec![sig.r, sig.s];
+    xvars.extend_from_slice(msg);
+    let yvars = [sig.t];
+
+    let mut xcoms: Commit1<E> = batch_commit_G1(&[sig.r, sig.s], crs, rng);
+    let rs_coms = xcoms.coms.clone();
+    xcoms.append(&mut msg_coms.clone());
+    let ycoms: Commit2<E> = commit_G2(&sig.t, crs, rng);
+
+    let equ_proofs = vk
+        .equations()
+        .iter()
+        .map(|equ| {
+            equ.prove(&xvars, &yvars, &xcoms, &ycoms, crs, rng)
+                .without_randomness()
+        })
+        .collect();
+    SignatureProof::<E> {
+        rs_coms,
+        t_com: ycoms.coms[0],
+        equ_proofs,
+    }
+}
+
+/// Verifies a proof from [`prove_signature_knowledge`] that the prover knows a valid signature on the
+/// message committed in `msg_coms`.
+pub fn verify_signature_knowledge<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    msg_coms: &Commit1<E>,
+    proof: &SignatureProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    let equs = vk.equations();
+    if proof.equ_proofs.len() != equs.len() {
+        return false;
+    }
+    let mut xcoms = proof.rs_coms.clone();
+    xcoms.extend_from_slice(&msg_coms.coms);
+    let xcoms = Commit1::<E>::from_coms(xcoms);
+    let ycoms = Commit2::<E>::from_coms(vec![proof.t_com]);
+    let crs_fingerprint = crs.fingerprint();
+    equs.iter()
+        .zip(proof.equ_proofs.iter())
+        .all(|(equ, equ_proof)| {
+            let com_proof = CProof::<E> {
+                xcoms: xcoms.clone(),
+                ycoms: ycoms.clone(),
+                equ_proofs: vec![equ_proof.clone()],
+                crs_fingerprint,
+            };
+            equ.verify_assembled(&com_proof, crs)
+        })
+}
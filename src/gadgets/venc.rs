@@ -0,0 +1,56 @@
+//! Verifiable encryption of a committed element `M` in [`G1`](ark_ec::Pairing::G1Affine): an ElGamal
+//! ciphertext of `M`, for a designated opener holding the secret key, with a proof that it decrypts to
+//! the element committed in [`B1`](crate::data_structures::B1), e.g. to escrow the identity committed in
+//! a group signature.
+//!
+//! The ciphertext and proof are those of the [`elgamal`](crate::gadgets::elgamal) gadget, whose two
+//! equations share the commitment to the encryption randomness, so a key pair is `(sk, PK = sk g)` for
+//! the CRS generator `g` [`g1_gen`](crate::generator::CRS::g1_gen).
+
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_std::{ops::Mul, rand::Rng, UniformRand};
+
+use crate::gadgets::elgamal::{
+    prove_plaintext_equality, verify_plaintext_equality, Ciphertext, PlaintextEqualityProof,
+};
+use crate::generator::CRS;
+use crate::prover::{commit_G1, Commit1};
+
+/// Commits to `m`, encrypts it under `pk` and proves that the ciphertext encrypts the committed element.
+///
+/// The commitment keeps its randomness, e.g. to also use `m` in other equations; publish it with
+/// [`from_coms`](Commit1::from_coms). The proof holds no randomness.
+pub fn encrypt_and_prove<E, CR>(
+    m: &E::G1Affine,
+    pk: &E::G1Affine,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> (Ciphertext<E>, Commit1<E>, PlaintextEqualityProof<E>)
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let r = E::ScalarField::rand(rng);
+    let cipher = Ciphertext::<E>::encrypt(m, &r, pk, crs);
+    let m_com = commit_G1(m, crs, rng);
+    let proof = prove_plaintext_equality(m, &r, pk, &cipher, &m_com, crs, rng);
+    (cipher, m_com, proof)
+}
+
+/// Verifies a proof from [`encrypt_and_prove`] that `cipher` encrypts, under `pk`, the element
+/// committed in `m_com`.
+pub fn verify<E: Pairing>(
+    pk: &E::G1Affine,
+    cipher: &Ciphertext<E>,
+    m_com: &Commit1<E>,
+    proof: &PlaintextEqualityProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    verify_plaintext_equality(pk, cipher, m_com, proof, crs)
+}
+
+/// Decrypts `cipher` with the secret key `sk` of its public key, i.e. recovers `M = C2 - sk C1`.
+pub fn decrypt<E: Pairing>(sk: &E::ScalarField, cipher: &Ciphertext<E>) -> E::G1Affine {
+    (-cipher.c1.mul(*sk) + cipher.c2).into_affine()
+}
@@ -11,15 +11,28 @@
 //!    1) Perfect soundness string (i.e. perfectly binding), or
 //!    2) Composable witness-indistinguishability string (i.e. perfectly hiding)
 
-use crate::data_structures::{Com1, Com2};
+use crate::data_structures::{
+    deserialize_vec, deserialize_versioned, serialize_vec, serialize_version, serialized_vec_size,
+    Com1, Com2, B1, B2, VERSION_HEADER_SIZE,
+};
+use crate::prover::Provable;
+use crate::statement::{QuadEqu, MSMEG1, MSMEG2, PPE};
+use crate::verifier::Verifiable;
 
 use ark_ec::{
     pairing::{Pairing, PairingOutput},
-    CurveGroup,
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::{
+    fmt::{self, Display, Formatter},
+    ops::Mul,
+    rand::Rng,
 };
-use ark_ff::{UniformRand, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{ops::Mul, rand::Rng};
 
 /// An abstract trait for denoting how to generate a CRS
 pub trait AbstractCrs<E: Pairing> {
@@ -32,7 +45,11 @@ pub trait AbstractCrs<E: Pairing> {
 }
 
 /// Contains the commitment keys and bilinear group generators
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// It serializes with a leading version header, see [`FORMAT_VERSION`](crate::data_structures::FORMAT_VERSION).
+/// A CRS from trusted storage loads fastest uncompressed and unchecked, see
+/// [the serialization tradeoffs](crate::data_structures#serialization).
+#[derive(Clone, Debug)]
 pub struct CRS<E: Pairing> {
     pub u: Vec<Com1<E>>,
     pub v: Vec<Com2<E>>,
@@ -41,7 +58,135 @@ pub struct CRS<E: Pairing> {
     pub gt_gen: PairingOutput<E>,
 }
 
+impl<E: Pairing> CanonicalSerialize for CRS<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_version(&mut writer)?;
+        serialize_vec(&self.u, &mut writer, compress)?;
+        serialize_vec(&self.v, &mut writer, compress)?;
+        self.g1_gen.serialize_with_mode(&mut writer, compress)?;
+        self.g2_gen.serialize_with_mode(&mut writer, compress)?;
+        self.gt_gen.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        VERSION_HEADER_SIZE
+            + serialized_vec_size(&self.u, compress)
+            + serialized_vec_size(&self.v, compress)
+            + self.g1_gen.serialized_size(compress)
+            + self.g2_gen.serialized_size(compress)
+            + self.gt_gen.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for CRS<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.u.check()?;
+        self.v.check()?;
+        self.g1_gen.check()?;
+        self.g2_gen.check()?;
+        self.gt_gen.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for CRS<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        // A CRS has two commitment keys in each of `u` and `v`
+        deserialize_versioned(reader, |mut reader, version| {
+            Ok(CRS::<E> {
+                u: deserialize_vec(&mut reader, version, compress, validate, 2)?,
+                v: deserialize_vec(&mut reader, version, compress, validate, 2)?,
+                g1_gen: E::G1Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+                g2_gen: E::G2Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+                gt_gen: PairingOutput::<E>::deserialize_with_mode(&mut reader, compress, validate)?,
+            })
+        })
+    }
+}
+
 impl<E: Pairing> CRS<E> {
+    /// The natural generator `e(g1, g2)` of `GT`, e.g. as a base for the target of scalar exponent relations.
+    ///
+    /// The pairing is computed once when the CRS is generated and cached as
+    /// [`gt_gen`](CRS::gt_gen), so this does not compute another pairing.
+    #[inline]
+    pub fn pt(&self) -> PairingOutput<E> {
+        self.gt_gen
+    }
+
+    /// The first commitment key `u_1` for [`B1`](crate::data_structures::B1), i.e. the only key used
+    /// to commit to scalars.
+    #[inline]
+    pub fn u1(&self) -> Com1<E> {
+        self.u[0]
+    }
+
+    /// The second commitment key `u_2` for [`B1`](crate::data_structures::B1), which the scalar linear
+    /// map is based on as `u_2 + (O, g1)`.
+    #[inline]
+    pub fn u2(&self) -> Com1<E> {
+        self.u[1]
+    }
+
+    /// The first commitment key `v_1` for [`B2`](crate::data_structures::B2), i.e. the only key used
+    /// to commit to scalars.
+    #[inline]
+    pub fn v1(&self) -> Com2<E> {
+        self.v[0]
+    }
+
+    /// The second commitment key `v_2` for [`B2`](crate::data_structures::B2), which the scalar linear
+    /// map is based on as `v_2 + (O, g2)`.
+    #[inline]
+    pub fn v2(&self) -> Com2<E> {
+        self.v[1]
+    }
+
+    /// Returns whether `other` was generated over the same bilinear group generators as this CRS.
+    ///
+    /// Compatible CRS instances may still hold different commitment keys; use
+    /// [`fingerprint`](CRS::fingerprint) to tell whether two CRS instances are identical.
+    pub fn is_compatible(&self, other: &CRS<E>) -> bool {
+        self.g1_gen == other.g1_gen && self.g2_gen == other.g2_gen && self.gt_gen == other.gt_gen
+    }
+
+    /// A short identifier of this CRS, computed as a 64-bit FNV-1a hash over its compressed serialization.
+    ///
+    /// The fingerprint is embedded in every [`CProof`](crate::prover::CProof) so that a proof cannot
+    /// accidentally be verified against a different CRS than the one used to commit to its variables.
+    /// It only catches such accidental mismatches: it is not collision-resistant and must not be
+    /// relied upon against a malicious prover.
+    ///
+    /// The serialization is hashed as it is written, without buffering it. Callers that check many
+    /// proofs against the same CRS should still compute the fingerprint once and reuse it.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a(Fnv1a::OFFSET_BASIS);
+        // Over the fields only, so that the fingerprint does not change with the format version
+        self.serialize_fields(&mut hasher, Compress::Yes)
+            .expect("hashing a serialization cannot fail");
+        hasher.0
+    }
+
+    // The fields as serialized in version 0, i.e. without the version header
+    fn serialize_fields<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.u.serialize_with_mode(&mut writer, compress)?;
+        self.v.serialize_with_mode(&mut writer, compress)?;
+        self.g1_gen.serialize_with_mode(&mut writer, compress)?;
+        self.g2_gen.serialize_with_mode(&mut writer, compress)?;
+        self.gt_gen.serialize_with_mode(&mut writer, compress)
+    }
+
     // Returns intermediate "second" values that are used to construct un-blinded (i.e. binding) committment keys
     #[inline(always)]
     #[allow(unused_variables)]
@@ -61,7 +206,6 @@ impl<E: Pairing> CRS<E> {
 
     // Returns intermediate "second" values that are used to construct blinded (i.e. hiding) committment keys
     #[inline(always)]
-    #[allow(dead_code)]
     fn prepare_simulated_hinding_key(
         g1_gen: E::G1,
         g2_gen: E::G2,
@@ -77,6 +221,183 @@ impl<E: Pairing> CRS<E> {
     }
 }
 
+/// Proves that a [`contribute`](CRS::contribute) call re-randomized the commitment keys of a CRS
+/// without otherwise changing them, as checked by [`verify_contribution`](CRS::verify_contribution).
+///
+/// A contribution scales the second entry of every key in `u` by a secret `α` and then `u_2` by a
+/// secret `s`, i.e. `u_1' = (u_11, α u_12)` and `u_2' = s (u_21, α u_22)`, and likewise `v` by the
+/// secrets `β` and `r`. The proof holds these secrets "in the exponent" of the generators, which
+/// the verifier pairs with the old and new keys.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ContributionProof<E: Pairing> {
+    /// `α g2`
+    pub u_alpha: E::G2Affine,
+    /// `s g1`
+    pub u_s1: E::G1Affine,
+    /// `s g2`
+    pub u_s2: E::G2Affine,
+    /// `sα g2`
+    pub u_s_alpha: E::G2Affine,
+    /// `β g1`
+    pub v_beta: E::G1Affine,
+    /// `r g2`
+    pub v_r2: E::G2Affine,
+    /// `r g1`
+    pub v_r1: E::G1Affine,
+    /// `rβ g1`
+    pub v_r_beta: E::G1Affine,
+}
+
+impl<E: Pairing> CRS<E> {
+    /// Re-randomizes the commitment keys with fresh secrets, e.g. as one participant's step in a
+    /// multi-party setup ceremony, and returns the new CRS with a [`ContributionProof`] for it.
+    ///
+    /// The bilinear group generators are unchanged. A contribution only re-randomizes the keys: a
+    /// binding CRS stays binding, and a hiding CRS (e.g. from [`generate_hiding_crs`](CRS::generate_hiding_crs))
+    /// stays hiding, so a ceremony must start from a binding CRS. The extraction trapdoor of the new
+    /// keys depends on the secrets of this contribution, so after a chain of verified contributions,
+    /// nobody knows it for the final CRS as long as one participant was honest and discarded their secrets.
+    pub fn contribute<R: Rng>(&self, rng: &mut R) -> (CRS<E>, ContributionProof<E>) {
+        let alpha = E::ScalarField::rand(rng);
+        let s = E::ScalarField::rand(rng);
+        let beta = E::ScalarField::rand(rng);
+        let r = E::ScalarField::rand(rng);
+
+        let u1 = Com1::<E>(self.u1().0, self.u1().1.mul(alpha).into_affine());
+        let u2 = Com1::<E>(
+            self.u2().0.mul(s).into_affine(),
+            self.u2().1.mul(s * alpha).into_affine(),
+        );
+        let v1 = Com2::<E>(self.v1().0, self.v1().1.mul(beta).into_affine());
+        let v2 = Com2::<E>(
+            self.v2().0.mul(r).into_affine(),
+            self.v2().1.mul(r * beta).into_affine(),
+        );
+
+        let proof = ContributionProof::<E> {
+            u_alpha: self.g2_gen.mul(alpha).into_affine(),
+            u_s1: self.g1_gen.mul(s).into_affine(),
+            u_s2: self.g2_gen.mul(s).into_affine(),
+            u_s_alpha: self.g2_gen.mul(s * alpha).into_affine(),
+            v_beta: self.g1_gen.mul(beta).into_affine(),
+            v_r2: self.g2_gen.mul(r).into_affine(),
+            v_r1: self.g1_gen.mul(r).into_affine(),
+            v_r_beta: self.g1_gen.mul(r * beta).into_affine(),
+        };
+        (
+            CRS::<E> {
+                u: vec![u1, u2],
+                v: vec![v1, v2],
+                ..self.clone()
+            },
+            proof,
+        )
+    }
+
+    /// Returns whether `next` was derived from this CRS by [`contribute`](CRS::contribute) with the
+    /// (nonzero) secrets of `proof`, i.e. whether it has the same generators and its commitment keys
+    /// are a re-randomization of these.
+    pub fn verify_contribution(&self, next: &CRS<E>, proof: &ContributionProof<E>) -> bool {
+        if !self.is_compatible(next)
+            || self.u.len() != 2
+            || self.v.len() != 2
+            || next.u.len() != 2
+            || next.v.len() != 2
+        {
+            return false;
+        }
+        if proof.u_alpha.is_zero()
+            || proof.u_s2.is_zero()
+            || proof.v_beta.is_zero()
+            || proof.v_r1.is_zero()
+        {
+            return false;
+        }
+        let (g1, g2) = (self.g1_gen, self.g2_gen);
+
+        // The secrets are consistent across the groups, i.e. `s g1 ~ s g2`, `(s, α) ~ sα` and likewise for `v`
+        let u_secrets = E::pairing(proof.u_s1, g2) == E::pairing(g1, proof.u_s2)
+            && E::pairing(proof.u_s1, proof.u_alpha) == E::pairing(g1, proof.u_s_alpha);
+        let v_secrets = E::pairing(g1, proof.v_r2) == E::pairing(proof.v_r1, g2)
+            && E::pairing(proof.v_beta, proof.v_r2) == E::pairing(proof.v_r_beta, g2);
+
+        // The new keys are the old ones scaled by these secrets
+        let u_keys = next.u1().0 == self.u1().0
+            && E::pairing(next.u1().1, g2) == E::pairing(self.u1().1, proof.u_alpha)
+            && E::pairing(next.u2().0, g2) == E::pairing(self.u2().0, proof.u_s2)
+            && E::pairing(next.u2().1, g2) == E::pairing(self.u2().1, proof.u_s_alpha);
+        let v_keys = next.v1().0 == self.v1().0
+            && E::pairing(g1, next.v1().1) == E::pairing(proof.v_beta, self.v1().1)
+            && E::pairing(g1, next.v2().0) == E::pairing(proof.v_r1, self.v2().0)
+            && E::pairing(g1, next.v2().1) == E::pairing(proof.v_r_beta, self.v2().1);
+
+        u_secrets && v_secrets && u_keys && v_keys
+    }
+}
+
+impl<E: Pairing> CRS<E> {
+    /// A one-call smoke test for the pairing engine `E`: generates a CRS, then proves and verifies one
+    /// satisfied equation of each type, and checks that an unsatisfied equation is rejected.
+    ///
+    /// Returns whether everything passed, e.g. to catch a misconfigured curve at startup.
+    pub fn self_test<R: Rng>(rng: &mut R) -> bool {
+        let crs = CRS::<E>::generate_crs(rng);
+        let gamma = vec![vec![E::ScalarField::one()]];
+
+        // e(X_1, Y_1) = t
+        let xvars = vec![crs.g1_gen.mul(E::ScalarField::rand(rng)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(E::ScalarField::rand(rng)).into_affine()];
+        let ppe = PPE::<E> {
+            a_consts: vec![E::G1Affine::zero()],
+            b_consts: vec![E::G2Affine::zero()],
+            gamma: gamma.clone(),
+            target: E::pairing(xvars[0], yvars[0]),
+        };
+        let bad_ppe = PPE::<E> {
+            target: ppe.target + crs.gt_gen,
+            ..ppe.clone()
+        };
+
+        // X_1 * y_1 = t_1, x_1 * Y_1 = t_2 and x_1 * y_1 = t_p
+        let scalar_xvars = vec![E::ScalarField::rand(rng)];
+        let scalar_yvars = vec![E::ScalarField::rand(rng)];
+        let msmeg1 = MSMEG1::<E> {
+            a_consts: vec![E::G1Affine::zero()],
+            b_consts: vec![E::ScalarField::zero()],
+            gamma: gamma.clone(),
+            target: xvars[0].mul(scalar_yvars[0]).into_affine(),
+        };
+        let msmeg2 = MSMEG2::<E> {
+            a_consts: vec![E::ScalarField::zero()],
+            b_consts: vec![E::G2Affine::zero()],
+            gamma: gamma.clone(),
+            target: yvars[0].mul(scalar_xvars[0]).into_affine(),
+        };
+        let quad = QuadEqu::<E> {
+            a_consts: vec![E::ScalarField::zero()],
+            b_consts: vec![E::ScalarField::zero()],
+            gamma,
+            target: scalar_xvars[0] * scalar_yvars[0],
+        };
+
+        let ppe_proof = ppe.commit_and_prove(&xvars, &yvars, &crs, rng);
+        ppe.verify(&ppe_proof, &crs)
+            && !bad_ppe.verify(&ppe_proof, &crs)
+            && msmeg1.verify(
+                &msmeg1.commit_and_prove(&xvars, &scalar_yvars, &crs, rng),
+                &crs,
+            )
+            && msmeg2.verify(
+                &msmeg2.commit_and_prove(&scalar_xvars, &yvars, &crs, rng),
+                &crs,
+            )
+            && quad.verify(
+                &quad.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, rng),
+                &crs,
+            )
+    }
+}
+
 impl<E: Pairing> AbstractCrs<E> for CRS<E> {
     fn generate_crs<R>(rng: &mut R) -> CRS<E>
     where
@@ -86,6 +407,141 @@ impl<E: Pairing> AbstractCrs<E> for CRS<E> {
         let p1 = E::G1::rand(rng);
         let p2 = E::G2::rand(rng);
 
+        Self::generate_crs_over(p1, p2, rng)
+    }
+}
+
+/// The reasons a CRS, or the generators it is generated over, may be invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrsError {
+    /// A bilinear group generator is the identity.
+    ZeroGenerator,
+    /// A bilinear group generator is not in the prime-order subgroup of its group.
+    InvalidGenerator,
+    /// There are not exactly two commitment keys in each of `u` and `v`.
+    KeyCount { u: usize, v: usize },
+    /// The cached generator of `GT` is not `e(g1, g2)`.
+    GtGenerator,
+}
+
+impl Display for CrsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CrsError::ZeroGenerator => write!(f, "a bilinear group generator is the identity"),
+            CrsError::InvalidGenerator => {
+                write!(
+                    f,
+                    "a bilinear group generator is not in the prime-order subgroup"
+                )
+            }
+            CrsError::KeyCount { u, v } => write!(
+                f,
+                "expected 2 commitment keys in each of u and v, found {} and {}",
+                u, v
+            ),
+            CrsError::GtGenerator => write!(f, "the generator of GT is not e(g1, g2)"),
+        }
+    }
+}
+
+impl ark_std::error::Error for CrsError {}
+
+// A generator must be a nonzero element of the prime-order subgroup of its group
+fn check_generator<G: AffineRepr>(gen: &G) -> Result<(), CrsError> {
+    if gen.is_zero() {
+        Err(CrsError::ZeroGenerator)
+    } else if gen.check().is_err() {
+        Err(CrsError::InvalidGenerator)
+    } else {
+        Ok(())
+    }
+}
+
+impl<E: Pairing> CRS<E> {
+    /// Generates a (binding) CRS as [`generate_crs`](AbstractCrs::generate_crs) does, but over the given
+    /// generators of `G1` and `G2` instead of sampled ones, e.g. to match the generators of an existing
+    /// system. Only the secret scalars of the commitment keys are sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either generator is zero or not in the prime-order subgroup of its group. Use
+    /// [`try_generate_crs_with_generators`](CRS::try_generate_crs_with_generators) to handle this as an error.
+    pub fn generate_crs_with_generators<R: Rng>(
+        g1_gen: E::G1Affine,
+        g2_gen: E::G2Affine,
+        rng: &mut R,
+    ) -> CRS<E> {
+        assert!(
+            check_generator(&g1_gen).is_ok(),
+            "g1_gen must be a nonzero element of the prime-order subgroup of G1"
+        );
+        assert!(
+            check_generator(&g2_gen).is_ok(),
+            "g2_gen must be a nonzero element of the prime-order subgroup of G2"
+        );
+        Self::generate_crs_over(g1_gen.into_group(), g2_gen.into_group(), rng)
+    }
+
+    /// Generates a CRS as [`generate_crs_with_generators`](CRS::generate_crs_with_generators) does,
+    /// reporting an invalid generator as an error instead of panicking.
+    pub fn try_generate_crs_with_generators<R: Rng>(
+        g1_gen: E::G1Affine,
+        g2_gen: E::G2Affine,
+        rng: &mut R,
+    ) -> Result<CRS<E>, CrsError> {
+        check_generator(&g1_gen)?;
+        check_generator(&g2_gen)?;
+        Ok(Self::generate_crs_over(
+            g1_gen.into_group(),
+            g2_gen.into_group(),
+            rng,
+        ))
+    }
+
+    /// Checks the structure of the CRS, e.g. after loading it without validation: two commitment keys in
+    /// each of `u` and `v`, valid generators of `G1` and `G2`, and `gt_gen = e(g1, g2)`, which costs a pairing.
+    ///
+    /// This does not (and cannot) check that the commitment keys are binding.
+    pub fn validate(&self) -> Result<(), CrsError> {
+        if self.u.len() != 2 || self.v.len() != 2 {
+            return Err(CrsError::KeyCount {
+                u: self.u.len(),
+                v: self.v.len(),
+            });
+        }
+        check_generator(&self.g1_gen)?;
+        check_generator(&self.g2_gen)?;
+        if self.gt_gen != E::pairing(self.g1_gen, self.g2_gen) {
+            return Err(CrsError::GtGenerator);
+        }
+        Ok(())
+    }
+
+    /// Generates a CRS with perfectly hiding commitment keys, together with the trapdoor to
+    /// [`simulate`](crate::statement::PPE::simulate) proofs under it.
+    ///
+    /// A hiding CRS is indistinguishable from one of [`generate_crs`](AbstractCrs::generate_crs) under
+    /// SXDH, but proofs under it are not sound. It is meant for proving zero-knowledge (e.g. of a
+    /// protocol built on these proofs), never for a deployed system.
+    pub fn generate_hiding_crs<R: Rng>(rng: &mut R) -> (CRS<E>, SimulationTrapdoor<E>) {
+        let p1 = E::G1::rand(rng);
+        let p2 = E::G2::rand(rng);
+
+        Self::generate_keys_over(p1, p2, true, rng)
+    }
+
+    // Samples binding commitment keys over the generators p1 and p2
+    fn generate_crs_over<R: Rng>(p1: E::G1, p2: E::G2, rng: &mut R) -> CRS<E> {
+        Self::generate_keys_over(p1, p2, false, rng).0
+    }
+
+    // Samples binding (or, if `hiding`, hiding) commitment keys over the generators p1 and p2
+    fn generate_keys_over<R: Rng>(
+        p1: E::G1,
+        p2: E::G2,
+        hiding: bool,
+        rng: &mut R,
+    ) -> (CRS<E>, SimulationTrapdoor<E>) {
         // Scalar intermediate values
         let a1 = E::ScalarField::rand(rng);
         let a2 = E::ScalarField::rand(rng);
@@ -98,7 +554,11 @@ impl<E: Pairing> AbstractCrs<E> for CRS<E> {
         let u1 = p1.mul(t1);
         let u2 = p2.mul(t2);
 
-        let (v1, v2) = Self::prepare_real_binding_key(p1, p2, q1, t1, q2, t2);
+        let (v1, v2) = if hiding {
+            Self::prepare_simulated_hinding_key(p1, p2, q1, t1, q2, t2)
+        } else {
+            Self::prepare_real_binding_key(p1, p2, q1, t1, q2, t2)
+        };
 
         // B1 commitment key for G1 and Fr
         let u11 = Com1::<E>(p1.into_affine(), q1.into_affine());
@@ -108,13 +568,56 @@ impl<E: Pairing> AbstractCrs<E> for CRS<E> {
         let u21 = Com2::<E>(p2.into_affine(), q2.into_affine());
         let u22 = Com2::<E>(u2.into_affine(), v2.into_affine());
 
-        CRS::<E> {
+        let crs = CRS::<E> {
             u: vec![u11, u12],
             v: vec![u21, u22],
             g1_gen: p1.into_affine(),
             g2_gen: p2.into_affine(),
             gt_gen: E::pairing(p1.into_affine(), p2.into_affine()),
+        };
+        (crs, SimulationTrapdoor { t1, t2 })
+    }
+}
+
+/// The trapdoor of a hiding CRS from [`generate_hiding_crs`](CRS::generate_hiding_crs), i.e. the
+/// scalars `t_1, t_2` with `u_2 = t_1 u_1 - (O, g1)` and `v_2 = t_2 v_1 - (O, g2)`.
+///
+/// It does not implement `Debug`, so that it cannot end up in logs by accident.
+#[derive(Clone)]
+pub struct SimulationTrapdoor<E: Pairing> {
+    t1: E::ScalarField,
+    t2: E::ScalarField,
+}
+
+impl<E: Pairing> SimulationTrapdoor<E> {
+    /// Returns whether this is the trapdoor of `crs`, which is then perfectly hiding.
+    pub fn is_for(&self, crs: &CRS<E>) -> bool {
+        if crs.u.len() != 2 || crs.v.len() != 2 {
+            return false;
         }
+        crs.u2() + Com1::<E>::linear_map(&crs.g1_gen) == crs.u1().scalar_mul(&self.t1)
+            && crs.v2() + Com2::<E>::linear_map(&crs.g2_gen) == crs.v1().scalar_mul(&self.t2)
+    }
+}
+
+// A 64-bit FNV-1a hash over the bytes written to it
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Write for Fnv1a {
+    fn write(&mut self, buf: &[u8]) -> ark_std::io::Result<usize> {
+        self.0 = buf.iter().fold(self.0, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(Self::PRIME)
+        });
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> ark_std::io::Result<()> {
+        Ok(())
     }
 }
 
@@ -146,6 +649,7 @@ mod tests {
         assert_ne!(crs.g1_gen, G1Affine::zero());
         assert_ne!(crs.g2_gen, G2Affine::zero());
         assert_ne!(crs.gt_gen, GT::zero());
+        assert_eq!(crs.pt(), F::pairing(crs.g1_gen, crs.g2_gen));
     }
 
     #[allow(non_snake_case)]
@@ -181,6 +685,148 @@ mod tests {
         assert_eq!(crs.v[1].1, v2.into_affine());
     }
 
+    #[test]
+    fn test_key_accessors() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        assert_eq!(crs.u1(), crs.u[0]);
+        assert_eq!(crs.u2(), crs.u[1]);
+        assert_eq!(crs.v1(), crs.v[0]);
+        assert_eq!(crs.v2(), crs.v[1]);
+        assert_ne!(crs.u1(), crs.u2());
+        assert_ne!(crs.v1(), crs.v2());
+    }
+
+    #[test]
+    fn test_self_test() {
+        let mut rng = test_rng();
+        assert!(CRS::<F>::self_test(&mut rng));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_with_generators() {
+        let mut rng = test_rng();
+        let g1_gen = G1Affine::generator();
+        let g2_gen = G2Affine::generator().mul(Fr::from(7u64)).into_affine();
+        let crs = CRS::<F>::generate_crs_with_generators(g1_gen, g2_gen, &mut rng);
+
+        assert_eq!(crs.g1_gen, g1_gen);
+        assert_eq!(crs.g2_gen, g2_gen);
+        assert_eq!(crs.gt_gen, F::pairing(g1_gen, g2_gen));
+        assert_eq!(crs.u1().0, g1_gen);
+        assert_eq!(crs.v1().0, g2_gen);
+        // Another CRS over the same generators has other commitment keys
+        let other = CRS::<F>::generate_crs_with_generators(g1_gen, g2_gen, &mut rng);
+        assert!(crs.is_compatible(&other));
+        assert_ne!(crs.u, other.u);
+
+        // e(X_1, Y_1) = t
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+        assert!(!equ.verify(&proof, &other));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    #[should_panic(expected = "g2_gen must be a nonzero element")]
+    fn test_CRS_with_zero_generator() {
+        let mut rng = test_rng();
+        CRS::<F>::generate_crs_with_generators(G1Affine::generator(), G2Affine::zero(), &mut rng);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_validate() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        assert_eq!(crs.validate(), Ok(()));
+
+        assert_eq!(
+            CRS::<F>::try_generate_crs_with_generators(G1Affine::zero(), crs.g2_gen, &mut rng)
+                .unwrap_err(),
+            CrsError::ZeroGenerator
+        );
+        let mut bad = crs.clone();
+        bad.u.pop();
+        assert_eq!(bad.validate(), Err(CrsError::KeyCount { u: 1, v: 2 }));
+        let mut bad = crs.clone();
+        bad.gt_gen = bad.gt_gen + bad.gt_gen;
+        assert_eq!(bad.validate(), Err(CrsError::GtGenerator));
+        assert_eq!(
+            CrsError::GtGenerator.to_string(),
+            "the generator of GT is not e(g1, g2)"
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_compatibility() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let other = CRS::<F>::generate_crs(&mut rng);
+
+        assert!(crs.is_compatible(&crs.clone()));
+        assert!(!crs.is_compatible(&other));
+        assert_eq!(crs.fingerprint(), crs.clone().fingerprint());
+        assert_ne!(crs.fingerprint(), other.fingerprint());
+
+        // Same generators, but a different commitment key
+        let mut rekeyed = crs.clone();
+        rekeyed.u[1] = other.u[1];
+        assert!(crs.is_compatible(&rekeyed));
+        assert_ne!(crs.fingerprint(), rekeyed.fingerprint());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_contributions() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (first, first_proof) = crs.contribute(&mut rng);
+        let (second, second_proof) = first.contribute(&mut rng);
+        assert!(crs.verify_contribution(&first, &first_proof));
+        assert!(first.verify_contribution(&second, &second_proof));
+        assert!(second.is_compatible(&crs));
+        assert_ne!(second.u, crs.u);
+        assert_ne!(second.v, crs.v);
+
+        // Proofs do not carry over to other links of the chain
+        assert!(!crs.verify_contribution(&second, &first_proof));
+        assert!(!crs.verify_contribution(&second, &second_proof));
+        assert!(!first.verify_contribution(&second, &first_proof));
+
+        // A corrupted key is rejected
+        let mut corrupted = second.clone();
+        corrupted.v[1] = CRS::<F>::generate_crs(&mut rng).v[1];
+        assert!(!first.verify_contribution(&corrupted, &second_proof));
+        let mut corrupted = second.clone();
+        corrupted.u[0] = first.u[0];
+        assert!(!first.verify_contribution(&corrupted, &second_proof));
+
+        // The final CRS still proves and verifies
+        let xvars = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let ppe = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof = ppe.commit_and_prove(&xvars, &yvars, &second, &mut rng);
+        assert!(ppe.verify(&proof, &second));
+    }
+
     #[allow(non_snake_case)]
     #[test]
     fn test_CRS_serde() {
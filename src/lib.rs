@@ -1,9 +1,26 @@
+#[cfg(feature = "ark-crypto-primitives")]
+pub mod commitment_scheme;
 pub mod data_structures;
+pub mod error;
+pub mod gadgets;
 pub mod generator;
+pub mod prelude;
 pub mod prover;
 pub mod statement;
 pub mod verifier;
+pub mod wire;
 
-pub use crate::data_structures::*;
-pub use crate::generator::*;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use crate::error::Error;
+pub use crate::generator::{AbstractCrs, CRS};
 pub use crate::statement::EquType;
+
+// Deprecated: the rest of `data_structures` and `generator` used to be glob-exported at the crate root.
+// They are kept for one more release; import them from their modules or the `prelude` instead.
+// `#[deprecated]` has no effect on re-exports, so they are hidden from the docs instead.
+#[doc(hidden)]
+pub use crate::data_structures::{
+    col_vec_to_vec, vec_to_col_vec, Com1, Com2, ComT, Mat, Matrix, B, B1, B2, BT,
+};
@@ -0,0 +1,38 @@
+//! The types, traits and functions needed to commit to variables and to prove and verify equations
+//! about them, for a single `use groth_sahai::prelude::*;`.
+//!
+//! ```
+//! use ark_bls12_381::Bls12_381 as F;
+//! use ark_ec::{pairing::Pairing, AffineRepr};
+//! use ark_std::{test_rng, UniformRand};
+//! use groth_sahai::prelude::*;
+//!
+//! type G1Affine = <F as Pairing>::G1Affine;
+//! type G2Affine = <F as Pairing>::G2Affine;
+//! type Fr = <F as Pairing>::ScalarField;
+//!
+//! let mut rng = test_rng();
+//! let crs = CRS::<F>::generate_crs(&mut rng);
+//!
+//! // e(X_1, Y_1)^2 = t
+//! let xvars = vec![G1Affine::rand(&mut rng)];
+//! let yvars = vec![G2Affine::rand(&mut rng)];
+//! let equ = PPE::<F> {
+//!     a_consts: vec![G1Affine::zero()],
+//!     b_consts: vec![G2Affine::zero()],
+//!     gamma: vec![vec![Fr::from(2u64)]],
+//!     target: F::pairing(xvars[0], yvars[0]) * Fr::from(2u64),
+//! };
+//! let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+//! assert!(equ.verify(&proof, &crs));
+//! ```
+
+pub use crate::data_structures::{Com1, Com2, ComT, Matrix, B1, B2, BT};
+pub use crate::generator::{AbstractCrs, CRS};
+pub use crate::prover::{
+    batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
+    commit_G1, commit_G2, commit_scalar_to_B1, commit_scalar_to_B2, CProof, Commit1, Commit2,
+    EquProof, Provable,
+};
+pub use crate::statement::{EquType, QuadEqu, MSMEG1, MSMEG2, PPE};
+pub use crate::verifier::Verifiable;
@@ -2,31 +2,116 @@
 //! into the Groth-Sahai commitment group `B1, B2` for the SXDH instantiation.
 #![allow(non_snake_case)]
 
-use ark_ec::pairing::Pairing;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{fmt::Debug, rand::Rng, UniformRand};
-
-use crate::data_structures::{col_vec_to_vec, vec_to_col_vec, Com1, Com2, Mat, Matrix, B1, B2};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::Zero;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::{
+    fmt::{self, Debug, Display, Formatter},
+    rand::Rng,
+    UniformRand,
+};
+
+use crate::data_structures::{
+    check_permutation, deserialize_matrix_of_shape, deserialize_vec, deserialize_versioned,
+    serialize_matrix, serialize_vec, serialize_version, serialized_matrix_size,
+    serialized_vec_size, Com1, Com1MulContext, Com1Projective, Com2, Com2MulContext,
+    Com2Projective, Mat, Matrix, MatrixExt, ShapeError, B, B1, B2, MAX_SERIALIZED_LEN,
+    VERSION_HEADER_SIZE,
+};
 use crate::generator::CRS;
 
+/// The reasons committing to a list of variables may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitError {
+    /// A list that must have one entry per variable has a different length.
+    Length { expected: usize, found: usize },
+    /// The variable at this index is marked as publicly the identity, but is not.
+    NotIdentity(usize),
+}
+
+impl Display for CommitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitError::Length { expected, found } => write!(
+                f,
+                "expected {} entries, one per variable, found {}",
+                expected, found
+            ),
+            CommitError::NotIdentity(index) => {
+                write!(
+                    f,
+                    "variable {} is marked as the identity, but is not",
+                    index
+                )
+            }
+        }
+    }
+}
+
+impl ark_std::error::Error for CommitError {}
+
 pub trait Commit: Eq + Debug {
     /// Append together two lists of commits to obtain single list of commits.
     fn append(&mut self, other: &mut Self);
 }
 
 /// Contains both the commitment's values (as [`Com1`](crate::data_structures::Com1)) and its randomness.
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// It serializes with a leading version header, see [`FORMAT_VERSION`](crate::data_structures::FORMAT_VERSION).
+#[derive(Clone, Debug)]
 pub struct Commit1<E: Pairing> {
     pub coms: Vec<Com1<E>>,
     pub(super) rand: Matrix<E::ScalarField>,
 }
 /// Contains both the commitment's values (as [`Com2`](crate::data_structures::Com2)) and its randomness.
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// It serializes with a leading version header, see [`FORMAT_VERSION`](crate::data_structures::FORMAT_VERSION).
+#[derive(Clone, Debug)]
 pub struct Commit2<E: Pairing> {
     pub coms: Vec<Com2<E>>,
     pub(super) rand: Matrix<E::ScalarField>,
 }
 
+/// Contains the commitment's values (as [`Com1`](crate::data_structures::Com1)) to scalars and its randomness.
+///
+/// Scalar commitments only use a single random scalar per committed value, so the randomness is
+/// stored as a flat vector rather than an `m' x 1` [`Matrix`](crate::data_structures::Matrix).
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CompactCommit1<E: Pairing> {
+    pub coms: Vec<Com1<E>>,
+    pub(super) rand: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> From<CompactCommit1<E>> for Commit1<E> {
+    fn from(compact: CompactCommit1<E>) -> Self {
+        Self {
+            coms: compact.coms,
+            rand: compact.rand.into_iter().map(|r| vec![r]).collect(),
+        }
+    }
+}
+
+/// Only commitments to scalars (i.e. with one random value per committed value) are compact, so any
+/// other commitment is an error with the expected `m' x 1` shape of its randomness.
+impl<E: Pairing> TryFrom<Commit1<E>> for CompactCommit1<E> {
+    type Error = ShapeError;
+
+    fn try_from(commit: Commit1<E>) -> Result<Self, ShapeError> {
+        if commit.rand.len() != commit.coms.len() || commit.rand.iter().any(|row| row.len() != 1) {
+            return Err(ShapeError {
+                expected: (commit.coms.len(), 1),
+            });
+        }
+        Ok(Self {
+            coms: commit.coms,
+            rand: commit.rand.into_iter().map(|row| row[0]).collect(),
+        })
+    }
+}
+
 macro_rules! impl_com {
     ($( $commit:ident ),*) => {
         $(
@@ -55,6 +140,335 @@ macro_rules! impl_com {
 }
 impl_com!(Commit1, Commit2);
 
+macro_rules! impl_commit_serde {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> CanonicalSerialize for $commit<E> {
+                fn serialize_with_mode<W: Write>(
+                    &self,
+                    mut writer: W,
+                    compress: Compress,
+                ) -> Result<(), SerializationError> {
+                    serialize_version(&mut writer)?;
+                    serialize_vec(&self.coms, &mut writer, compress)?;
+                    serialize_matrix(&self.rand, &mut writer, compress)
+                }
+
+                fn serialized_size(&self, compress: Compress) -> usize {
+                    VERSION_HEADER_SIZE
+                        + serialized_vec_size(&self.coms, compress)
+                        + serialized_matrix_size(&self.rand, compress)
+                }
+            }
+
+            impl<E: Pairing> Valid for $commit<E> {
+                fn check(&self) -> Result<(), SerializationError> {
+                    self.coms.check()?;
+                    self.rand.check()
+                }
+            }
+
+            impl<E: Pairing> CanonicalDeserialize for $commit<E> {
+                fn deserialize_with_mode<R: Read>(
+                    reader: R,
+                    compress: Compress,
+                    validate: Validate,
+                ) -> Result<Self, SerializationError> {
+                    deserialize_versioned(reader, |mut reader, version| {
+                        let coms: Vec<_> =
+                            deserialize_vec(&mut reader, version, compress, validate, MAX_SERIALIZED_LEN)?;
+                        // One row of randomness per value, of one scalar for a scalar or two for a group
+                        // element, or none at all
+                        let rand =
+                            deserialize_matrix_of_shape(&mut reader, version, compress, validate, coms.len(), &[1, 2])?;
+                        Ok(Self { coms, rand })
+                    })
+                }
+            }
+        )*
+    }
+}
+impl_commit_serde!(Commit1, Commit2);
+
+macro_rules! impl_from_coms {
+    ($( $commit:ident, $com:ident );*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// The commitment with the given values and without its randomness, e.g. as received by a
+                /// verifier. It cannot be used to prove about the committed variables.
+                pub fn from_coms(coms: Vec<$com<E>>) -> Self {
+                    Self { coms, rand: vec![] }
+                }
+            }
+        )*
+    }
+}
+impl_from_coms!(Commit1, Com1; Commit2, Com2);
+
+macro_rules! impl_randomness {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// The randomness of the `i`-th commitment, i.e. the `i`-th row of the randomness matrix, or
+                /// `None` if there is no such row (e.g. for a commitment received by a verifier).
+                ///
+                /// Together with the committed value, it opens the commitment (see e.g.
+                /// [`verify_opening_G1`](self::verify_opening_G1)), so it must only be revealed for
+                /// values that are meant to become public.
+                pub fn randomness(&self, i: usize) -> Option<&[E::ScalarField]> {
+                    self.rand.get(i).map(|row| row.as_slice())
+                }
+            }
+        )*
+    }
+}
+impl_randomness!(Commit1, Commit2);
+
+macro_rules! impl_pad_to {
+    ($( $commit:ident, $com:ident );*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                #[doc = concat!("Pads the commitment with zero [`", stringify!($com), "`](crate::data_structures::", stringify!($com), ") entries, each with a zero")]
+                /// row of randomness, until it commits to `n` variables. Does nothing if it already commits to
+                /// at least `n` variables.
+                ///
+                /// A zero entry is the commitment to the zero variable with zero randomness, so the padded
+                /// commitment opens to the same values for the existing variables, and to zero for the padding.
+                /// The rows of randomness have as many entries as the existing rows, or two (as for group
+                /// variables) if there are none.
+                pub fn pad_to(&mut self, n: usize) {
+                    if n <= self.coms.len() {
+                        return;
+                    }
+                    let width = self.rand.first().map_or(2, |row| row.len());
+                    self.coms.resize(n, $com::<E>::zero());
+                    self.rand.resize(n, vec![E::ScalarField::zero(); width]);
+                }
+            }
+        )*
+    }
+}
+impl_pad_to!(Commit1, Com1; Commit2, Com2);
+
+macro_rules! impl_permute {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// Reorders the committed variables, so that the `i`-th commitment (and its row of randomness)
+                /// is the `perm[i]`-th one of `self`.
+                ///
+                /// The permuted commitment opens to the permuted variables, so it can be used to prove about
+                /// e.g. a shuffle of the variables. Applying the inverse permutation restores the commitment.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `perm` is not a permutation of `0..n`, for `n` committed variables. The rows of a
+                /// matrix, e.g. of `Γ`, are permuted in lock-step with [`permute_rows`](crate::data_structures::MatrixExt::permute_rows).
+                pub fn permute(&self, perm: &[usize]) -> Self {
+                    if let Err(err) = check_permutation(perm, self.coms.len()) {
+                        panic!("{}", err);
+                    }
+                    Self {
+                        coms: perm.iter().map(|&idx| self.coms[idx]).collect(),
+                        rand: perm.iter().map(|&idx| self.rand[idx].clone()).collect(),
+                    }
+                }
+            }
+        )*
+    }
+}
+impl_permute!(Commit1, Commit2);
+
+macro_rules! impl_select {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// The commitment to the variables at `indices`, in order, with their rows of randomness (if
+                /// the commitment has any), e.g. to prove about only some of the committed variables.
+                ///
+                /// # Panics
+                ///
+                /// Panics if an index is not that of a committed variable.
+                pub fn select(&self, indices: &[usize]) -> Self {
+                    Self {
+                        coms: indices.iter().map(|&idx| self.coms[idx]).collect(),
+                        rand: if self.rand.is_empty() {
+                            vec![]
+                        } else {
+                            indices.iter().map(|&idx| self.rand[idx].clone()).collect()
+                        },
+                    }
+                }
+            }
+        )*
+    }
+}
+impl_select!(Commit1, Commit2);
+
+macro_rules! impl_is_degenerate {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// Whether there are committed variables and every commitment is zero, which no honest
+                /// commitment with randomness is (except with negligible probability).
+                ///
+                /// All-zero commitments open to zero variables with zero randomness, so with an all-zero
+                /// proof they form a valid proof for any equation those variables satisfy (e.g. one whose
+                /// target is zero). Rejecting them is a policy against such trivial openings, independent
+                /// of the equation, not a soundness check.
+                ///
+                /// Commitments to public zeros (see e.g.
+                /// [`batch_commit_G1_with_public_zeros`](crate::prover::batch_commit_G1_with_public_zeros))
+                /// are zero by design, so are degenerate if all variables are public zeros.
+                pub fn is_degenerate(&self) -> bool {
+                    !self.coms.is_empty() && self.coms.iter().all(|com| com.is_zero())
+                }
+            }
+        )*
+    }
+}
+impl_is_degenerate!(Commit1, Commit2);
+
+macro_rules! impl_homomorphic_add {
+    ($( $commit:ident ),*) => {
+        $(
+            impl<E: Pairing> $commit<E> {
+                /// The entry-wise sum of two commitments to the same number of variables, summing both the
+                /// commitments and their randomness.
+                ///
+                /// Commitments are additively homomorphic: the sum of commitments to `x` and `y` (with
+                /// randomness `R` and `R'`) is the commitment to `x + y` with randomness `R + R'`, so the sum
+                /// opens to (and can be proven about as) the summed variables. Protocols layered on top rely on
+                /// this e.g. to combine or re-randomize commitments.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the commitments are to different numbers of variables, or if their randomness
+                /// has different shapes (e.g. only one of them has randomness).
+                pub fn homomorphic_add(&self, other: &Self) -> Self {
+                    assert_eq!(self.coms.len(), other.coms.len());
+                    assert_eq!(self.rand.len(), other.rand.len());
+                    Self {
+                        coms: self
+                            .coms
+                            .iter()
+                            .zip(other.coms.iter())
+                            .map(|(lhs, rhs)| *lhs + *rhs)
+                            .collect(),
+                        rand: self.rand.add(&other.rand),
+                    }
+                }
+            }
+        )*
+    }
+}
+impl_homomorphic_add!(Commit1, Commit2);
+
+impl<E: Pairing> Commit1<E> {
+    /// The entry-wise difference `self.coms - other.coms` of two commitments to the same number of variables.
+    ///
+    /// Under a binding CRS, each entry is a commitment to zero (with randomness `R - R'`) if and only if
+    /// both commitments open to the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the commitments are to different numbers of variables.
+    pub fn diff(&self, other: &Commit1<E>) -> Vec<Com1<E>> {
+        assert_eq!(self.coms.len(), other.coms.len());
+        self.coms
+            .iter()
+            .zip(other.coms.iter())
+            .map(|(lhs, rhs)| *lhs - *rhs)
+            .collect()
+    }
+}
+
+impl<E: Pairing> Commit2<E> {
+    /// The entry-wise difference `self.coms - other.coms` of two commitments to the same number of variables.
+    ///
+    /// Under a binding CRS, each entry is a commitment to zero (with randomness `S - S'`) if and only if
+    /// both commitments open to the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the commitments are to different numbers of variables.
+    pub fn diff(&self, other: &Commit2<E>) -> Vec<Com2<E>> {
+        assert_eq!(self.coms.len(), other.coms.len());
+        self.coms
+            .iter()
+            .zip(other.coms.iter())
+            .map(|(lhs, rhs)| *lhs - *rhs)
+            .collect()
+    }
+}
+
+/// Commitment to [scalar field](ark_ec::Pairing::Fr) elements on either side of the SXDH instantiation,
+/// i.e. [`Commit1`](self::Commit1) into `B1` or [`Commit2`](self::Commit2) into `B2`.
+///
+/// Allows writing side-agnostic code, e.g. for the scalar variables of a quadratic equation.
+pub trait ScalarCommit<E: Pairing>: Commit + Sized {
+    /// The commitment group of this side.
+    type Com: B<E>;
+
+    /// Commit a single [scalar field](ark_ec::Pairing::Fr) element, using one random scalar.
+    fn commit_scalar<CR: Rng>(scalar: &E::ScalarField, key: &CRS<E>, rng: &mut CR) -> Self;
+
+    /// Commit all [scalar field](ark_ec::Pairing::Fr) elements in list, using one random scalar each.
+    fn batch_commit_scalar<CR: Rng>(scalars: &[E::ScalarField], key: &CRS<E>, rng: &mut CR)
+        -> Self;
+
+    /// The commitment values.
+    fn coms(&self) -> &[Self::Com];
+}
+
+impl<E: Pairing> ScalarCommit<E> for Commit1<E> {
+    type Com = Com1<E>;
+
+    fn commit_scalar<CR: Rng>(scalar: &E::ScalarField, key: &CRS<E>, rng: &mut CR) -> Self {
+        let r: E::ScalarField = E::ScalarField::rand(rng);
+        Commit1::<E> {
+            coms: vec![commit_scalar_to_B1_with(scalar, key, &r)],
+            rand: vec![vec![r]],
+        }
+    }
+
+    fn batch_commit_scalar<CR: Rng>(
+        scalars: &[E::ScalarField],
+        key: &CRS<E>,
+        rng: &mut CR,
+    ) -> Self {
+        batch_commit_scalar_to_B1(scalars, key, rng)
+    }
+
+    fn coms(&self) -> &[Com1<E>] {
+        &self.coms
+    }
+}
+
+impl<E: Pairing> ScalarCommit<E> for Commit2<E> {
+    type Com = Com2<E>;
+
+    fn commit_scalar<CR: Rng>(scalar: &E::ScalarField, key: &CRS<E>, rng: &mut CR) -> Self {
+        let s: E::ScalarField = E::ScalarField::rand(rng);
+        Commit2::<E> {
+            coms: vec![commit_scalar_to_B2_with(scalar, key, &s)],
+            rand: vec![vec![s]],
+        }
+    }
+
+    fn batch_commit_scalar<CR: Rng>(
+        scalars: &[E::ScalarField],
+        key: &CRS<E>,
+        rng: &mut CR,
+    ) -> Self {
+        batch_commit_scalar_to_B2(scalars, key, rng)
+    }
+
+    fn coms(&self) -> &[Com2<E>] {
+        &self.coms
+    }
+}
+
 /// Commit a single [`G1`](ark_ec::Pairing::G1Affine) element to [`B1`](crate::data_structures::Com1).
 pub fn commit_G1<CR, E>(xvar: &E::G1Affine, key: &CRS<E>, rng: &mut CR) -> Commit1<E>
 where
@@ -62,43 +476,289 @@ where
     CR: Rng,
 {
     let (r1, r2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
-
-    // c := i_1(x) + r_1 u_1 + r_2 u_2
     Commit1::<E> {
-        coms: vec![
-            Com1::<E>::linear_map(xvar)
-                + vec_to_col_vec(&key.u)[0][0].scalar_mul(&r1)
-                + vec_to_col_vec(&key.u)[1][0].scalar_mul(&r2),
-        ],
+        coms: vec![commit_G1_with(xvar, key, &r1, &r2)],
         rand: vec![vec![r1, r2]],
     }
 }
 
+// c := i_1(x) + r_1 u_1 + r_2 u_2
+pub(crate) fn commit_G1_with<E: Pairing>(
+    xvar: &E::G1Affine,
+    key: &CRS<E>,
+    r1: &E::ScalarField,
+    r2: &E::ScalarField,
+) -> Com1<E> {
+    Com1::<E>::linear_map(xvar) + key.u1().scalar_mul(r1) + key.u2().scalar_mul(r2)
+}
+
+/// Commit a publicly-known [`G1`](ark_ec::Pairing::G1Affine) constant to [`B1`](crate::data_structures::Com1)
+/// with zero randomness, i.e. `c := i_1(x)`.
+///
+/// The commitment is deterministic and hides nothing, so anyone can check it against the constant.
+/// It can be mixed with hiding commitments (e.g. via [`append`](Commit::append)) when proving.
+pub fn commit_G1_const<E: Pairing>(xvar: &E::G1Affine) -> Commit1<E> {
+    Commit1::<E> {
+        coms: vec![Com1::<E>::linear_map(xvar)],
+        rand: vec![vec![E::ScalarField::zero(), E::ScalarField::zero()]],
+    }
+}
+
 /// Commit all [`G1`](ark_ec::Pairing::G1Affine) elements in list to corresponding element in [`B1`](crate::data_structures::Com1).
+///
+/// Besides the randomness and the multiples of the two commitment keys (each computed with a
+/// precomputed [`Com1MulContext`](crate::data_structures::Com1MulContext) for more than one element),
+/// the only allocation is the output vector.
 pub fn batch_commit_G1<CR, E>(xvars: &[E::G1Affine], key: &CRS<E>, rng: &mut CR) -> Commit1<E>
 where
     E: Pairing,
     CR: Rng,
+{
+    batch_commit_G1_iter(xvars.iter().copied(), key, rng)
+}
+
+/// Commit all [`G1`](ark_ec::Pairing::G1Affine) elements in list to corresponding element in [`B1`](crate::data_structures::Com1),
+/// where the elements at the positions set in `public_zeros` are publicly known to be the identity.
+///
+/// Those are committed with zero randomness (as in [`commit_G1_const`](self::commit_G1_const)), so
+/// that randomness is only drawn for the other elements, in the same order as
+/// [`batch_commit_G1`](self::batch_commit_G1) would draw it for them alone.
+///
+/// # Panics
+///
+/// Panics if `public_zeros` has a different length than `xvars`, or if an element it marks is not the identity.
+/// Use [`try_batch_commit_G1_with_public_zeros`](self::try_batch_commit_G1_with_public_zeros) to handle
+/// these as errors.
+pub fn batch_commit_G1_with_public_zeros<CR, E>(
+    xvars: &[E::G1Affine],
+    public_zeros: &[bool],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Commit1<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    try_batch_commit_G1_with_public_zeros(xvars, public_zeros, key, rng)
+        .unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Commits as [`batch_commit_G1_with_public_zeros`](self::batch_commit_G1_with_public_zeros) does,
+/// reporting a mismatched `public_zeros` as an error instead of panicking. No randomness is drawn on error.
+pub fn try_batch_commit_G1_with_public_zeros<CR, E>(
+    xvars: &[E::G1Affine],
+    public_zeros: &[bool],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Result<Commit1<E>, CommitError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    if xvars.len() != public_zeros.len() {
+        return Err(CommitError::Length {
+            expected: xvars.len(),
+            found: public_zeros.len(),
+        });
+    }
+    if let Some(index) = xvars
+        .iter()
+        .zip(public_zeros)
+        .position(|(xvar, &is_zero)| is_zero && !xvar.is_zero())
+    {
+        return Err(CommitError::NotIdentity(index));
+    }
+
+    let hidden: Vec<E::G1Affine> = xvars
+        .iter()
+        .zip(public_zeros)
+        .filter(|(_, &is_zero)| !is_zero)
+        .map(|(xvar, _)| *xvar)
+        .collect();
+    let hidden = batch_commit_G1_iter(hidden.into_iter(), key, rng);
+    let (mut coms, mut rand) = (hidden.coms.into_iter(), hidden.rand.into_iter());
+
+    // Interleave the identities, i.e. c := i_1(O) = O with R's row zero, with the hidden commitments
+    let zero_rand = vec![E::ScalarField::zero(), E::ScalarField::zero()];
+    let (coms, rand) = public_zeros
+        .iter()
+        .map(|&is_zero| {
+            if is_zero {
+                (Com1::<E>::zero(), zero_rand.clone())
+            } else {
+                (
+                    coms.next().expect("one commitment per hidden element"),
+                    rand.next().expect("one randomness row per hidden element"),
+                )
+            }
+        })
+        .unzip();
+    Ok(Commit1::<E> { coms, rand })
+}
+
+/// Commit all [`G1`](ark_ec::Pairing::G1Affine) elements produced by an iterator to corresponding element
+/// in [`B1`](crate::data_structures::Com1), without first collecting them into a slice.
+///
+/// The length of the iterator sizes the randomness, which is drawn up front in the same order as
+/// [`batch_commit_G1`](self::batch_commit_G1), so both produce the same commitment for the same input
+/// and RNG state.
+///
+/// # Panics
+///
+/// Panics if the iterator yields a different number of elements than its reported length.
+pub fn batch_commit_G1_iter<CR, E, I>(xvars: I, key: &CRS<E>, rng: &mut CR) -> Commit1<E>
+where
+    E: Pairing,
+    CR: Rng,
+    I: ExactSizeIterator<Item = E::G1Affine>,
 {
     // R is a random scalar m x 2 matrix
     let m = xvars.len();
-    let mut R: Matrix<E::ScalarField> = Vec::with_capacity(m);
-    for _ in 0..m {
-        R.push(vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)]);
+    let R: Matrix<E::ScalarField> = MatrixExt::from_fn((m, 2), |_, _| E::ScalarField::rand(rng));
+
+    // The multiples of each key by its column of R, sharing one precomputed table per key unless
+    // there is a single multiple, which the table would not pay off for
+    let key_multiples = |key: Com1<E>, j: usize| -> Vec<Com1<E>> {
+        if m == 1 {
+            return vec![key.scalar_mul(&R[0][j])];
+        }
+        let col: Vec<E::ScalarField> = R.col(j).copied().collect();
+        Com1MulContext::new(&key, m).batch_mul(&col)
+    };
+    let u1_rand = key_multiples(key.u1(), 0);
+    let u2_rand = key_multiples(key.u2(), 1);
+
+    // c := i_1(X) + Ru, summed row by row in projective coordinates in a single pass over X, then
+    // converted back to affine with a single batched inversion (i.e. without the intermediate m x 1
+    // matrices for i_1(X), u and their sum)
+    let sums: Vec<Com1Projective<E>> = xvars
+        .zip(u1_rand.into_iter().zip(u2_rand))
+        .map(|(xvar, (rand1, rand2))| {
+            Com1Projective::<E>::from(Com1::<E>::linear_map(&xvar))
+                + Com1Projective::<E>::from(rand1)
+                + Com1Projective::<E>::from(rand2)
+        })
+        .collect();
+    let coms = Com1Projective::<E>::normalize_batch(&sums);
+    assert_eq!(coms.len(), m);
+
+    Commit1::<E> { coms, rand: R }
+}
+
+/// Commit a stream of [`G1`](ark_ec::Pairing::G1Affine) elements to [`B1`](crate::data_structures::Com1)
+/// as they arrive, without first collecting them into a slice.
+///
+/// Randomness is drawn per element in the same order as [`batch_commit_G1`](self::batch_commit_G1),
+/// so both produce the same commitment for the same input and RNG state.
+pub fn commit_G1_stream<CR, E, I>(xvars: I, key: &CRS<E>, rng: &mut CR) -> Commit1<E>
+where
+    E: Pairing,
+    CR: Rng,
+    I: IntoIterator<Item = E::G1Affine>,
+{
+    let xvars = xvars.into_iter();
+    let (lower, _) = xvars.size_hint();
+    let mut coms = Vec::with_capacity(lower);
+    let mut R: Matrix<E::ScalarField> = Vec::with_capacity(lower);
+    for xvar in xvars {
+        let (r1, r2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
+        coms.push(commit_G1_with(&xvar, key, &r1, &r2));
+        R.push(vec![r1, r2]);
     }
 
-    // i_1(X) = [ (O, X_1), ..., (O, X_m) ] (m x 1 matrix)
-    let lin_x: Matrix<Com1<E>> = vec_to_col_vec(&Com1::<E>::batch_linear_map(xvars));
+    Commit1::<E> { coms, rand: R }
+}
 
-    // c := i_1(X) + Ru (m x 1 matrix)
-    let coms = lin_x.add(&vec_to_col_vec(&key.u).left_mul(&R, false));
+/// Verify many claimed openings `(X_i, R_i)` of [`G1`](ark_ec::Pairing::G1Affine) commitments `c_i` at once.
+///
+/// Rather than recomputing every commitment, the openings are checked with a single random linear
+/// combination `sum_i p_i c_i = i_1(sum_i p_i X_i) + (sum_i p_i R_i) u`, which fails with
+/// overwhelming probability if any single opening is wrong. Each `R_i` is the `1 x 2` randomness
+/// matrix of a single [`commit_G1`](self::commit_G1). Malformed input is rejected.
+pub fn batch_verify_openings_G1<CR, E>(
+    coms: &[Com1<E>],
+    values: &[E::G1Affine],
+    rands: &[Matrix<E::ScalarField>],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> bool
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let m = coms.len();
+    if values.len() != m || rands.len() != m {
+        return false;
+    }
+    if rands.iter().any(|r| r.len() != 1 || r[0].len() != 2) {
+        return false;
+    }
 
-    Commit1::<E> {
-        coms: col_vec_to_vec(&coms),
-        rand: R,
+    let rho: Vec<E::ScalarField> = (0..m).map(|_| E::ScalarField::rand(rng)).collect();
+
+    // sum_i p_i c_i, one multi-scalar multiplication per coordinate
+    let coms_0: Vec<E::G1Affine> = coms.iter().map(|com| com.0).collect();
+    let coms_1: Vec<E::G1Affine> = coms.iter().map(|com| com.1).collect();
+    let lhs = Com1::<E>(
+        E::G1::msm_unchecked(&coms_0, &rho).into_affine(),
+        E::G1::msm_unchecked(&coms_1, &rho).into_affine(),
+    );
+
+    // i_1(sum_i p_i X_i) + (sum_i p_i r_i1) u_1 + (sum_i p_i r_i2) u_2, where both sums of the
+    // randomness are accumulated in a single pass
+    let value = E::G1::msm_unchecked(values, &rho).into_affine();
+    let zero = E::ScalarField::zero();
+    let (r1, r2) = rands
+        .iter()
+        .zip(&rho)
+        .fold((zero, zero), |(r1, r2), (r, p)| {
+            (r1 + r[0][0] * p, r2 + r[0][1] * p)
+        });
+    let rhs = Com1::<E>::linear_map(&value) + key.u1().scalar_mul(&r1) + key.u2().scalar_mul(&r2);
+
+    lhs == rhs
+}
+
+/// Verify a single opening `(X, r)` of a [`G1`](ark_ec::Pairing::G1Affine) commitment `c`, i.e. that
+/// `c = i_1(X) + r_1 u_1 + r_2 u_2`, e.g. for a committed constant that is revealed later.
+///
+/// `rand` is the randomness of a single [`commit_G1`](self::commit_G1) (see
+/// [`Commit1::randomness`](self::Commit1::randomness)). Malformed randomness is rejected.
+pub fn verify_opening_G1<E: Pairing>(
+    com: &Com1<E>,
+    value: &E::G1Affine,
+    rand: &[E::ScalarField],
+    key: &CRS<E>,
+) -> bool {
+    match rand {
+        [r1, r2] => commit_G1_with(value, key, r1, r2) == *com,
+        _ => false,
+    }
+}
+
+/// Verify a single opening `(Y, s)` of a [`G2`](ark_ec::Pairing::G2Affine) commitment `d`, i.e. that
+/// `d = i_2(Y) + s_1 v_1 + s_2 v_2`, as for [`verify_opening_G1`](self::verify_opening_G1).
+pub fn verify_opening_G2<E: Pairing>(
+    com: &Com2<E>,
+    value: &E::G2Affine,
+    rand: &[E::ScalarField],
+    key: &CRS<E>,
+) -> bool {
+    match rand {
+        [s1, s2] => commit_G2_with(value, key, s1, s2) == *com,
+        _ => false,
     }
 }
 
+// c := i_1'(x) + r u_1
+pub(crate) fn commit_scalar_to_B1_with<E: Pairing>(
+    scalar: &E::ScalarField,
+    key: &CRS<E>,
+    r: &E::ScalarField,
+) -> Com1<E> {
+    Com1::<E>::scalar_linear_map(scalar, key) + key.u1().scalar_mul(r)
+}
+
 /// Commit a single [scalar field](ark_ec::Pairing::Fr) element to [`B1`](crate::data_structures::Com1).
 pub fn commit_scalar_to_B1<CR, E>(
     scalar_xvar: &E::ScalarField,
@@ -109,16 +769,7 @@ where
     E: Pairing,
     CR: Rng,
 {
-    let r: E::ScalarField = E::ScalarField::rand(rng);
-
-    // c := i_1'(x) + r u_1
-    Commit1::<E> {
-        coms: vec![
-            Com1::<E>::scalar_linear_map(scalar_xvar, key)
-                + vec_to_col_vec(&key.u)[0][0].scalar_mul(&r),
-        ],
-        rand: vec![vec![r]],
-    }
+    Commit1::<E>::commit_scalar(scalar_xvar, key, rng)
 }
 
 /// Commit all [scalar field](ark_ec::Pairing::Fr) elements in list to corresponding element in [`B1`](crate::data_structures::Com1).
@@ -132,27 +783,109 @@ where
     CR: Rng,
 {
     let mprime = scalar_xvars.len();
-    let mut r: Matrix<E::ScalarField> = Vec::with_capacity(mprime);
+    let r: Matrix<E::ScalarField> =
+        MatrixExt::from_fn((mprime, 1), |_, _| E::ScalarField::rand(rng));
+
+    // c := i_1'(x) + r u_1, multiplying u_1 by the single column of r with a precomputed table
+    let r_col: Vec<E::ScalarField> = r.col(0).copied().collect();
+    let coms: Vec<Com1<E>> = Com1::<E>::batch_scalar_linear_map(scalar_xvars, key)
+        .into_iter()
+        .zip(Com1MulContext::new(&key.u1(), mprime).batch_mul(&r_col))
+        .map(|(lin, rand)| lin + rand)
+        .collect();
+
+    Commit1::<E> { coms, rand: r }
+}
+
+/// Commit all [scalar field](ark_ec::Pairing::Fr) elements in list to corresponding element in [`B1`](crate::data_structures::Com1),
+/// keeping the randomness in the compact form of [`CompactCommit1`](self::CompactCommit1).
+pub fn batch_commit_scalar_to_B1_compact<CR, E>(
+    scalar_xvars: &[E::ScalarField],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> CompactCommit1<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let mprime = scalar_xvars.len();
+    let mut r: Vec<E::ScalarField> = Vec::with_capacity(mprime);
     for _ in 0..mprime {
-        r.push(vec![E::ScalarField::rand(rng)]);
+        r.push(E::ScalarField::rand(rng));
     }
 
-    let slin_x: Matrix<Com1<E>> =
-        vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, key));
-    let ru: Matrix<Com1<E>> = vec_to_col_vec(
-        &col_vec_to_vec(&r)
-            .into_iter()
-            .map(|sca| vec_to_col_vec(&key.u)[0][0].scalar_mul(&sca))
-            .collect::<Vec<Com1<E>>>(),
-    );
+    // c := i_1'(x) + r u_1, computed entry by entry
+    let base = key.u2().prepare_scalar_base(&key.g1_gen);
+    let rand = Com1MulContext::new(&key.u1(), mprime).batch_mul(&r);
+    let coms: Vec<Com1<E>> = scalar_xvars
+        .iter()
+        .zip(rand)
+        .map(|(x, rand)| Com1::<E>::scalar_linear_map_with_base(&base, x) + rand)
+        .collect();
+
+    CompactCommit1::<E> { coms, rand: r }
+}
 
-    // c := i_1'(x) + r u_1 (mprime x 1 matrix)
-    let coms: Matrix<Com1<E>> = slin_x.add(&ru);
+/// The kind of variable at a position of a mixed commitment (see [`commit_mixed_B1`](self::commit_mixed_B1)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MixedVar {
+    /// The next [`G1`](ark_ec::Pairing::G1Affine) element.
+    Group,
+    /// The next [scalar field](ark_ec::Pairing::Fr) element.
+    Scalar,
+}
 
-    Commit1::<E> {
-        coms: col_vec_to_vec(&coms),
-        rand: r,
+/// Commit [`G1`](ark_ec::Pairing::G1Affine) and [scalar field](ark_ec::Pairing::Fr) elements to
+/// [`B1`](crate::data_structures::Com1) in one [`Commit1`](self::Commit1), taking the next element of
+/// `group_vars` or `scalar_vars` at each position as specified by `order`.
+///
+/// A scalar `x` is committed as `c := i_1'(x) + r u_1`, exactly as by [`commit_scalar_to_B1`](self::commit_scalar_to_B1).
+/// Since `i_1'(x) = i_1(x g1) + x u_2`, this is also a commitment to the group element `x g1` with
+/// randomness `(r, x)`, which is the randomness row it gets. Every row therefore has the 2 entries of a
+/// [`G1`](ark_ec::Pairing::G1Affine) commitment, so that the result can be used for the `X` variables
+/// of e.g. an [`MSMEG1`](crate::statement::MSMEG1) proof, where the scalar `x` is the variable `x g1`.
+///
+/// # Panics
+///
+/// Panics if `order` does not consist of exactly as many [`Group`](MixedVar::Group) and
+/// [`Scalar`](MixedVar::Scalar) entries as there are elements in `group_vars` and `scalar_vars`.
+pub fn commit_mixed_B1<CR, E>(
+    group_vars: &[E::G1Affine],
+    scalar_vars: &[E::ScalarField],
+    order: &[MixedVar],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Commit1<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let num_groups = order.iter().filter(|var| **var == MixedVar::Group).count();
+    assert_eq!(num_groups, group_vars.len());
+    assert_eq!(order.len() - num_groups, scalar_vars.len());
+
+    let mut group_vars = group_vars.iter();
+    let mut scalar_vars = scalar_vars.iter();
+    let mut coms = Vec::with_capacity(order.len());
+    let mut R: Matrix<E::ScalarField> = Vec::with_capacity(order.len());
+    for var in order {
+        match var {
+            MixedVar::Group => {
+                let xvar = group_vars.next().expect("counted above");
+                let (r1, r2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
+                coms.push(commit_G1_with(xvar, key, &r1, &r2));
+                R.push(vec![r1, r2]);
+            }
+            MixedVar::Scalar => {
+                let scalar_xvar = scalar_vars.next().expect("counted above");
+                let r = E::ScalarField::rand(rng);
+                coms.push(commit_scalar_to_B1_with(scalar_xvar, key, &r));
+                R.push(vec![r, *scalar_xvar]);
+            }
+        }
     }
+
+    Commit1::<E> { coms, rand: R }
 }
 
 /// Commit a single [`G2`](ark_ec::Pairing::G2Affine) element to [`B2`](crate::data_structures::Com2).
@@ -162,41 +895,87 @@ where
     CR: Rng,
 {
     let (s1, s2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
-
-    // d := i_2(y) + s_1 v_1 + s_2 v_2
     Commit2::<E> {
-        coms: vec![
-            Com2::<E>::linear_map(yvar)
-                + vec_to_col_vec(&key.v)[0][0].scalar_mul(&s1)
-                + vec_to_col_vec(&key.v)[1][0].scalar_mul(&s2),
-        ],
+        coms: vec![commit_G2_with(yvar, key, &s1, &s2)],
         rand: vec![vec![s1, s2]],
     }
 }
 
+// d := i_2(y) + s_1 v_1 + s_2 v_2
+pub(crate) fn commit_G2_with<E: Pairing>(
+    yvar: &E::G2Affine,
+    key: &CRS<E>,
+    s1: &E::ScalarField,
+    s2: &E::ScalarField,
+) -> Com2<E> {
+    Com2::<E>::linear_map(yvar) + key.v1().scalar_mul(s1) + key.v2().scalar_mul(s2)
+}
+
 /// Commit all [`G2`](ark_ec::Pairing::G2Affine) elements in list to corresponding element in [`B2`](crate::data_structures::Com2).
 pub fn batch_commit_G2<CR, E>(yvars: &[E::G2Affine], key: &CRS<E>, rng: &mut CR) -> Commit2<E>
 where
     E: Pairing,
     CR: Rng,
+{
+    batch_commit_G2_iter(yvars.iter().copied(), key, rng)
+}
+
+/// Commit all [`G2`](ark_ec::Pairing::G2Affine) elements produced by an iterator to corresponding element
+/// in [`B2`](crate::data_structures::Com2), without first collecting them into a slice.
+///
+/// The length of the iterator sizes the randomness, which is drawn up front in the same order as
+/// [`batch_commit_G2`](self::batch_commit_G2), so both produce the same commitment for the same input
+/// and RNG state.
+///
+/// # Panics
+///
+/// Panics if the iterator yields a different number of elements than its reported length.
+pub fn batch_commit_G2_iter<CR, E, I>(yvars: I, key: &CRS<E>, rng: &mut CR) -> Commit2<E>
+where
+    E: Pairing,
+    CR: Rng,
+    I: ExactSizeIterator<Item = E::G2Affine>,
 {
     // S is a random scalar n x 2 matrix
     let n = yvars.len();
-    let mut S: Matrix<E::ScalarField> = Vec::with_capacity(n);
-    for _ in 0..n {
-        S.push(vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)]);
-    }
-
-    // i_2(Y) = [ (O, Y_1), ..., (O, Y_m) ] (n x 1 matrix)
-    let lin_y: Matrix<Com2<E>> = vec_to_col_vec(&Com2::<E>::batch_linear_map(yvars));
-
-    // c := i_2(Y) + Sv (n x 1 matrix)
-    let coms = lin_y.add(&vec_to_col_vec(&key.v).left_mul(&S, false));
+    let S: Matrix<E::ScalarField> = MatrixExt::from_fn((n, 2), |_, _| E::ScalarField::rand(rng));
+
+    // The multiples of each key by its column of S, sharing one precomputed table per key unless
+    // there is a single multiple, which the table would not pay off for
+    let key_multiples = |key: Com2<E>, j: usize| -> Vec<Com2<E>> {
+        if n == 1 {
+            return vec![key.scalar_mul(&S[0][j])];
+        }
+        let col: Vec<E::ScalarField> = S.col(j).copied().collect();
+        Com2MulContext::new(&key, n).batch_mul(&col)
+    };
+    let v1_rand = key_multiples(key.v1(), 0);
+    let v2_rand = key_multiples(key.v2(), 1);
+
+    // c := i_2(Y) + Sv, summed row by row in projective coordinates in a single pass over Y, then
+    // converted back to affine with a single batched inversion (i.e. without the intermediate n x 1
+    // matrices for i_2(Y), v and their sum)
+    let sums: Vec<Com2Projective<E>> = yvars
+        .zip(v1_rand.into_iter().zip(v2_rand))
+        .map(|(yvar, (rand1, rand2))| {
+            Com2Projective::<E>::from(Com2::<E>::linear_map(&yvar))
+                + Com2Projective::<E>::from(rand1)
+                + Com2Projective::<E>::from(rand2)
+        })
+        .collect();
+    let coms = Com2Projective::<E>::normalize_batch(&sums);
+    assert_eq!(coms.len(), n);
+
+    Commit2::<E> { coms, rand: S }
+}
 
-    Commit2::<E> {
-        coms: col_vec_to_vec(&coms),
-        rand: S,
-    }
+// d := i_2'(y) + s v_1
+pub(crate) fn commit_scalar_to_B2_with<E: Pairing>(
+    scalar: &E::ScalarField,
+    key: &CRS<E>,
+    s: &E::ScalarField,
+) -> Com2<E> {
+    Com2::<E>::scalar_linear_map(scalar, key) + key.v1().scalar_mul(s)
 }
 
 /// Commit a single [scalar field](ark_ec::Pairing::Fr) element to [`B2`](crate::data_structures::Com2).
@@ -209,16 +988,7 @@ where
     E: Pairing,
     CR: Rng,
 {
-    let s: E::ScalarField = E::ScalarField::rand(rng);
-
-    // d := i_2'(y) + s v_1
-    Commit2::<E> {
-        coms: vec![
-            Com2::<E>::scalar_linear_map(scalar_yvar, key)
-                + vec_to_col_vec(&key.v)[0][0].scalar_mul(&s),
-        ],
-        rand: vec![vec![s]],
-    }
+    Commit2::<E>::commit_scalar(scalar_yvar, key, rng)
 }
 
 /// Commit all [scalar field](ark_ec::Pairing::Fr) elements in list to corresponding element in [`B2`](crate::data_structures::Com2).
@@ -232,27 +1002,18 @@ where
     CR: Rng,
 {
     let nprime = scalar_yvars.len();
-    let mut s: Matrix<E::ScalarField> = Vec::with_capacity(nprime);
-    for _ in 0..nprime {
-        s.push(vec![E::ScalarField::rand(rng)]);
-    }
-
-    let slin_y: Matrix<Com2<E>> =
-        vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, key));
-    let sv: Matrix<Com2<E>> = vec_to_col_vec(
-        &col_vec_to_vec(&s)
-            .into_iter()
-            .map(|sca| vec_to_col_vec(&key.v)[0][0].scalar_mul(&sca))
-            .collect::<Vec<Com2<E>>>(),
-    );
-
-    // d := i_2'(y) + s v_1 (nprime x 1 matrix)
-    let coms: Matrix<Com2<E>> = slin_y.add(&sv);
-
-    Commit2::<E> {
-        coms: col_vec_to_vec(&coms),
-        rand: s,
-    }
+    let s: Matrix<E::ScalarField> =
+        MatrixExt::from_fn((nprime, 1), |_, _| E::ScalarField::rand(rng));
+
+    // d := i_2'(y) + s v_1, multiplying v_1 by the single column of s with a precomputed table
+    let s_col: Vec<E::ScalarField> = s.col(0).copied().collect();
+    let coms: Vec<Com2<E>> = Com2::<E>::batch_scalar_linear_map(scalar_yvars, key)
+        .into_iter()
+        .zip(Com2MulContext::new(&key.v1(), nprime).batch_mul(&s_col))
+        .map(|(lin, rand)| lin + rand)
+        .collect();
+
+    Commit2::<E> { coms, rand: s }
 }
 
 #[cfg(test)]
@@ -463,6 +1224,237 @@ mod tests {
         assert_eq!(exp, res);
     }
 
+    #[test]
+    fn test_commit_G1_stream() {
+        std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+        let mut rng = test_rng();
+        let mut rng2 = test_rng();
+
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let _ = CRS::<F>::generate_crs(&mut rng2);
+
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen,
+            affine_group_new!(crs.g1_gen, "2"),
+            affine_group_new!(crs.g1_gen, "3"),
+        ];
+        let exp: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        // A lazy iterator with no useful size hint
+        let stream = xvars.iter().copied().filter(|_| true);
+        let res: Commit1<F> = commit_G1_stream(stream, &crs, &mut rng2);
+        assert_eq!(exp, res);
+
+        let empty: Commit1<F> = commit_G1_stream(vec![], &crs, &mut rng2);
+        assert!(empty.coms.is_empty());
+        assert!(empty.rand.is_empty());
+    }
+
+    #[test]
+    fn test_commit_mixed_B1() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let group_vars: Vec<G1Affine> = vec![crs.g1_gen, affine_group_new!(crs.g1_gen, "2")];
+        let scalar_vars: Vec<Fr> = vec![Fr::from_str("3").unwrap()];
+        let order = [MixedVar::Group, MixedVar::Scalar, MixedVar::Group];
+        let mixed: Commit1<F> = commit_mixed_B1(&group_vars, &scalar_vars, &order, &crs, &mut rng);
+
+        assert_eq!(mixed.coms.len(), 3);
+        assert!(mixed.rand.iter().all(|row| row.len() == 2));
+        // Group entries open as G1 commitments
+        for (i, xvar) in [0, 2].into_iter().zip(group_vars.iter()) {
+            let row = &mixed.rand[i];
+            assert_eq!(
+                mixed.coms[i],
+                Com1::<F>::linear_map(xvar)
+                    + crs.u1().scalar_mul(&row[0])
+                    + crs.u2().scalar_mul(&row[1])
+            );
+        }
+        // The scalar entry opens both as a scalar commitment and as a G1 commitment to x g1
+        let (com, row) = (mixed.coms[1], &mixed.rand[1]);
+        assert_eq!(row[1], scalar_vars[0]);
+        assert_eq!(
+            com,
+            Com1::<F>::scalar_linear_map(&scalar_vars[0], &crs) + crs.u1().scalar_mul(&row[0])
+        );
+        assert_eq!(
+            com,
+            Com1::<F>::linear_map(&affine_group_new!(crs.g1_gen, "3"))
+                + crs.u1().scalar_mul(&row[0])
+                + crs.u2().scalar_mul(&row[1])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_mixed_B1_order_mismatch() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let _ = commit_mixed_B1(&[crs.g1_gen], &[], &[MixedVar::Scalar], &crs, &mut rng);
+    }
+
+    #[test]
+    fn test_batch_commit_iter() {
+        std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+        let mut rng = test_rng();
+        let mut rng2 = test_rng();
+
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let _ = CRS::<F>::generate_crs(&mut rng2);
+
+        // Variables produced lazily from their discrete logarithms
+        let xvars = ["1", "2", "3"]
+            .iter()
+            .map(|k| affine_group_new!(crs.g1_gen, k));
+        let yvars = ["4", "5"].iter().map(|k| affine_group_new!(crs.g2_gen, k));
+        let xvec: Vec<G1Affine> = xvars.clone().collect();
+        let yvec: Vec<G2Affine> = yvars.clone().collect();
+
+        let exp1: Commit1<F> = batch_commit_G1(&xvec, &crs, &mut rng);
+        let exp2: Commit2<F> = batch_commit_G2(&yvec, &crs, &mut rng);
+        let res1: Commit1<F> = batch_commit_G1_iter(xvars, &crs, &mut rng2);
+        let res2: Commit2<F> = batch_commit_G2_iter(yvars, &crs, &mut rng2);
+        assert_eq!(exp1, res1);
+        assert_eq!(exp2, res2);
+    }
+
+    #[test]
+    fn test_commit_diff() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvar: G1Affine = affine_group_new!(crs.g1_gen, "2");
+        let yvar: G2Affine = affine_group_new!(crs.g2_gen, "3");
+        let xcom1: Commit1<F> = commit_G1(&xvar, &crs, &mut rng);
+        let xcom2: Commit1<F> = commit_G1(&xvar, &crs, &mut rng);
+        let ycom1: Commit2<F> = commit_G2(&yvar, &crs, &mut rng);
+        let ycom2: Commit2<F> = commit_G2(&yvar, &crs, &mut rng);
+
+        // Same value under different randomness: the difference commits to zero with randomness R - R'
+        let xdiff = xcom1.diff(&xcom2);
+        let (dr1, dr2) = (
+            xcom1.rand[0][0] - xcom2.rand[0][0],
+            xcom1.rand[0][1] - xcom2.rand[0][1],
+        );
+        assert_eq!(
+            xdiff,
+            vec![crs.u[0].scalar_mul(&dr1) + crs.u[1].scalar_mul(&dr2)]
+        );
+        let ydiff = ycom1.diff(&ycom2);
+        let (ds1, ds2) = (
+            ycom1.rand[0][0] - ycom2.rand[0][0],
+            ycom1.rand[0][1] - ycom2.rand[0][1],
+        );
+        assert_eq!(
+            ydiff,
+            vec![crs.v[0].scalar_mul(&ds1) + crs.v[1].scalar_mul(&ds2)]
+        );
+
+        // A different value leaves i_1(x - x') in the difference
+        let other: Commit1<F> = commit_G1(&crs.g1_gen, &crs, &mut rng);
+        let (dr1, dr2) = (
+            xcom1.rand[0][0] - other.rand[0][0],
+            xcom1.rand[0][1] - other.rand[0][1],
+        );
+        assert_ne!(
+            xcom1.diff(&other),
+            vec![crs.u[0].scalar_mul(&dr1) + crs.u[1].scalar_mul(&dr2)]
+        );
+    }
+
+    #[test]
+    fn test_commit_homomorphic_add() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (x1, x2) = (G1Affine::rand(&mut rng), G1Affine::rand(&mut rng));
+        let (y1, y2) = (G2Affine::rand(&mut rng), G2Affine::rand(&mut rng));
+        let xsum: Commit1<F> =
+            commit_G1(&x1, &crs, &mut rng).homomorphic_add(&commit_G1(&x2, &crs, &mut rng));
+        let ysum: Commit2<F> =
+            commit_G2(&y1, &crs, &mut rng).homomorphic_add(&commit_G2(&y2, &crs, &mut rng));
+
+        // commit(x) + commit(y) opens to x + y with the summed randomness
+        let x = (x1 + x2).into_affine();
+        let y = (y1 + y2).into_affine();
+        assert!(verify_opening_G1(
+            &xsum.coms[0],
+            &x,
+            xsum.randomness(0).unwrap(),
+            &crs
+        ));
+        assert!(verify_opening_G2(
+            &ysum.coms[0],
+            &y,
+            ysum.randomness(0).unwrap(),
+            &crs
+        ));
+        assert!(!verify_opening_G1(
+            &xsum.coms[0],
+            &x1,
+            xsum.randomness(0).unwrap(),
+            &crs
+        ));
+    }
+
+    #[test]
+    fn test_commit_select() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let xvars: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+
+        let selected = xcoms.select(&[2, 0]);
+        assert_eq!(selected.coms, vec![xcoms.coms[2], xcoms.coms[0]]);
+        assert_eq!(
+            selected.rand,
+            vec![xcoms.rand[2].clone(), xcoms.rand[0].clone()]
+        );
+        // A commitment without randomness selects none
+        let public = Commit1::<F>::from_coms(xcoms.coms.clone()).select(&[1]);
+        assert_eq!(public, Commit1::<F>::from_coms(vec![xcoms.coms[1]]));
+    }
+
+    #[test]
+    fn test_commit_permute() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let xvars: Vec<G1Affine> = (1..=4)
+            .map(|k| crs.g1_gen.mul(Fr::from(k as u64)).into_affine())
+            .collect();
+        let yvars: Vec<G2Affine> = (1..=4)
+            .map(|k| crs.g2_gen.mul(Fr::from(k as u64)).into_affine())
+            .collect();
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        let perm = [2, 0, 3, 1];
+        let mut inverse = [0; 4];
+        for (i, &idx) in perm.iter().enumerate() {
+            inverse[idx] = i;
+        }
+
+        // The permuted commitments are to the permuted variables
+        let xperm = xcoms.permute(&perm);
+        assert_eq!(xperm.coms[0], xcoms.coms[2]);
+        assert_eq!(xperm.rand[0], xcoms.rand[2]);
+        assert_eq!(xperm.permute(&inverse), xcoms);
+        let yperm = ycoms.permute(&perm);
+        assert_eq!(yperm.coms[3], ycoms.coms[1]);
+        assert_eq!(yperm.permute(&inverse), ycoms);
+        assert_eq!(xcoms.permute(&[0, 1, 2, 3]), xcoms);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a permutation")]
+    fn test_commit_permute_repeated_index() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let xcoms = batch_commit_G1(&[crs.g1_gen, crs.g1_gen], &crs, &mut rng);
+        let _ = xcoms.permute(&[1, 1]);
+    }
+
     #[test]
     fn test_commit_G2_batching() {
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
@@ -519,6 +1511,129 @@ mod tests {
         assert_eq!(exp, res);
     }
 
+    #[test]
+    fn test_batch_verify_openings_G1() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen,
+            affine_group_new!(crs.g1_gen, "2"),
+            affine_group_new!(crs.g1_gen, "3"),
+        ];
+        let commits: Vec<Commit1<F>> = xvars.iter().map(|x| commit_G1(x, &crs, &mut rng)).collect();
+        let coms: Vec<Com1<F>> = commits.iter().map(|c| c.coms[0]).collect();
+        let mut rands: Vec<Matrix<Fr>> = commits.iter().map(|c| c.rand.clone()).collect();
+
+        assert!(batch_verify_openings_G1(
+            &coms, &xvars, &rands, &crs, &mut rng
+        ));
+
+        // A single wrong value fails the whole batch
+        let mut wrong_xvars = xvars.clone();
+        wrong_xvars[1] = affine_group_new!(crs.g1_gen, "4");
+        assert!(!batch_verify_openings_G1(
+            &coms,
+            &wrong_xvars,
+            &rands,
+            &crs,
+            &mut rng
+        ));
+
+        // As does a single wrong randomness
+        rands[2][0][1] += Fr::one();
+        assert!(!batch_verify_openings_G1(
+            &coms, &xvars, &rands, &crs, &mut rng
+        ));
+
+        // Mismatched lengths are rejected
+        assert!(!batch_verify_openings_G1(
+            &coms[..2],
+            &xvars,
+            &rands,
+            &crs,
+            &mut rng
+        ));
+    }
+
+    #[test]
+    fn test_commit_scalar_B1_compact() {
+        std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+        let mut rng = test_rng();
+        let mut rng2 = test_rng();
+
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let _ = CRS::<F>::generate_crs(&mut rng2);
+
+        let scalar_xvars: Vec<Fr> = vec![
+            Fr::one(),
+            Fr::from_str("2").unwrap(),
+            Fr::from_str("3").unwrap(),
+        ];
+        let exp: Commit1<F> = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+        let res: CompactCommit1<F> =
+            batch_commit_scalar_to_B1_compact(&scalar_xvars, &crs, &mut rng2);
+
+        assert_eq!(res.rand.len(), 3);
+        assert_eq!(exp, Commit1::<F>::from(res.clone()));
+        assert_eq!(CompactCommit1::<F>::try_from(exp), Ok(res));
+    }
+
+    #[test]
+    fn test_commit_G1_not_compact() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let com: Commit1<F> = commit_G1(&crs.g1_gen, &crs, &mut rng);
+        assert_eq!(
+            CompactCommit1::<F>::try_from(com),
+            Err(ShapeError { expected: (1, 1) })
+        );
+    }
+
+    // Side-agnostic helper: commits the scalars one at a time through the trait.
+    fn commit_scalars_generic<C: ScalarCommit<F>>(scalars: &[Fr], crs: &CRS<F>) -> C {
+        let mut rng = test_rng();
+        let mut coms = C::commit_scalar(&scalars[0], crs, &mut rng);
+        for scalar in &scalars[1..] {
+            coms.append(&mut C::commit_scalar(scalar, crs, &mut rng));
+        }
+        assert_eq!(coms.coms().len(), scalars.len());
+        coms
+    }
+
+    #[test]
+    fn test_scalar_commit_trait() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let scalars = vec![Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng)];
+
+        let xcoms: Commit1<F> = commit_scalars_generic(&scalars, &crs);
+        for ((com, x), r) in xcoms.coms.iter().zip(scalars.iter()).zip(xcoms.rand.iter()) {
+            assert_eq!(
+                *com,
+                Com1::<F>::scalar_linear_map(x, &crs) + crs.u[0].scalar_mul(&r[0])
+            );
+        }
+        // The free function is a wrapper around the trait method
+        assert_eq!(
+            commit_scalar_to_B1(&scalars[0], &crs, &mut test_rng()),
+            Commit1::<F>::commit_scalar(&scalars[0], &crs, &mut test_rng())
+        );
+
+        let ycoms: Commit2<F> = commit_scalars_generic(&scalars, &crs);
+        for ((com, y), s) in ycoms.coms.iter().zip(scalars.iter()).zip(ycoms.rand.iter()) {
+            assert_eq!(
+                *com,
+                Com2::<F>::scalar_linear_map(y, &crs) + crs.v[0].scalar_mul(&s[0])
+            );
+        }
+        assert_eq!(
+            Commit2::<F>::batch_commit_scalar(&scalars, &crs, &mut test_rng()),
+            batch_commit_scalar_to_B2(&scalars, &crs, &mut test_rng())
+        );
+    }
+
     #[test]
     fn test_commit_scalar_B2_batching() {
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
@@ -4,9 +4,9 @@
 //! with respect to a pre-defined bilinear group `(A1, A2, AT)`:
 //!
 //! - `π`: 1-2 elements in [`B2`](crate::data_structures::Com2) (equiv. 2-4 elements in [`G2`](ark_ec::Pairing::G2Affine))
-//!     which prove about the satisfiability of `A2` variables in the equation, and
+//!   which prove about the satisfiability of `A2` variables in the equation, and
 //! - `θ`: 1-2 elements in [`B1`](crate::data_structures::Com1) (equiv. 2-4 elements in [`G1`](ark_ec::Pairing::G1Affine))
-//!     which prove about the satisfiability of `A1` variables in the equation
+//!   which prove about the satisfiability of `A1` variables in the equation
 //!
 //! Computing these proofs primarily involves matrix multiplication in the [scalar field](ark_ec::Pairing::Fr) and in `B1` and `B2`.
 //!
@@ -14,17 +14,61 @@
 
 use ark_ec::pairing::Pairing;
 use ark_ec::pairing::PairingOutput;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{rand::Rng, UniformRand};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, One, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+#[cfg(feature = "prf")]
+use ark_std::rand::RngCore;
+use ark_std::{
+    fmt,
+    ops::{AddAssign, Mul},
+    rand::Rng,
+    UniformRand,
+};
+#[cfg(feature = "prf")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "prf")]
+use sha2::Sha256;
 
 use super::commit::{
     batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
-    Commit1, Commit2,
+    commit_G1_with, commit_G2_with, Commit1, Commit2,
 };
-use crate::data_structures::{col_vec_to_vec, vec_to_col_vec, Com1, Com2, Mat, Matrix, B1, B2};
-use crate::generator::CRS;
+use crate::data_structures::{
+    batch_linear_map_both, deserialize_matrix_of_shape, deserialize_vec, deserialize_versioned,
+    serialize_matrix, serialize_vec, serialize_version, serialized_matrix_size,
+    serialized_vec_size, Com1, Com2, Mat, Matrix, MatrixExt, ScalarMatVec, ShapeError, B1, B2,
+    MAX_SERIALIZED_LEN, VERSION_HEADER_SIZE,
+};
+use crate::generator::{SimulationTrapdoor, CRS};
+#[cfg(feature = "prf")]
+use crate::statement::AggregatePPE;
 use crate::statement::{EquType, QuadEqu, MSMEG1, MSMEG2, PPE};
 
+impl<E: Pairing> PPE<E> {
+    // Checks that a proof of this equation has `pi_len` elements in π and `theta_len` in θ, returning the
+    // expected (column vector) shape of π, or else of θ, if not. As in `prove`, a linear equation in only
+    // x (resp. y) variables may leave out θ (resp. π).
+    pub(crate) fn check_proof_lens(
+        &self,
+        pi_len: usize,
+        theta_len: usize,
+    ) -> Result<(), ShapeError> {
+        let x_only = self.is_linear() && self.a_consts.iter().all(|a| a.is_zero());
+        let y_only = self.is_linear() && self.b_consts.iter().all(|b| b.is_zero());
+        if pi_len != 2 && !(y_only && pi_len == 0) {
+            return Err(ShapeError { expected: (2, 1) });
+        }
+        if theta_len != 2 && !(x_only && theta_len == 0) {
+            return Err(ShapeError { expected: (2, 1) });
+        }
+        Ok(())
+    }
+}
+
 /// A collection  of attributes containing prover functionality for an [`Equation`](crate::statement::Equation).
 pub trait Provable<E: Pairing, A1, A2, AT> {
     /// Commits to the witness variables and then produces a Groth-Sahai proof for this equation.
@@ -52,7 +96,13 @@ pub trait Provable<E: Pairing, A1, A2, AT> {
 }
 
 /// A witness-indistinguishable proof for a single [`Equation`](crate::statement::Equation).
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// The proof of a [linear](crate::statement::PPE::is_linear) pairing-product equation in only `x`
+/// (resp. `y`) variables leaves out `θ` (resp. `π`), which is zero.
+///
+/// It serializes with a leading version header, see [`FORMAT_VERSION`](crate::data_structures::FORMAT_VERSION),
+/// in either point encoding (see [the serialization tradeoffs](crate::data_structures#serialization)).
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EquProof<E: Pairing> {
     pub pi: Vec<Com2<E>>,
     pub theta: Vec<Com1<E>>,
@@ -61,11 +111,507 @@ pub struct EquProof<E: Pairing> {
 }
 
 /// A collection of committed variables and proofs for Groth-Sahai compatible bilinear equations.
+///
+/// It serializes with a leading version header, see [`FORMAT_VERSION`](crate::data_structures::FORMAT_VERSION).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CProof<E: Pairing> {
     pub xcoms: Commit1<E>,
     pub ycoms: Commit2<E>,
     pub equ_proofs: Vec<EquProof<E>>,
+    /// The [`fingerprint`](crate::generator::CRS::fingerprint) of the CRS the variables were committed under.
+    ///
+    /// It guards against accidentally verifying under the wrong CRS, not against a malicious prover.
+    pub crs_fingerprint: u64,
+}
+
+impl<E: Pairing> CanonicalSerialize for EquProof<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_version(&mut writer)?;
+        serialize_vec(&self.pi, &mut writer, compress)?;
+        serialize_vec(&self.theta, &mut writer, compress)?;
+        self.equ_type.serialize_with_mode(&mut writer, compress)?;
+        serialize_matrix(&self.rand, &mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        VERSION_HEADER_SIZE
+            + serialized_vec_size(&self.pi, compress)
+            + serialized_vec_size(&self.theta, compress)
+            + self.equ_type.serialized_size(compress)
+            + serialized_matrix_size(&self.rand, compress)
+    }
+}
+
+impl<E: Pairing> Valid for EquProof<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.pi.check()?;
+        self.theta.check()?;
+        self.equ_type.check()?;
+        self.rand.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for EquProof<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        // π and θ have at most two elements, and the proof randomness T (if not left out) has a row per
+        // commitment key of the y variables and a column per commitment key of the x variables
+        deserialize_versioned(reader, |mut reader, version| {
+            let pi = deserialize_vec(&mut reader, version, compress, validate, 2)?;
+            let theta = deserialize_vec(&mut reader, version, compress, validate, 2)?;
+            let equ_type = EquType::deserialize_with_mode(&mut reader, compress, validate)?;
+            let (rows, cols) = match equ_type {
+                EquType::PairingProduct => (2, 2),
+                EquType::MultiScalarG1 => (1, 2),
+                EquType::MultiScalarG2 => (2, 1),
+                EquType::Quadratic => (1, 1),
+            };
+            Ok(EquProof::<E> {
+                pi,
+                theta,
+                equ_type,
+                rand: deserialize_matrix_of_shape(
+                    &mut reader,
+                    version,
+                    compress,
+                    validate,
+                    rows,
+                    &[cols],
+                )?,
+            })
+        })
+    }
+}
+
+impl<E: Pairing> CanonicalSerialize for CProof<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_version(&mut writer)?;
+        self.xcoms.serialize_with_mode(&mut writer, compress)?;
+        self.ycoms.serialize_with_mode(&mut writer, compress)?;
+        serialize_vec(&self.equ_proofs, &mut writer, compress)?;
+        self.crs_fingerprint
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        VERSION_HEADER_SIZE
+            + self.xcoms.serialized_size(compress)
+            + self.ycoms.serialized_size(compress)
+            + serialized_vec_size(&self.equ_proofs, compress)
+            + self.crs_fingerprint.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for CProof<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.xcoms.check()?;
+        self.ycoms.check()?;
+        self.equ_proofs.check()?;
+        self.crs_fingerprint.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for CProof<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        deserialize_versioned(reader, |mut reader, version| {
+            Ok(CProof::<E> {
+                xcoms: Commit1::deserialize_with_mode(&mut reader, compress, validate)?,
+                ycoms: Commit2::deserialize_with_mode(&mut reader, compress, validate)?,
+                equ_proofs: deserialize_vec(
+                    &mut reader,
+                    version,
+                    compress,
+                    validate,
+                    MAX_SERIALIZED_LEN,
+                )?,
+                crs_fingerprint: u64::deserialize_with_mode(&mut reader, compress, validate)?,
+            })
+        })
+    }
+}
+
+impl<E: Pairing> EquProof<E> {
+    /// Builds a proof of an equation of type `equ_type` from its public parts `(π, θ)`, e.g. after
+    /// deserializing them separately. The proof does not carry the prover's randomness, as in
+    /// [`CProof::without_randomness`].
+    ///
+    /// Returns an error with the expected (column vector) shape of `π`, or else of `θ`, if either has
+    /// the wrong number of elements for the type of equation.
+    pub fn from_parts(
+        pi: Vec<Com2<E>>,
+        theta: Vec<Com1<E>>,
+        equ_type: EquType,
+    ) -> Result<Self, ShapeError> {
+        // pi (resp. theta) only has a single element if the y (resp. x) variables are scalars
+        let (pi_len, theta_len) = match equ_type {
+            EquType::PairingProduct => (2, 2),
+            EquType::MultiScalarG1 => (2, 1),
+            EquType::MultiScalarG2 => (1, 2),
+            EquType::Quadratic => (1, 1),
+        };
+        // The proof of a linear pairing-product equation may leave out its zero pi or theta
+        let reduced = equ_type == EquType::PairingProduct && (pi.is_empty() || theta.is_empty());
+        if pi.len() != pi_len && !(reduced && pi.is_empty()) {
+            return Err(ShapeError {
+                expected: (pi_len, 1),
+            });
+        }
+        if theta.len() != theta_len && !(reduced && theta.is_empty()) {
+            return Err(ShapeError {
+                expected: (theta_len, 1),
+            });
+        }
+        Ok(Self {
+            pi,
+            theta,
+            equ_type,
+            rand: vec![],
+        })
+    }
+
+    /// The proof's elements `π` in [`B2`](crate::data_structures::Com2).
+    #[inline]
+    pub fn pi(&self) -> &[Com2<E>] {
+        &self.pi
+    }
+
+    /// The proof's elements `θ` in [`B1`](crate::data_structures::Com1).
+    #[inline]
+    pub fn theta(&self) -> &[Com1<E>] {
+        &self.theta
+    }
+
+    /// Whether the proof has the shape of a proof of `equ`, i.e. is a proof of a pairing-product equation
+    /// with two elements in each of `π` and `θ`, or with one of them left out if the equation is linear in
+    /// only the other kind of variable (as the prover does).
+    ///
+    /// This is cheap, and [`verify`](crate::verifier::Verifiable::verify) checks it before computing any
+    /// pairing, so a misshapen proof is rejected early. It does not check the commitments, whose
+    /// number depends on the equation's variable counts.
+    pub fn check_shape(&self, equ: &PPE<E>) -> bool {
+        self.equ_type == EquType::PairingProduct
+            && equ
+                .check_proof_lens(self.pi.len(), self.theta.len())
+                .is_ok()
+    }
+
+    /// Returns a copy of the proof without the prover's private proof randomness, as in
+    /// [`CProof::without_randomness`].
+    pub fn without_randomness(&self) -> Self {
+        EquProof::<E> {
+            pi: self.pi.clone(),
+            theta: self.theta.clone(),
+            equ_type: self.equ_type.clone(),
+            rand: vec![],
+        }
+    }
+}
+
+impl<E: Pairing> CProof<E> {
+    /// Returns a copy of the proof without the prover's private commitment and proof randomness,
+    /// i.e. only the parts of the proof that a verifier needs and that are safe to publish.
+    pub fn without_randomness(&self) -> Self {
+        CProof::<E> {
+            xcoms: Commit1::<E> {
+                coms: self.xcoms.coms.clone(),
+                rand: vec![],
+            },
+            ycoms: Commit2::<E> {
+                coms: self.ycoms.coms.clone(),
+                rand: vec![],
+            },
+            equ_proofs: self
+                .equ_proofs
+                .iter()
+                .map(EquProof::without_randomness)
+                .collect(),
+            crs_fingerprint: self.crs_fingerprint,
+        }
+    }
+}
+
+/// A witness of [`G1`](ark_ec::Pairing::G1Affine) and [`G2`](ark_ec::Pairing::G2Affine) variables that is
+/// committed once and shared by several equations, which reference its variables by index.
+///
+/// Every proof from [`prove_for`](SharedWitness::prove_for) carries the identical commitments, so a
+/// verifier comparing them across proofs knows all the equations hold for the same variables.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SharedWitness<E: Pairing> {
+    xvars: Vec<E::G1Affine>,
+    yvars: Vec<E::G2Affine>,
+    xcoms: Commit1<E>,
+    ycoms: Commit2<E>,
+    crs_fingerprint: u64,
+}
+
+// Shows only the number of variables and the commitments, since the variables and the randomness of
+// their commitments are secret
+impl<E: Pairing> fmt::Debug for SharedWitness<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedWitness")
+            .field("xvars", &format_args!("<{} redacted>", self.xvars.len()))
+            .field("yvars", &format_args!("<{} redacted>", self.yvars.len()))
+            .field("xcoms", &self.xcoms.coms)
+            .field("ycoms", &self.ycoms.coms)
+            .field("crs_fingerprint", &self.crs_fingerprint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: Pairing> SharedWitness<E> {
+    /// Commits to the `x` and `y` variables under `crs`.
+    pub fn commit<CR: Rng>(
+        xvars: Vec<E::G1Affine>,
+        yvars: Vec<E::G2Affine>,
+        crs: &CRS<E>,
+        rng: &mut CR,
+    ) -> Self {
+        let xcoms = batch_commit_G1(&xvars, crs, rng);
+        let ycoms = batch_commit_G2(&yvars, crs, rng);
+        Self {
+            xvars,
+            yvars,
+            xcoms,
+            ycoms,
+            crs_fingerprint: crs.fingerprint(),
+        }
+    }
+
+    /// The shared commitment to the `x` variables.
+    #[inline]
+    pub fn xcoms(&self) -> &Commit1<E> {
+        &self.xcoms
+    }
+
+    /// The shared commitment to the `y` variables.
+    #[inline]
+    pub fn ycoms(&self) -> &Commit2<E> {
+        &self.ycoms
+    }
+
+    /// Proves that the shared variables satisfy `equ`, with the shared commitments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `crs` is not the CRS the variables were committed under.
+    pub fn prove_for<Q, AT, CR>(&self, equ: &Q, crs: &CRS<E>, rng: &mut CR) -> CProof<E>
+    where
+        Q: Provable<E, E::G1Affine, E::G2Affine, AT>,
+        CR: Rng,
+    {
+        assert_eq!(
+            self.crs_fingerprint,
+            crs.fingerprint(),
+            "the shared witness was committed under a different CRS"
+        );
+        CProof::<E> {
+            xcoms: self.xcoms.clone(),
+            ycoms: self.ycoms.clone(),
+            equ_proofs: vec![equ.prove(
+                &self.xvars,
+                &self.yvars,
+                &self.xcoms,
+                &self.ycoms,
+                crs,
+                rng,
+            )],
+            crs_fingerprint: self.crs_fingerprint,
+        }
+    }
+}
+
+/// Proves that the scalar committed in `com1` equals the scalar committed in `com2`, i.e. the
+/// [`scalar_equality`](crate::statement::QuadEqu::scalar_equality) equation, given that common `scalar`.
+///
+/// Both commitments must be commitments to the single `scalar`, made with
+/// [`commit_scalar_to_B1`](super::commit::commit_scalar_to_B1) and
+/// [`commit_scalar_to_B2`](super::commit::commit_scalar_to_B2) respectively.
+pub fn prove_scalar_equality<CR, E>(
+    com1: &Commit1<E>,
+    com2: &Commit2<E>,
+    scalar: &E::ScalarField,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    QuadEqu::<E>::scalar_equality().prove(&[*scalar], &[*scalar], com1, com2, crs, rng)
+}
+
+/// A proof from [`prove_gt_exponent`] that `e(g, h)^z = t` for a committed scalar `z`, which holds no
+/// randomness and is safe to publish.
+///
+/// A scalar in the exponent of an arbitrary base in `GT` cannot be paired directly, so the relation is
+/// reduced to the multi-scalar equation `X = z g` in [`G1`](ark_ec::Pairing::G1Affine), for a committed
+/// auxiliary variable `X`, and the pairing-product equation `e(X, h) = t`.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GtExponentProof<E: Pairing> {
+    /// The commitment to `X = z g` in [`B1`](crate::data_structures::B1).
+    pub xcom: Com1<E>,
+    /// The proof of `X = z g` over `xcom` and the commitment to `z`.
+    pub msm_proof: EquProof<E>,
+    /// The proof of `e(X, h) = t` over `xcom`.
+    pub ppe_proof: EquProof<E>,
+}
+
+impl<E: Pairing> GtExponentProof<E> {
+    // The equations `z g - X = 0`, with X as the only x variable and z as the only (scalar) y variable,
+    // and `e(X, h) = t`, with X as the only variable
+    pub(crate) fn equations(
+        g: &E::G1Affine,
+        h: &E::G2Affine,
+        target: &PairingOutput<E>,
+    ) -> (MSMEG1<E>, PPE<E>) {
+        let msm = MSMEG1::<E> {
+            a_consts: vec![*g],
+            b_consts: vec![-E::ScalarField::one()],
+            gamma: vec![vec![E::ScalarField::zero()]],
+            target: E::G1Affine::zero(),
+        };
+        let ppe = PPE::<E> {
+            a_consts: vec![],
+            b_consts: vec![*h],
+            gamma: vec![vec![]],
+            target: *target,
+        };
+        (msm, ppe)
+    }
+}
+
+/// Proves that `e(g, h)^z = t` in `GT`, for the scalar `z` committed in `z_com`, given `z`.
+///
+/// The base is given as the pairing of `g` and `h`, which the reduction (see [`GtExponentProof`]) needs.
+/// `z_com` must be a commitment to the single scalar `z`, made with
+/// [`commit_scalar_to_B2`](super::commit::commit_scalar_to_B2), e.g. to also use `z` in other equations.
+/// The proof only verifies if `e(g, h)^z = t`.
+pub fn prove_gt_exponent<CR, E>(
+    g: &E::G1Affine,
+    h: &E::G2Affine,
+    target: &PairingOutput<E>,
+    z: &E::ScalarField,
+    z_com: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> GtExponentProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let (msm, ppe) = GtExponentProof::<E>::equations(g, h, target);
+    let xvar = g.mul(*z).into_affine();
+    let xcom = batch_commit_G1(&[xvar], crs, rng);
+    let msm_proof = msm.prove(&[xvar], &[*z], &xcom, z_com, crs, rng);
+    let no_ycoms = Commit2::<E>::from_coms(vec![]);
+    let ppe_proof = ppe.prove(&[xvar], &[], &xcom, &no_ycoms, crs, rng);
+    GtExponentProof::<E> {
+        xcom: xcom.coms[0],
+        msm_proof: msm_proof.without_randomness(),
+        ppe_proof: ppe_proof.without_randomness(),
+    }
+}
+
+// A deterministic RNG whose output stream is HMAC-SHA256(key, domain || input || counter) for
+// counter = 0, 1, ..., with the domain and input length-prefixed so that their encoding is unambiguous
+#[cfg(feature = "prf")]
+pub(crate) struct PrfRng {
+    mac: Hmac<Sha256>,
+    counter: u64,
+    block: [u8; 32],
+    pos: usize,
+}
+
+#[cfg(feature = "prf")]
+impl PrfRng {
+    pub(crate) fn new(key: &[u8], domain: &[u8], input: &[u8]) -> Self {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        for part in [domain, input] {
+            mac.update(&(part.len() as u64).to_le_bytes());
+            mac.update(part);
+        }
+        Self {
+            mac,
+            counter: 0,
+            block: [0; 32],
+            pos: 32,
+        }
+    }
+}
+
+#[cfg(feature = "prf")]
+impl RngCore for PrfRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest {
+            if self.pos == self.block.len() {
+                let mut mac = self.mac.clone();
+                mac.update(&self.counter.to_le_bytes());
+                self.block = mac.finalize().into_bytes().into();
+                self.counter += 1;
+                self.pos = 0;
+            }
+            *byte = self.block[self.pos];
+            self.pos += 1;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ark_std::rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// The matrix `R^T Γ S - T^T` from the product `R^T Γ S`, which is the empty product (i.e. the zero matrix
+// of the shape of `T^T`) for an equation without x or y variables
+fn sub_pf_rand<F: Field>(rand_stmt: Matrix<F>, pf_rand: &Matrix<F>) -> Matrix<F> {
+    let neg_pf_rand = pf_rand.transpose().into_neg();
+    if rand_stmt.is_empty_matrix() {
+        return neg_pf_rand;
+    }
+    rand_stmt.add(&neg_pf_rand)
+}
+
+// Adds `other` to `acc` entry-wise, where an empty vector (e.g. the product with a matrix without
+// rows, for an equation without variables of some kind) is the zero vector of the other's length
+fn add_assign_vec<T: Clone + for<'a> AddAssign<&'a T>>(acc: &mut Vec<T>, other: &[T]) {
+    if other.is_empty() {
+        return;
+    }
+    if acc.is_empty() {
+        acc.extend_from_slice(other);
+        return;
+    }
+    assert_eq!(acc.len(), other.len());
+    for (lhs, rhs) in acc.iter_mut().zip(other.iter()) {
+        *lhs += rhs;
+    }
 }
 
 impl<E: Pairing> Provable<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE<E> {
@@ -86,6 +632,7 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
             xcoms: xcoms.clone(),
             ycoms: ycoms.clone(),
             equ_proofs: vec![self.prove(xvars, yvars, &xcoms, &ycoms, crs, rng)],
+            crs_fingerprint: crs.fingerprint(),
         }
     }
 
@@ -101,65 +648,118 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
     where
         CR: Rng,
     {
+        self.prove_with_pf_rand(xvars, yvars, xcoms, ycoms, crs, || {
+            vec![
+                vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+                vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+            ]
+        })
+    }
+}
+
+impl<E: Pairing> PPE<E> {
+    // Proves this equation with the (2 x 2) proof randomness T from `pf_rand`, which is only called
+    // if the proof needs it
+    fn prove_with_pf_rand(
+        &self,
+        xvars: &[E::G1Affine],
+        yvars: &[E::G2Affine],
+        xcoms: &Commit1<E>,
+        ycoms: &Commit2<E>,
+        crs: &CRS<E>,
+        pf_rand: impl FnOnce() -> Matrix<E::ScalarField>,
+    ) -> EquProof<E> {
         // Gamma is an (m x n) matrix with m x variables and n y variables
         // x's commit randomness (i.e. R) is a (m x 2) matrix
         assert_eq!(xvars.len(), xcoms.rand.len());
         assert_eq!(self.gamma.len(), xcoms.rand.len());
-        assert_eq!(xcoms.rand[0].len(), 2);
+        assert!(xcoms.rand.iter().all(|row| row.len() == 2));
         let _m = xvars.len();
         // y's commit randomness (i.e. S) is a (n x 2) matrix
         assert_eq!(yvars.len(), ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), ycoms.rand.len());
-        assert_eq!(ycoms.rand[0].len(), 2);
+        assert!(self.gamma.iter().all(|row| row.len() == ycoms.rand.len()));
+        assert!(ycoms.rand.iter().all(|row| row.len() == 2));
         let _n = yvars.len();
 
-        let is_parallel = true;
-
-        // (2 x m) field matrix R^T, in GS parlance
-        let x_rand_trans = xcoms.rand.transpose();
-        // (2 x n) field matrix S^T, in GS parlance
-        let y_rand_trans = ycoms.rand.transpose();
-        // (2 x 2) field matrix T, in GS parlance
-        let pf_rand: Matrix<E::ScalarField> = vec![
-            vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
-            vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
-        ];
+        // A linear equation in only one kind of variable has a proof of half the size: its other half
+        // is zero, and T is not needed to randomize it, since (under a hiding CRS) the remaining half is
+        // determined by the commitments alone
+        if self.is_linear() && self.a_consts.iter().all(|a| a.is_zero()) {
+            // pi := R^T i_2(B), computed as i_2(B)^T R
+            return EquProof::<E> {
+                pi: xcoms
+                    .rand
+                    .com_vec_mul(&Com2::<E>::batch_linear_map(&self.b_consts)),
+                theta: vec![],
+                equ_type: EquType::PairingProduct,
+                rand: vec![],
+            };
+        }
+        if self.is_linear() && self.b_consts.iter().all(|b| b.is_zero()) {
+            // theta := S^T i_1(A), computed as i_1(A)^T S
+            return EquProof::<E> {
+                pi: vec![],
+                theta: ycoms
+                    .rand
+                    .com_vec_mul(&Com1::<E>::batch_linear_map(&self.a_consts)),
+                equ_type: EquType::PairingProduct,
+                rand: vec![],
+            };
+        }
 
-        // (2 x 1) Com2 matrix
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_linear_map(&self.b_consts))
-            .left_mul(&x_rand_trans, is_parallel);
+        let is_parallel = true;
 
-        // (2 x n) field matrix
-        let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (2 x 1) Com2 matrix
-        let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_linear_map(yvars)).left_mul(&x_rand_stmt, is_parallel);
+        // The commitments enter the proof only through their randomness R (m x 2) and S (n x 2), which
+        // are multiplied in place rather than transposed or cloned: R^T and S^T are borrowed views or
+        // folded into row-vector products (v^T M = (M^T v)^T), and R^T Γ is computed once for both of its
+        // uses. The proof is identical to that of the textbook formulas.
 
-        // (2 x 2) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        // (2 x 1) Com2 matrix
-        let pf_rand_stmt_com2 = vec_to_col_vec(&crs.v).left_mul(&pf_rand_stmt, is_parallel);
-
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        // (2 x 2) field matrix T, in GS parlance
+        let pf_rand = pf_rand();
+        assert!(pf_rand.len() == 2 && pf_rand.iter().all(|row| row.len() == 2));
+
+        // The linear maps of the constants and variables in both groups
+        let (lin_a, lin_b) = batch_linear_map_both::<E>(&self.a_consts, &self.b_consts);
+        let (lin_x, lin_y) = batch_linear_map_both::<E>(xvars, yvars);
+
+        // Com2 vector of length 2: R^T i_2(B), computed as i_2(B)^T R
+        let x_rand_lin_b = xcoms.rand.com_vec_mul(&lin_b);
+
+        // (2 x n) field matrix R^T Γ
+        let x_rand_stmt = xcoms
+            .rand
+            .transposed_view()
+            .right_mul(&self.gamma, is_parallel);
+        // Com2 vector of length 2
+        let x_rand_stmt_lin_y = x_rand_stmt.mul_com_vec(&lin_y);
+
+        // (2 x 2) field matrix R^T Γ S - T^T
+        let pf_rand_stmt = sub_pf_rand(x_rand_stmt.right_mul(&ycoms.rand, is_parallel), &pf_rand);
+        // Com2 vector of length 2
+        let pf_rand_stmt_com2 = pf_rand_stmt.mul_com_vec(&crs.v);
+
+        // Accumulate the Com2 vectors in place
+        let mut pi = x_rand_lin_b;
+        add_assign_vec(&mut pi, &x_rand_stmt_lin_y);
+        add_assign_vec(&mut pi, &pf_rand_stmt_com2);
         assert_eq!(pi.len(), 2);
 
-        // (2 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_linear_map(&self.a_consts))
-            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 2: S^T i_1(A), computed as i_1(A)^T S
+        let y_rand_lin_a = ycoms.rand.com_vec_mul(&lin_a);
 
-        // (2 x m) field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (2 x 1) Com1 matrix
-        let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_linear_map(xvars)).left_mul(&y_rand_stmt, is_parallel);
+        // Com1 vector of length 2: S^T Γ^T i_1(X), computed as i_1(X)^T (Γ S) for the (m x 2) field matrix Γ S
+        let y_rand_stmt_lin_x = self
+            .gamma
+            .right_mul(&ycoms.rand, is_parallel)
+            .com_vec_mul(&lin_x);
 
-        // (2 x 1) Com1 matrix
-        let pf_rand_com1 = vec_to_col_vec(&crs.u).left_mul(&pf_rand, is_parallel);
+        // Com1 vector of length 2
+        let pf_rand_com1 = pf_rand.mul_com_vec(&crs.u);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        // Accumulate the Com1 vectors in place
+        let mut theta = y_rand_lin_a;
+        add_assign_vec(&mut theta, &y_rand_stmt_lin_x);
+        add_assign_vec(&mut theta, &pf_rand_com1);
         assert_eq!(theta.len(), 2);
 
         EquProof::<E> {
@@ -171,6 +771,189 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
     }
 }
 
+/// All the randomness of a [`CProof`] for a single [`PPE`]: the commitment randomness `R` (m x 2) of
+/// the `x` variables, `S` (n x 2) of the `y` variables, and the proof randomness `T` (2 x 2).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProofRandomness<E: Pairing> {
+    pub xrand: Matrix<E::ScalarField>,
+    pub yrand: Matrix<E::ScalarField>,
+    pub pf_rand: Matrix<E::ScalarField>,
+}
+
+impl<E: Pairing> ProofRandomness<E> {
+    /// Samples the randomness for `m` x variables and `n` y variables.
+    pub fn rand<R: Rng>(m: usize, n: usize, rng: &mut R) -> Self {
+        let mut rand_rows = |rows: usize| -> Matrix<E::ScalarField> {
+            (0..rows)
+                .map(|_| vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)])
+                .collect()
+        };
+        Self {
+            xrand: rand_rows(m),
+            yrand: rand_rows(n),
+            pf_rand: rand_rows(2),
+        }
+    }
+}
+
+impl<E: Pairing> PPE<E> {
+    /// Commits to the variables and proves this equation as [`commit_and_prove`](Provable::commit_and_prove)
+    /// does, but with the given randomness rather than sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the randomness does not have one row of two scalars per variable, and a `T` of 2 x 2.
+    pub fn prove_with_rand(
+        &self,
+        xvars: &[E::G1Affine],
+        yvars: &[E::G2Affine],
+        rand: &ProofRandomness<E>,
+        crs: &CRS<E>,
+    ) -> CProof<E> {
+        assert_eq!(xvars.len(), rand.xrand.len());
+        assert_eq!(yvars.len(), rand.yrand.len());
+        assert!(rand
+            .xrand
+            .iter()
+            .chain(&rand.yrand)
+            .all(|row| row.len() == 2));
+        let xcoms = Commit1::<E> {
+            coms: xvars
+                .iter()
+                .zip(&rand.xrand)
+                .map(|(x, r)| commit_G1_with(x, crs, &r[0], &r[1]))
+                .collect(),
+            rand: rand.xrand.clone(),
+        };
+        let ycoms = Commit2::<E> {
+            coms: yvars
+                .iter()
+                .zip(&rand.yrand)
+                .map(|(y, s)| commit_G2_with(y, crs, &s[0], &s[1]))
+                .collect(),
+            rand: rand.yrand.clone(),
+        };
+        let proof =
+            self.prove_with_pf_rand(xvars, yvars, &xcoms, &ycoms, crs, || rand.pf_rand.clone());
+
+        CProof::<E> {
+            xcoms,
+            ycoms,
+            equ_proofs: vec![proof],
+            crs_fingerprint: crs.fingerprint(),
+        }
+    }
+
+    /// Samples randomness with which [`prove_with_rand`](PPE::prove_with_rand) proves this equation
+    /// without its witness, i.e. with the identity for every variable, under the hiding CRS of `trapdoor`.
+    ///
+    /// Under a hiding CRS, the commitments and proof produced this way are distributed exactly as those
+    /// of a real witness, which is the zero-knowledge simulation of Groth-Sahai proofs. It only applies to
+    /// equations with the identity as target: those are satisfied by the identity for every variable,
+    /// while other targets would need the simulator to know a pairing that equals them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trapdoor` is not the trapdoor of `crs`, i.e. if the CRS may be binding and the
+    /// simulated proof would reveal that it has no witness, or if the target is not the identity.
+    pub fn simulation_randomness<R: Rng>(
+        &self,
+        trapdoor: &SimulationTrapdoor<E>,
+        crs: &CRS<E>,
+        rng: &mut R,
+    ) -> ProofRandomness<E> {
+        assert!(
+            trapdoor.is_for(crs),
+            "cannot simulate a proof under a CRS without its hiding trapdoor"
+        );
+        assert!(
+            self.target.is_zero(),
+            "cannot simulate a proof for an equation whose target is not the identity"
+        );
+        ProofRandomness::rand(self.b_consts.len(), self.a_consts.len(), rng)
+    }
+
+    /// Simulates a proof for this equation under the hiding CRS of `trapdoor`, without a witness, as
+    /// [`prove_with_rand`](PPE::prove_with_rand) with the identity for every variable and the
+    /// [`simulation_randomness`](PPE::simulation_randomness).
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`simulation_randomness`](PPE::simulation_randomness) does.
+    pub fn simulate<R: Rng>(
+        &self,
+        trapdoor: &SimulationTrapdoor<E>,
+        crs: &CRS<E>,
+        rng: &mut R,
+    ) -> CProof<E> {
+        let rand = self.simulation_randomness(trapdoor, crs, rng);
+        let xvars = vec![E::G1Affine::zero(); rand.xrand.len()];
+        let yvars = vec![E::G2Affine::zero(); rand.yrand.len()];
+        self.prove_with_rand(&xvars, &yvars, &rand, crs)
+    }
+}
+
+#[cfg(feature = "prf")]
+impl<E: Pairing> PPE<E> {
+    /// Produces a proof `(π, θ)` for this equation as [`prove`](Provable::prove) does, but with the
+    /// proof randomness derived from `key`, the witness and the statement rather than sampled, so that
+    /// proving the same statement with the same witness under the same key always gives the same proof.
+    ///
+    /// The statement is this equation together with the (public) commitments and the CRS, and the
+    /// witness is the variables `xvars` and `yvars`. The randomness is the output of HMAC-SHA256 in
+    /// counter mode, keyed by `key`, over the witness and the statement, in the manner of RFC 6979.
+    /// `key` must be kept secret and uniformly random for the proof to remain witness-indistinguishable.
+    ///
+    /// Requires the `prf` feature.
+    pub fn prove_deterministic(
+        &self,
+        xvars: &[E::G1Affine],
+        yvars: &[E::G2Affine],
+        xcoms: &Commit1<E>,
+        ycoms: &Commit2<E>,
+        crs: &CRS<E>,
+        key: &[u8],
+    ) -> EquProof<E> {
+        // The witness and the statement, whose serializations are self-delimiting
+        let mut input = Vec::new();
+        xvars
+            .serialize_compressed(&mut input)
+            .and_then(|_| yvars.serialize_compressed(&mut input))
+            .and_then(|_| self.serialize_compressed(&mut input))
+            .and_then(|_| xcoms.coms.serialize_compressed(&mut input))
+            .and_then(|_| ycoms.coms.serialize_compressed(&mut input))
+            .and_then(|_| crs.serialize_compressed(&mut input))
+            .expect("serializing into a Vec cannot fail");
+        let mut rng = PrfRng::new(key, b"groth-sahai PPE proof randomness", &input);
+        self.prove(xvars, yvars, xcoms, ycoms, crs, &mut rng)
+    }
+}
+
+#[cfg(feature = "prf")]
+impl<E: Pairing> AggregatePPE<E> {
+    /// Commits to the `x` and `y` variables and proves that they satisfy every equation, with a
+    /// single proof for the [`combined`](AggregatePPE::combined) equation of the commitments.
+    pub fn commit_and_prove<CR: Rng>(
+        &self,
+        xvars: &[E::G1Affine],
+        yvars: &[E::G2Affine],
+        crs: &CRS<E>,
+        rng: &mut CR,
+    ) -> CProof<E> {
+        let xcoms: Commit1<E> = batch_commit_G1(xvars, crs, rng);
+        let ycoms: Commit2<E> = batch_commit_G2(yvars, crs, rng);
+        let equ = self.combined(&xcoms, &ycoms, crs);
+        let equ_proof = equ.prove(xvars, yvars, &xcoms, &ycoms, crs, rng);
+
+        CProof::<E> {
+            xcoms,
+            ycoms,
+            equ_proofs: vec![equ_proof],
+            crs_fingerprint: crs.fingerprint(),
+        }
+    }
+}
+
 impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG1<E> {
     fn commit_and_prove<CR>(
         &self,
@@ -189,6 +972,7 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
             xcoms: xcoms.clone(),
             ycoms: scalar_ycoms.clone(),
             equ_proofs: vec![self.prove(xvars, scalar_yvars, &xcoms, &scalar_ycoms, crs, rng)],
+            crs_fingerprint: crs.fingerprint(),
         }
     }
 
@@ -208,12 +992,15 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
         // x's commit randomness (i.e. R) is a (m x 2) matrix
         assert_eq!(xvars.len(), xcoms.rand.len());
         assert_eq!(self.gamma.len(), xcoms.rand.len());
-        assert_eq!(xcoms.rand[0].len(), 2);
+        assert!(xcoms.rand.iter().all(|row| row.len() == 2));
         let _m = xvars.len();
         // scalar y's commit randomness (i.e. s) is a (n' x 1) matrix (i.e. column vector)
         assert_eq!(scalar_yvars.len(), scalar_ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), scalar_ycoms.rand.len());
-        assert_eq!(scalar_ycoms.rand[0].len(), 1);
+        assert!(self
+            .gamma
+            .iter()
+            .all(|row| row.len() == scalar_ycoms.rand.len()));
+        assert!(scalar_ycoms.rand.iter().all(|row| row.len() == 1));
         let _n_prime = scalar_yvars.len();
 
         let is_parallel = true;
@@ -226,43 +1013,50 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
         let pf_rand: Matrix<E::ScalarField> =
             vec![vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)]];
 
-        // (2 x 1) Com2 matrix
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs))
-            .left_mul(&x_rand_trans, is_parallel);
+        // Com2 vector of length 2
+        let x_rand_lin_b =
+            x_rand_trans.mul_com_vec(&Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs));
 
         // (2 x n) field matrix
         let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (2 x 1) Com2 matrix
+        // Com2 vector of length 2
         let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs))
-                .left_mul(&x_rand_stmt, is_parallel);
+            x_rand_stmt.mul_com_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs));
 
         // (2 x 1) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&scalar_ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        // (2 x 1) Com2 matrix
-        let v1: Matrix<Com2<E>> = vec![vec![crs.v[0]]];
-        let pf_rand_stmt_com2 = v1.left_mul(&pf_rand_stmt, is_parallel);
-
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        let pf_rand_stmt = sub_pf_rand(
+            x_rand_trans
+                .right_mul(&self.gamma, is_parallel)
+                .right_mul(&scalar_ycoms.rand, is_parallel),
+            &pf_rand,
+        );
+        // Com2 vector of length 2
+        let pf_rand_stmt_com2 = pf_rand_stmt.mul_com_vec(&[crs.v1()]);
+
+        // Accumulate the Com2 vectors in place
+        let mut pi = x_rand_lin_b;
+        add_assign_vec(&mut pi, &x_rand_stmt_lin_y);
+        add_assign_vec(&mut pi, &pf_rand_stmt_com2);
         assert_eq!(pi.len(), 2);
 
-        // (1 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_linear_map(&self.a_consts))
-            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 1
+        let y_rand_lin_a = y_rand_trans.mul_com_vec(&Com1::<E>::batch_linear_map(&self.a_consts));
 
         // (1 x m) field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (1 x 1) Com1 matrix
-        let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_linear_map(xvars)).left_mul(&y_rand_stmt, is_parallel);
+        let y_rand_stmt = self
+            .gamma
+            .transposed_view()
+            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 1
+        let y_rand_stmt_lin_x = y_rand_stmt.mul_com_vec(&Com1::<E>::batch_linear_map(xvars));
 
-        // (1 x 1) Com1 matrix
-        let pf_rand_com1 = vec_to_col_vec(&crs.u).left_mul(&pf_rand, is_parallel);
+        // Com1 vector of length 1
+        let pf_rand_com1 = pf_rand.mul_com_vec(&crs.u);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        // Accumulate the Com1 vectors in place
+        let mut theta = y_rand_lin_a;
+        add_assign_vec(&mut theta, &y_rand_stmt_lin_x);
+        add_assign_vec(&mut theta, &pf_rand_com1);
         assert_eq!(theta.len(), 1);
 
         EquProof::<E> {
@@ -292,6 +1086,7 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
             xcoms: scalar_xcoms.clone(),
             ycoms: ycoms.clone(),
             equ_proofs: vec![self.prove(scalar_xvars, yvars, &scalar_xcoms, &ycoms, crs, rng)],
+            crs_fingerprint: crs.fingerprint(),
         }
     }
 
@@ -311,12 +1106,12 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
         // x's commit randomness (i.e. r) is a (m' x 1) matrix (i.e. column vector)
         assert_eq!(scalar_xvars.len(), scalar_xcoms.rand.len());
         assert_eq!(self.gamma.len(), scalar_xcoms.rand.len());
-        assert_eq!(scalar_xcoms.rand[0].len(), 1);
+        assert!(scalar_xcoms.rand.iter().all(|row| row.len() == 1));
         let _m_prime = scalar_xvars.len();
         // y's commit randomness (i.e. S) is a (n x 2) matrix
         assert_eq!(yvars.len(), ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), ycoms.rand.len());
-        assert_eq!(ycoms.rand[0].len(), 2);
+        assert!(self.gamma.iter().all(|row| row.len() == ycoms.rand.len()));
+        assert!(ycoms.rand.iter().all(|row| row.len() == 2));
         let _n = yvars.len();
 
         let is_parallel = true;
@@ -331,43 +1126,50 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
             vec![E::ScalarField::rand(rng)],
         ];
 
-        // (1 x 1) Com2 matrix
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_linear_map(&self.b_consts))
-            .left_mul(&x_rand_trans, is_parallel);
+        // Com2 vector of length 1
+        let x_rand_lin_b = x_rand_trans.mul_com_vec(&Com2::<E>::batch_linear_map(&self.b_consts));
 
         // (1 x n) field matrix
         let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (1 x 1) Com2 matrix
-        let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_linear_map(yvars)).left_mul(&x_rand_stmt, is_parallel);
+        // Com2 vector of length 1
+        let x_rand_stmt_lin_y = x_rand_stmt.mul_com_vec(&Com2::<E>::batch_linear_map(yvars));
 
         // (1 x 2) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        // (1 x 1) Com2 matrix
-        let pf_rand_stmt_com2 = vec_to_col_vec(&crs.v).left_mul(&pf_rand_stmt, is_parallel);
+        let pf_rand_stmt = sub_pf_rand(
+            x_rand_trans
+                .right_mul(&self.gamma, is_parallel)
+                .right_mul(&ycoms.rand, is_parallel),
+            &pf_rand,
+        );
+        // Com2 vector of length 1
+        let pf_rand_stmt_com2 = pf_rand_stmt.mul_com_vec(&crs.v);
 
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        // Accumulate the Com2 vectors in place
+        let mut pi = x_rand_lin_b;
+        add_assign_vec(&mut pi, &x_rand_stmt_lin_y);
+        add_assign_vec(&mut pi, &pf_rand_stmt_com2);
         assert_eq!(pi.len(), 1);
 
-        // (2 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs))
-            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 2
+        let y_rand_lin_a =
+            y_rand_trans.mul_com_vec(&Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs));
 
         // (2 x m') field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (2 x 1) Com1 matrix
+        let y_rand_stmt = self
+            .gamma
+            .transposed_view()
+            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 2
         let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs))
-                .left_mul(&y_rand_stmt, is_parallel);
+            y_rand_stmt.mul_com_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs));
 
-        // (2 x 1) Com1 matrix
-        let u1: Matrix<Com1<E>> = vec![vec![crs.u[0]]];
-        let pf_rand_com1 = u1.left_mul(&pf_rand, is_parallel);
+        // Com1 vector of length 2
+        let pf_rand_com1 = pf_rand.mul_com_vec(&[crs.u1()]);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        // Accumulate the Com1 vectors in place
+        let mut theta = y_rand_lin_a;
+        add_assign_vec(&mut theta, &y_rand_stmt_lin_x);
+        add_assign_vec(&mut theta, &pf_rand_com1);
         assert_eq!(theta.len(), 2);
 
         EquProof::<E> {
@@ -404,6 +1206,7 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::ScalarField, E::ScalarField> for
                 crs,
                 rng,
             )],
+            crs_fingerprint: crs.fingerprint(),
         }
     }
     fn prove<CR>(
@@ -422,12 +1225,15 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::ScalarField, E::ScalarField> for
         // x's commit randomness (i.e. r) is a (m' x 1) matrix (i.e. column vector)
         assert_eq!(scalar_xvars.len(), scalar_xcoms.rand.len());
         assert_eq!(self.gamma.len(), scalar_xcoms.rand.len());
-        assert_eq!(scalar_xcoms.rand[0].len(), 1);
+        assert!(scalar_xcoms.rand.iter().all(|row| row.len() == 1));
         let _m_prime = scalar_xvars.len();
         // y's commit randomness (i.e. s) is a (n' x 1) matrix (i.e. column vector)
         assert_eq!(scalar_yvars.len(), scalar_ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), scalar_ycoms.rand.len());
-        assert_eq!(scalar_ycoms.rand[0].len(), 1);
+        assert!(self
+            .gamma
+            .iter()
+            .all(|row| row.len() == scalar_ycoms.rand.len()));
+        assert!(scalar_ycoms.rand.iter().all(|row| row.len() == 1));
         let _n_prime = scalar_yvars.len();
 
         let is_parallel = true;
@@ -439,44 +1245,51 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::ScalarField, E::ScalarField> for
         // field element T, in GS parlance
         let pf_rand: Matrix<E::ScalarField> = vec![vec![E::ScalarField::rand(rng)]];
 
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs))
-            .left_mul(&x_rand_trans, is_parallel);
+        let x_rand_lin_b =
+            x_rand_trans.mul_com_vec(&Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs));
 
         // (1 x n') field matrix
         let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (1 x 1) Com2 matrix
+        // Com2 vector of length 1
         let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs))
-                .left_mul(&x_rand_stmt, is_parallel);
+            x_rand_stmt.mul_com_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs));
 
         // (1 x 2) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&scalar_ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        let v1: Matrix<Com2<E>> = vec![vec![crs.v[0]]];
-        // (1 x 1) Com2 matrix
-        let pf_rand_stmt_com2 = v1.left_mul(&pf_rand_stmt, is_parallel);
-
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        let pf_rand_stmt = sub_pf_rand(
+            x_rand_trans
+                .right_mul(&self.gamma, is_parallel)
+                .right_mul(&scalar_ycoms.rand, is_parallel),
+            &pf_rand,
+        );
+        // Com2 vector of length 1
+        let pf_rand_stmt_com2 = pf_rand_stmt.mul_com_vec(&[crs.v1()]);
+
+        // Accumulate the Com2 vectors in place
+        let mut pi = x_rand_lin_b;
+        add_assign_vec(&mut pi, &x_rand_stmt_lin_y);
+        add_assign_vec(&mut pi, &pf_rand_stmt_com2);
         assert_eq!(pi.len(), 1);
 
-        // (1 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs))
-            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 1
+        let y_rand_lin_a =
+            y_rand_trans.mul_com_vec(&Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs));
 
         // (1 x m') field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (1 x 1) Com1 matrix
+        let y_rand_stmt = self
+            .gamma
+            .transposed_view()
+            .left_mul(&y_rand_trans, is_parallel);
+        // Com1 vector of length 1
         let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs))
-                .left_mul(&y_rand_stmt, is_parallel);
+            y_rand_stmt.mul_com_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs));
 
-        // (1 x 1) Com1 matrix
-        let u1: Matrix<Com1<E>> = vec![vec![crs.u[0]]];
-        let pf_rand_com1 = u1.left_mul(&pf_rand, is_parallel);
+        // Com1 vector of length 1
+        let pf_rand_com1 = pf_rand.mul_com_vec(&[crs.u1()]);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        // Accumulate the Com1 vectors in place
+        let mut theta = y_rand_lin_a;
+        add_assign_vec(&mut theta, &y_rand_stmt_lin_x);
+        add_assign_vec(&mut theta, &pf_rand_com1);
         assert_eq!(theta.len(), 1);
 
         EquProof::<E> {
@@ -534,6 +1347,59 @@ mod tests {
         assert_eq!(proof.equ_type, EquType::PairingProduct);
     }
 
+    #[test]
+    fn test_PPE_proof_matches_reference() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // A large equation with a dense Γ
+        let (m, n) = (24, 20);
+        let xvars: Vec<G1Affine> = (0..m).map(|_| G1Affine::rand(&mut rng)).collect();
+        let yvars: Vec<G2Affine> = (0..n).map(|_| G2Affine::rand(&mut rng)).collect();
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: (0..n).map(|_| G1Affine::rand(&mut rng)).collect(),
+            b_consts: (0..m).map(|_| G2Affine::rand(&mut rng)).collect(),
+            gamma: (0..m)
+                .map(|_| (0..n).map(|_| Fr::rand(&mut rng)).collect())
+                .collect(),
+            target: GT::rand(&mut rng),
+        };
+        let proof: EquProof<F> = equ.prove(&xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+
+        // pi = R^T i_2(B) + R^T Γ i_2(Y) + (R^T Γ S - T^T) v and theta = S^T i_1(A) + S^T Γ^T i_1(X) + T u,
+        // with the transposes materialized
+        let (r_trans, s_trans) = (xcoms.rand.transpose(), ycoms.rand.transpose());
+        let (lin_a, lin_b) = batch_linear_map_both::<F>(&equ.a_consts, &equ.b_consts);
+        let (lin_x, lin_y) = batch_linear_map_both::<F>(&xvars, &yvars);
+        let r_gamma = r_trans.right_mul(&equ.gamma, false);
+        let pi: Vec<Com2<F>> = [
+            r_trans.mul_com_vec(&lin_b),
+            r_gamma.mul_com_vec(&lin_y),
+            r_gamma
+                .right_mul(&ycoms.rand, false)
+                .add(&proof.rand.transpose().neg())
+                .mul_com_vec(&crs.v),
+        ]
+        .into_iter()
+        .reduce(|acc, term| acc.iter().zip(term).map(|(lhs, rhs)| *lhs + rhs).collect())
+        .unwrap();
+        let theta: Vec<Com1<F>> = [
+            s_trans.mul_com_vec(&lin_a),
+            s_trans
+                .right_mul(&equ.gamma.transpose(), false)
+                .mul_com_vec(&lin_x),
+            proof.rand.mul_com_vec(&crs.u),
+        ]
+        .into_iter()
+        .reduce(|acc, term| acc.iter().zip(term).map(|(lhs, rhs)| *lhs + rhs).collect())
+        .unwrap();
+
+        assert_eq!(proof.pi, pi);
+        assert_eq!(proof.theta, theta);
+    }
+
     #[test]
     fn test_PPE_cproof_is_commit_and_prove() {
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
@@ -564,6 +1430,7 @@ mod tests {
             xcoms,
             ycoms,
             equ_proofs: vec![proof],
+            crs_fingerprint: crs.fingerprint(),
         };
 
         // Mock calls to CRS to get them in sync
@@ -677,6 +1544,7 @@ mod tests {
             xcoms,
             ycoms: scalar_ycoms,
             equ_proofs: vec![proof],
+            crs_fingerprint: crs.fingerprint(),
         };
 
         // Mock calls to CRS to get them in sync
@@ -788,6 +1656,7 @@ mod tests {
             xcoms: scalar_xcoms,
             ycoms,
             equ_proofs: vec![proof],
+            crs_fingerprint: crs.fingerprint(),
         };
 
         // Mock calls to CRS to get them in sync
@@ -907,6 +1776,7 @@ mod tests {
             xcoms: scalar_xcoms,
             ycoms: scalar_ycoms,
             equ_proofs: vec![proof],
+            crs_fingerprint: crs.fingerprint(),
         };
 
         // Mock calls to CRS to get them in sync
@@ -0,0 +1,163 @@
+//! [`serde`] support (behind the `serde` feature) for the commitment groups, the CRS, commitments,
+//! proofs and statements.
+//!
+//! Every type is encoded as its canonical compressed serialization (see [`CanonicalSerialize`]), i.e.
+//! as a lowercase hex string in human-readable formats (e.g. JSON) and as raw bytes in binary formats
+//! (e.g. bincode). Deserializing validates the encoding as
+//! [`deserialize_compressed`](CanonicalDeserialize::deserialize_compressed) does, and rejects trailing bytes.
+//!
+//! [`Matrix`](crate::data_structures::Matrix) is a type alias, so matrices of field elements are
+//! supported through the [`FieldMatrix`](crate::data_structures::FieldMatrix) wrapper.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress};
+use ark_std::fmt::{self, Formatter, Write};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::data_structures::{Com1, Com2, ComT, FieldMatrix};
+use crate::generator::CRS;
+use crate::prover::{CProof, Commit1, Commit2, EquProof};
+use crate::statement::{EquType, QuadEqu, MSMEG1, MSMEG2, PPE};
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let mut hex = String::with_capacity(2 * bytes.len());
+        for byte in bytes {
+            write!(hex, "{:02x}", byte).expect("writing into a String cannot fail");
+        }
+        serializer.serialize_str(&hex)
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 2 != 0 {
+            return Err(D::Error::custom("expected an even number of hex digits"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                    .ok_or_else(|| D::Error::custom("expected a string of hex digits"))
+            })
+            .collect()
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+// Accepts raw bytes, or a sequence of bytes for formats without a native byte string
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a canonically serialized byte string")
+    }
+
+    fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+fn serialize_canonical<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: CanonicalSerialize,
+    S: Serializer,
+{
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(serde::ser::Error::custom)?;
+    serialize_bytes(&bytes, serializer)
+}
+
+fn deserialize_canonical<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: CanonicalDeserialize,
+    D: Deserializer<'de>,
+{
+    let bytes = deserialize_bytes(deserializer)?;
+    let mut reader = bytes.as_slice();
+    let value = T::deserialize_compressed(&mut reader).map_err(D::Error::custom)?;
+    if !reader.is_empty() {
+        return Err(D::Error::custom(
+            "trailing bytes after the serialized value",
+        ));
+    }
+    Ok(value)
+}
+
+macro_rules! impl_serde_canonical {
+    ($( $ty:ident<$param:ident: $bound:ident> ),*) => {
+        $(
+            impl<$param: $bound> Serialize for $ty<$param> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_canonical(self, serializer)
+                }
+            }
+            impl<'de, $param: $bound> Deserialize<'de> for $ty<$param> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_canonical(deserializer)
+                }
+            }
+        )*
+    }
+}
+impl_serde_canonical!(
+    Com1<E: Pairing>,
+    Com2<E: Pairing>,
+    FieldMatrix<F: Field>,
+    CRS<E: Pairing>,
+    Commit1<E: Pairing>,
+    Commit2<E: Pairing>,
+    EquProof<E: Pairing>,
+    CProof<E: Pairing>,
+    PPE<E: Pairing>,
+    MSMEG1<E: Pairing>,
+    MSMEG2<E: Pairing>,
+    QuadEqu<E: Pairing>
+);
+
+impl Serialize for EquType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_canonical(self, serializer)
+    }
+}
+impl<'de> Deserialize<'de> for EquType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_canonical(deserializer)
+    }
+}
+
+// ComT is not CanonicalSerialize itself, but has the same encoding through to_bytes
+impl<E: Pairing> Serialize for ComT<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(Compress::Yes), serializer)
+    }
+}
+impl<'de, E: Pairing> Deserialize<'de> for ComT<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        ComT::<E>::from_bytes(&bytes, Compress::Yes).map_err(D::Error::custom)
+    }
+}
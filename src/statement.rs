@@ -5,7 +5,7 @@
 //! - `A` and `B` are vectors representing public constants in the equation,
 //! - `X` and `Y` are vectors representing private variables in the equation (introduced on prove),
 //! - `Γ` is a matrix of public [scalar](ark_ec::Pairing::Fr) constants defining how to scalar multiply
-//!     the corresponding variables being paired together,
+//!   the corresponding variables being paired together,
 //! - `t` is a public constant representing the RHS of the equation, and
 //! - `*` is the specified pairing, applied entry-wise to the corresponding elements in each vector.
 //!
@@ -13,13 +13,13 @@
 //! and must be one of the following four types, each defined over a bilinear group:
 //!
 //! 1) **Pairing-product equation** ([`PPE`](self::PPE)):&emsp;&emsp;&emsp;&emsp;&emsp;&emsp; `(G1, G2, GT)` with
-//!     [`e`](ark_ec::Pairing::pairing)` : G1 x G2 -> GT` as the equipped pairing.
+//!    [`e`](ark_ec::Pairing::pairing)` : G1 x G2 -> GT` as the equipped pairing.
 //! 2) **Multi-scalar mult. equation in G1** ([`MSMEG1`](self::MSMEG1)):&emsp;`(G1, Fr, G1)`
-//!     with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
+//!    with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
 //! 3) **Multi-scalar mult. equation in G2** ([`MSMEG2`](self::MSMEG2)):&emsp;`(Fr, G2, G2)`
-//!     with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
+//!    with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
 //! 4) **Quadratic equation** ([`QuadEqu`](self::QuadEqu)):&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&ensp;`(Fr, Fr, Fr)`
-//!     with [scalar](ark_ec::Pairing::Fr) multiplication as the equipped pairing.
+//!    with [scalar](ark_ec::Pairing::Fr) multiplication as the equipped pairing.
 //!
 //! The Groth-Sahai proof system expects that **each** equation is defined with respect to the list of variables
 //! that span across **ALL** equations being proven about. For example, if one wishes to prove
@@ -27,16 +27,42 @@
 //! `n` `Y` variables in `G2`, and `m'` `x` variables in `Fr`, then the PPE equation would need
 //! `Γ` to be a `m` by `n` matrix and the MSMEG2 equations would need `Γ` to be `m'` by `n` matrices.
 //!
+//! # Product groups
+//!
+//! There is no commitment to an element of a product group such as `G1 x G1` as such: a variable
+//! `P = (P_1, P_2)` is committed as the two consecutive `X` variables `P_1` and `P_2` (i.e. the
+//! concatenation of its components), e.g. with [`batch_commit_G1`](crate::prover::batch_commit_G1).
+//! A term `e(P, B)` for a constant `B = (B_1, B_2)` in `G2 x G2` (paired component-wise) becomes
+//! `e(P_1, B_1) e(P_2, B_2)`, i.e. the constants `B_1` and `B_2` at the positions of `P_1` and `P_2`,
+//! and a relation between the components, e.g. `P_2 = k P_1`, is an equation over both positions,
+//! here the [`MSMEG1`](self::MSMEG1) with `B = [-k, 1]`. Both components are hidden alike, and the
+//! commitments to `P` are the consecutive entries of [`Commit1::coms`](crate::prover::Commit1::coms).
+//!
 //! **NOTE**: The bilinear equation may need to be re-arranged using the properties
 //! of bilinear group arithmetic and pairings in order to form a valid Groth-Sahai statement.
 //! This API does not provide such functionality.
 
 use ark_ec::pairing::{Pairing, PairingOutput};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
-
-use crate::data_structures::Matrix;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+use crate::data_structures::{
+    deserialize_matrix_of_shape, deserialize_vec, deserialize_versioned, serialize_matrix,
+    serialize_vec, serialize_version, serialized_matrix_size, serialized_vec_size, Matrix,
+    MatrixExt, MAX_SERIALIZED_LEN, VERSION_HEADER_SIZE,
+};
+use crate::generator::CRS;
 use crate::prover::Provable;
 use crate::verifier::Verifiable;
+// The Fiat-Shamir coefficients of `AggregatePPE`
+#[cfg(feature = "prf")]
+use crate::prover::{prove::PrfRng, Commit1, Commit2};
+#[cfg(feature = "prf")]
+use ark_std::UniformRand;
 
 /// Groth-Sahai statement (i.e. bilinear equation) types.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -114,7 +140,7 @@ pub type Statement = Vec<dyn Equ>;
 /// For example, the equation `e(W, N) * e(U, V)^5 = t_T` can be expressed by the following
 /// (private) witness variables `X = [U, W]`, `Y = [V]`, (public) constants `A = [0]`, `B = [0, N]`,
 /// pairing exponent matrix `Γ = [[5], [0]]`, and `target = t_T` in `GT`.
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PPE<E: Pairing> {
     pub a_consts: Vec<E::G1Affine>,
     pub b_consts: Vec<E::G2Affine>,
@@ -122,6 +148,72 @@ pub struct PPE<E: Pairing> {
     pub target: PairingOutput<E>,
 }
 
+impl<E: Pairing> PPE<E> {
+    /// Whether the equation is linear, i.e. `Γ` is zero so that no two variables are paired together.
+    pub fn is_linear(&self) -> bool {
+        self.gamma.iter().flatten().all(|gamma| gamma.is_zero())
+    }
+
+    /// The equivalent equation in which the constant `B_i` is a new, last `Y` variable instead, e.g. for a
+    /// constant that is committed to when proving and only revealed at verification time.
+    ///
+    /// The term `e(X_i, B_i)` becomes `e(X_i, Y_{n+1})` through a new column of `Γ`, and `B_i` is replaced
+    /// by zero, so the equation no longer contains the constant. Its proof is made for the variables
+    /// `Y` followed by `B_i`; once `B_i` is revealed, the verifier checks that the last commitment in `Y`
+    /// opens to it, e.g. with [`verify_opening_G2`](crate::prover::verify_opening_G2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no `i`-th `X` variable.
+    pub fn with_committed_b_const(&self, i: usize) -> PPE<E> {
+        let m = self.b_consts.len();
+        assert!(i < m, "there is no X variable {} of {}", i, m);
+
+        let mut equ = self.clone();
+        equ.b_consts[i] = E::G2Affine::zero();
+        equ.a_consts.push(E::G1Affine::zero());
+        for (k, row) in equ.gamma.iter_mut().enumerate() {
+            row.push(if k == i {
+                E::ScalarField::one()
+            } else {
+                E::ScalarField::zero()
+            });
+        }
+        equ
+    }
+
+    /// The equivalent equation in which the constant `A_j` is a new, last `X` variable instead, as for
+    /// [`with_committed_b_const`](PPE::with_committed_b_const).
+    ///
+    /// The term `e(A_j, Y_j)` becomes `e(X_{m+1}, Y_j)` through a new row of `Γ`. Once `A_j` is revealed,
+    /// the verifier checks that the last commitment in `X` opens to it, e.g. with
+    /// [`verify_opening_G1`](crate::prover::verify_opening_G1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no `j`-th `Y` variable.
+    pub fn with_committed_a_const(&self, j: usize) -> PPE<E> {
+        let n = self.a_consts.len();
+        assert!(j < n, "there is no Y variable {} of {}", j, n);
+
+        let mut equ = self.clone();
+        equ.a_consts[j] = E::G1Affine::zero();
+        equ.b_consts.push(E::G2Affine::zero());
+        equ.gamma.push(
+            (0..n)
+                .map(|k| {
+                    if k == j {
+                        E::ScalarField::one()
+                    } else {
+                        E::ScalarField::zero()
+                    }
+                })
+                .collect(),
+        );
+        equ
+    }
+}
+
 impl<E: Pairing> Equ for PPE<E> {}
 impl<E: Pairing> Equation<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE<E> {
     #[inline(always)]
@@ -130,12 +222,131 @@ impl<E: Pairing> Equation<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
     }
 }
 
+/// Pairing-product equations over the same variables, proven with a single proof for their random
+/// linear combination `∏_k (A_k * Y)^{c_k} (X * B_k)^{c_k} (X * Γ_k Y)^{c_k} = ∏_k t_k^{c_k}`.
+///
+/// The combination holds whenever every equation does. Conversely, if some equation does not hold then
+/// the combination holds only with probability `1/|Fr|` over coefficients chosen independently of the
+/// variables, so the coefficients must be fixed *after* the variables are committed. The prover and
+/// verifier of this type therefore derive them from the equations, the commitments and the CRS
+/// with [`coefficients`](AggregatePPE::coefficients), in the manner of Fiat-Shamir.
+///
+/// [`aggregate`](AggregatePPE::aggregate) is available in every build; deriving the coefficients,
+/// and hence proving and verifying, requires the `prf` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregatePPE<E: Pairing> {
+    pub equs: Vec<PPE<E>>,
+}
+
+impl<E: Pairing> AggregatePPE<E> {
+    pub fn new(equs: Vec<PPE<E>>) -> Self {
+        Self { equs }
+    }
+
+    /// The linear combination of `equs` with the corresponding `coeffs`, as a single equation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `equs` is empty, if there is not exactly one coefficient per equation, or if the
+    /// equations are not all over the same number of `X` and `Y` variables.
+    pub fn aggregate(equs: &[PPE<E>], coeffs: &[E::ScalarField]) -> PPE<E> {
+        assert!(
+            !equs.is_empty(),
+            "cannot aggregate an empty list of equations"
+        );
+        assert_eq!(
+            equs.len(),
+            coeffs.len(),
+            "expected exactly one coefficient per equation"
+        );
+        let (m, n) = (equs[0].b_consts.len(), equs[0].a_consts.len());
+        for equ in equs {
+            assert!(
+                equ.b_consts.len() == m
+                    && equ.a_consts.len() == n
+                    && equ.gamma.len() == m
+                    && equ.gamma.iter().all(|row| row.len() == n),
+                "every aggregated equation must be over {} X and {} Y variables",
+                m,
+                n
+            );
+        }
+
+        let a_consts: Vec<E::G1> = (0..n)
+            .map(|j| {
+                equs.iter()
+                    .zip(coeffs)
+                    .map(|(equ, coeff)| equ.a_consts[j] * coeff)
+                    .sum()
+            })
+            .collect();
+        let b_consts: Vec<E::G2> = (0..m)
+            .map(|i| {
+                equs.iter()
+                    .zip(coeffs)
+                    .map(|(equ, coeff)| equ.b_consts[i] * coeff)
+                    .sum()
+            })
+            .collect();
+        let gamma: Matrix<E::ScalarField> = MatrixExt::from_fn((m, n), |i, j| {
+            equs.iter()
+                .zip(coeffs)
+                .map(|(equ, coeff)| equ.gamma[i][j] * coeff)
+                .sum()
+        });
+        PPE::<E> {
+            a_consts: E::G1::normalize_batch(&a_consts),
+            b_consts: E::G2::normalize_batch(&b_consts),
+            gamma,
+            target: equs
+                .iter()
+                .zip(coeffs)
+                .map(|(equ, coeff)| equ.target * coeff)
+                .sum(),
+        }
+    }
+}
+
+#[cfg(feature = "prf")]
+impl<E: Pairing> AggregatePPE<E> {
+    /// The coefficients of the combination, derived by hashing the equations together with the
+    /// commitments to the variables and the CRS.
+    pub fn coefficients(
+        &self,
+        xcoms: &Commit1<E>,
+        ycoms: &Commit2<E>,
+        crs: &CRS<E>,
+    ) -> Vec<E::ScalarField> {
+        let mut transcript = Vec::new();
+        self.equs
+            .serialize_compressed(&mut transcript)
+            .and_then(|_| xcoms.coms.serialize_compressed(&mut transcript))
+            .and_then(|_| ycoms.coms.serialize_compressed(&mut transcript))
+            .and_then(|_| crs.serialize_compressed(&mut transcript))
+            .expect("serializing into a Vec cannot fail");
+        let mut rng = PrfRng::new(
+            &[],
+            b"groth-sahai PPE aggregation coefficients",
+            &transcript,
+        );
+        (0..self.equs.len())
+            .map(|_| E::ScalarField::rand(&mut rng))
+            .collect()
+    }
+
+    /// The combined equation for the given commitments, i.e. [`aggregate`](AggregatePPE::aggregate)
+    /// with the [`coefficients`](AggregatePPE::coefficients) for them.
+    pub fn combined(&self, xcoms: &Commit1<E>, ycoms: &Commit2<E>, crs: &CRS<E>) -> PPE<E> {
+        Self::aggregate(&self.equs, &self.coefficients(xcoms, ycoms, crs))
+    }
+}
+
 /// A multi-scalar multiplication equation in [`G1`](ark_ec::Pairing::G1Affine), equipped with point-scalar multiplication as pairing.
 ///
 /// For example, the equation `n * W + (v * U)^5 = t_1` can be expressed by the following
 /// (private) witness variables `X = [U, W]`, `Y = [v]`, (public) constants `A = [0]`, `B = [0, n]`,
 /// pairing exponent matrix `Γ = [[5], [0]]`, and `target = t_1` in `G1`.
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MSMEG1<E: Pairing> {
     pub a_consts: Vec<E::G1Affine>,
     pub b_consts: Vec<E::ScalarField>,
@@ -143,6 +354,24 @@ pub struct MSMEG1<E: Pairing> {
     pub target: E::G1Affine,
 }
 
+impl<E: Pairing> MSMEG1<E> {
+    /// Lifts this equation into an equivalent pairing-product equation by pairing both sides with the
+    /// CRS generator `g2`, i.e. `e(A, y g2) * e(X, b g2) * e(X, Γ (y g2)) = e(t, g2)`.
+    ///
+    /// The scalar variables `y` become the `G2` variables `Y = y g2` of the lifted equation, so
+    /// its proof is made for `X` and `Y` rather than `X` and `y`.
+    pub fn to_ppe(&self, crs: &CRS<E>) -> PPE<E> {
+        let g2_gen = crs.g2_gen.into_group();
+        let b_consts: Vec<E::G2> = self.b_consts.iter().map(|b| g2_gen * b).collect();
+        PPE::<E> {
+            a_consts: self.a_consts.clone(),
+            b_consts: E::G2::normalize_batch(&b_consts),
+            gamma: self.gamma.clone(),
+            target: E::pairing(self.target, crs.g2_gen),
+        }
+    }
+}
+
 impl<E: Pairing> Equ for MSMEG1<E> {}
 impl<E: Pairing> Equation<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG1<E> {
     #[inline(always)]
@@ -156,7 +385,7 @@ impl<E: Pairing> Equation<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
 /// For example, the equation `w * N + (u * V)^5 = t_2` can be expressed by the following
 /// (private) witness variables `X = [u, w]`, `Y = [V]`, (public) constants `A = [0]`, `B = [0, N]`,
 /// pairing exponent matrix `Γ = [[5], [0]]`, and `target = t_2` in `G2`.
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MSMEG2<E: Pairing> {
     pub a_consts: Vec<E::ScalarField>,
     pub b_consts: Vec<E::G2Affine>,
@@ -176,13 +405,39 @@ impl<E: Pairing> Equation<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
 /// For example, the equation `w * n + (u * v)^5 = t_p` can be expressed by the following
 /// (private) witness variables `X = [u, w]`, `Y = [v]`, (public) constants `A = [0]`, `B = [0, n]`,
 /// pairing exponent matrix `Γ = [[5], [0]]`, and `target = t_p` in `Fr`.
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QuadEqu<E: Pairing> {
     pub a_consts: Vec<E::ScalarField>,
     pub b_consts: Vec<E::ScalarField>,
     pub gamma: Matrix<E::ScalarField>,
     pub target: E::ScalarField,
 }
+
+impl<E: Pairing> QuadEqu<E> {
+    /// The equation `x_1 - y_1 = 0`, stating that the scalar committed in [`B1`](crate::data_structures::B1)
+    /// equals the scalar committed in [`B2`](crate::data_structures::B2), e.g. to wire together the
+    /// scalar variables of different equations.
+    pub fn scalar_equality() -> Self {
+        QuadEqu::<E> {
+            a_consts: vec![-E::ScalarField::one()],
+            b_consts: vec![E::ScalarField::one()],
+            gamma: vec![vec![E::ScalarField::zero()]],
+            target: E::ScalarField::zero(),
+        }
+    }
+
+    /// The equation `x_1 y_1 - x_1 = 0`, stating that `x_1 = 0` or `y_1 = 1`, i.e. that a scalar is a bit
+    /// when it is committed as both `x_1` and `y_1` (see [`scalar_equality`](QuadEqu::scalar_equality)).
+    pub fn bit() -> Self {
+        QuadEqu::<E> {
+            a_consts: vec![E::ScalarField::zero()],
+            b_consts: vec![-E::ScalarField::one()],
+            gamma: vec![vec![E::ScalarField::one()]],
+            target: E::ScalarField::zero(),
+        }
+    }
+}
+
 impl<E: Pairing> Equ for QuadEqu<E> {}
 impl<E: Pairing> Equation<E, E::ScalarField, E::ScalarField, E::ScalarField> for QuadEqu<E> {
     #[inline(always)]
@@ -191,6 +446,75 @@ impl<E: Pairing> Equation<E, E::ScalarField, E::ScalarField, E::ScalarField> for
     }
 }
 
+// The equations serialize with a leading version header (see `FORMAT_VERSION`), and their `Γ` must be
+// `m x n` for the `m` constants `B` and `n` constants `A` (or empty) when deserialized
+macro_rules! impl_equ_serde {
+    ($( $equ:ident ),*) => {
+        $(
+            impl<E: Pairing> CanonicalSerialize for $equ<E> {
+                fn serialize_with_mode<W: Write>(
+                    &self,
+                    mut writer: W,
+                    compress: Compress,
+                ) -> Result<(), SerializationError> {
+                    serialize_version(&mut writer)?;
+                    serialize_vec(&self.a_consts, &mut writer, compress)?;
+                    serialize_vec(&self.b_consts, &mut writer, compress)?;
+                    serialize_matrix(&self.gamma, &mut writer, compress)?;
+                    self.target.serialize_with_mode(&mut writer, compress)
+                }
+
+                fn serialized_size(&self, compress: Compress) -> usize {
+                    VERSION_HEADER_SIZE
+                        + serialized_vec_size(&self.a_consts, compress)
+                        + serialized_vec_size(&self.b_consts, compress)
+                        + serialized_matrix_size(&self.gamma, compress)
+                        + self.target.serialized_size(compress)
+                }
+            }
+
+            impl<E: Pairing> Valid for $equ<E> {
+                fn check(&self) -> Result<(), SerializationError> {
+                    self.a_consts.check()?;
+                    self.b_consts.check()?;
+                    self.gamma.check()?;
+                    self.target.check()
+                }
+            }
+
+            impl<E: Pairing> CanonicalDeserialize for $equ<E> {
+                fn deserialize_with_mode<R: Read>(
+                    reader: R,
+                    compress: Compress,
+                    validate: Validate,
+                ) -> Result<Self, SerializationError> {
+                    deserialize_versioned(reader, |mut reader, version| {
+                        let a_consts: Vec<_> =
+                            deserialize_vec(&mut reader, version, compress, validate, MAX_SERIALIZED_LEN)?;
+                        let b_consts: Vec<_> =
+                            deserialize_vec(&mut reader, version, compress, validate, MAX_SERIALIZED_LEN)?;
+                        let gamma = deserialize_matrix_of_shape(
+                            &mut reader,
+                            version,
+                            compress,
+                            validate,
+                            b_consts.len(),
+                            &[a_consts.len()],
+                        )?;
+                        Ok(Self {
+                            a_consts,
+                            b_consts,
+                            gamma,
+                            target: CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                        })
+                    })
+                }
+            }
+        )*
+    }
+}
+impl_equ_serde!(PPE, MSMEG1, MSMEG2, QuadEqu);
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
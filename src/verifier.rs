@@ -5,154 +5,861 @@
 //!
 //! See the [`prover`](crate::prover) and [`statement`](crate::statement) modules for more details about the structure of the equations and their proofs.
 
-use ark_ec::pairing::Pairing;
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ff::Zero;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::borrow::Cow;
+use ark_std::fmt::{self, Display, Formatter};
 
 use crate::data_structures::{
-    col_vec_to_vec, vec_to_col_vec, Com1, Com2, ComT, Mat, Matrix, B1, B2, BT,
+    batch_linear_map_both, deserialize_versioned, serialize_version, vec_to_col_vec, Com1,
+    Com1Projective, Com2, Com2Prepared, ComMillerAccumulator, ComT, Mat, Matrix, PairingSumError,
+    ShapeError, B1, B2, BT, VERSION_HEADER_SIZE,
 };
+use crate::error::Error;
 use crate::generator::CRS;
-use crate::prover::CProof;
-use crate::statement::{Equation, QuadEqu, MSMEG1, MSMEG2, PPE};
+use crate::prover::{CProof, Commit1, Commit2, EquProof, GtExponentProof};
+#[cfg(feature = "prf")]
+use crate::statement::AggregatePPE;
+use crate::statement::{EquType, Equation, QuadEqu, MSMEG1, MSMEG2, PPE};
+
+/// The reasons a proof may be malformed with respect to the [`Equation`](crate::statement::Equation) it is verified against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The proof does not contain exactly one equation proof.
+    ProofCount(usize),
+    /// The equation proof was produced for a different type of equation.
+    EquTypeMismatch { expected: EquType, found: EquType },
+    /// The pairing exponent matrix `Γ` does not match the number of committed `y` variables.
+    GammaDimension,
+    /// Two vectors that are paired together have different lengths.
+    PairingSum(PairingSumError),
+    /// `π` or `θ` has the wrong number of elements for the equation, see
+    /// [`EquProof::check_shape`](crate::prover::EquProof::check_shape).
+    ProofShape(ShapeError),
+    /// The variables were committed under a different CRS than the one the proof is verified against.
+    ///
+    /// This only catches accidental mismatches, as the [`fingerprint`](crate::generator::CRS::fingerprint)
+    /// is not collision-resistant.
+    CrsMismatch,
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::ProofCount(count) => {
+                write!(f, "expected exactly 1 equation proof, found {}", count)
+            }
+            VerificationError::EquTypeMismatch { expected, found } => write!(
+                f,
+                "expected a proof for {:?} equation, found {:?}",
+                expected, found
+            ),
+            VerificationError::GammaDimension => {
+                write!(f, "gamma does not match the number of committed variables")
+            }
+            VerificationError::PairingSum(err) => write!(f, "{}", err),
+            VerificationError::ProofShape(err) => write!(f, "pi or theta: {}", err),
+            VerificationError::CrsMismatch => {
+                write!(f, "the proof was not committed under the given CRS")
+            }
+        }
+    }
+}
+
+impl ark_std::error::Error for VerificationError {}
+
+impl From<ShapeError> for VerificationError {
+    fn from(err: ShapeError) -> Self {
+        VerificationError::ProofShape(err)
+    }
+}
+
+impl From<PairingSumError> for VerificationError {
+    fn from(err: PairingSumError) -> Self {
+        VerificationError::PairingSum(err)
+    }
+}
+
+/// How strictly [`verify_with_options`](Verifiable::verify_with_options) verifies a proof, with every
+/// option disabled by default, i.e. verifying as [`verify`](Verifiable::verify) does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Compare the sides of the verification equation in constant time, as `verify_ct` does with the
+    /// `subtle` feature.
+    ///
+    /// Without the `subtle` feature there is no constant-time comparison, so every proof is rejected
+    /// with this option rather than silently compared in variable time.
+    pub constant_time: bool,
+    /// Reject the proof if any of its group elements is not in the prime-order subgroup (or not on the
+    /// curve), e.g. for a proof built from untrusted points rather than deserialized with validation,
+    /// which already checks them.
+    pub check_subgroup: bool,
+    /// Reject the proof if all commitments to the `x` (or to the `y`) variables are zero, see
+    /// [`Commit1::is_degenerate`]. This is a policy that also rejects valid proofs about zero openings,
+    /// e.g. of variables that are all public zeros.
+    pub reject_degenerate: bool,
+}
+
+/// A self-describing wire object holding everything a remote verifier needs: the equation, the
+/// commitments to its variables and the proof.
+///
+/// The bundle never holds the prover's commitment or proof randomness (see
+/// [`CProof::without_randomness`](crate::prover::CProof::without_randomness)), so it is safe to publish.
+///
+/// Its serialization starts with a [format version](crate::data_structures::FORMAT_VERSION) header, and
+/// deserializing a bundle of an unknown version fails with an
+/// [`UnsupportedVersion`](crate::data_structures::UnsupportedVersion) error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofBundle<E: Pairing, S: CanonicalSerialize + CanonicalDeserialize> {
+    pub statement: S,
+    pub proof: CProof<E>,
+}
+
+impl<E, S> CanonicalSerialize for ProofBundle<E, S>
+where
+    E: Pairing,
+    S: CanonicalSerialize + CanonicalDeserialize,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_version(&mut writer)?;
+        self.statement.serialize_with_mode(&mut writer, compress)?;
+        self.proof.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        VERSION_HEADER_SIZE
+            + self.statement.serialized_size(compress)
+            + self.proof.serialized_size(compress)
+    }
+}
+
+impl<E, S> Valid for ProofBundle<E, S>
+where
+    E: Pairing,
+    S: CanonicalSerialize + CanonicalDeserialize,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.statement.check()?;
+        self.proof.check()
+    }
+}
+
+impl<E, S> CanonicalDeserialize for ProofBundle<E, S>
+where
+    E: Pairing,
+    S: CanonicalSerialize + CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        // The statement and proof read their own vectors in the version they were written in
+        deserialize_versioned(reader, |mut reader, _| {
+            Ok(ProofBundle::<E, S> {
+                statement: S::deserialize_with_mode(&mut reader, compress, validate)?,
+                proof: CProof::deserialize_with_mode(&mut reader, compress, validate)?,
+            })
+        })
+    }
+}
+
+impl<E, S> ProofBundle<E, S>
+where
+    E: Pairing,
+    S: Verifiable<E> + CanonicalSerialize + CanonicalDeserialize,
+{
+    pub fn new(statement: S, proof: &CProof<E>) -> Self {
+        Self {
+            statement,
+            proof: proof.without_randomness(),
+        }
+    }
+
+    /// Verifies the bundled proof against the bundled equation.
+    pub fn verify(&self, crs: &CRS<E>) -> bool {
+        self.statement.verify(&self.proof, crs)
+    }
+}
+
+/// Verifies a proof from [`prove_scalar_equality`](crate::prover::prove_scalar_equality) that the scalar
+/// committed in `com1` equals the scalar committed in `com2`.
+pub fn verify_scalar_equality<E: Pairing>(
+    com1: &Commit1<E>,
+    com2: &Commit2<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    scalar_equality_holds(com1, com2, proof, crs, crs.fingerprint())
+}
+
+// Verifies a scalar equality proof like `verify_scalar_equality`, for a verifier that has already
+// computed the fingerprint of `crs`
+pub(crate) fn scalar_equality_holds<E: Pairing>(
+    com1: &Commit1<E>,
+    com2: &Commit2<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+    crs_fingerprint: u64,
+) -> bool {
+    let com_proof = CProof::<E> {
+        xcoms: com1.clone(),
+        ycoms: com2.clone(),
+        equ_proofs: vec![proof.clone()],
+        crs_fingerprint,
+    };
+    QuadEqu::<E>::scalar_equality().verify_assembled(&com_proof, crs)
+}
+
+/// Verifies a proof from [`prove_gt_exponent`](crate::prover::prove_gt_exponent) that `e(g, h)^z = t`
+/// in `GT`, for the scalar `z` committed in `z_com`.
+pub fn verify_gt_exponent<E: Pairing>(
+    g: &E::G1Affine,
+    h: &E::G2Affine,
+    target: &PairingOutput<E>,
+    z_com: &Commit2<E>,
+    proof: &GtExponentProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    let (msm, ppe) = GtExponentProof::<E>::equations(g, h, target);
+    let crs_fingerprint = crs.fingerprint();
+    let xcoms = Commit1::<E>::from_coms(vec![proof.xcom]);
+    let msm_proof = CProof::<E> {
+        xcoms: xcoms.clone(),
+        ycoms: Commit2::<E>::from_coms(z_com.coms.clone()),
+        equ_proofs: vec![proof.msm_proof.clone()],
+        crs_fingerprint,
+    };
+    let ppe_proof = CProof::<E> {
+        xcoms,
+        ycoms: Commit2::<E>::from_coms(vec![]),
+        equ_proofs: vec![proof.ppe_proof.clone()],
+        crs_fingerprint,
+    };
+    msm.verify_assembled(&msm_proof, crs) && ppe.verify_assembled(&ppe_proof, crs)
+}
 
 /// A collection of attributes containing verifier functionality for an [`Equation`](crate::statement::Equation).
 pub trait Verifiable<E: Pairing> {
     /// Verifies that a single Groth-Sahai equation is satisfied using the prover's committed `x` and `y` variables.
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool;
+    ///
+    /// A malformed proof (see [`verify_detailed`](Verifiable::verify_detailed)) is rejected.
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
+        self.verify_detailed(com_proof, crs).unwrap_or(false)
+    }
+
+    /// Verifies that a single Groth-Sahai equation is satisfied using the prover's committed `x` and `y` variables,
+    /// reporting a malformed proof as an error instead of panicking.
+    fn verify_detailed(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<bool, VerificationError> {
+        let (lhs, rhs) = self.verification_terms(com_proof, crs)?;
+        Ok(lhs == rhs)
+    }
+
+    /// Verifies the proof like [`verify_detailed`](Verifiable::verify_detailed), but as a crate-wide
+    /// [`Error`](crate::Error), with [`VerificationFailed`](crate::Error::VerificationFailed) for a
+    /// well-formed proof that does not verify, e.g. to propagate any failure with `?`.
+    fn try_verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), Error> {
+        if self.verify_detailed(com_proof, crs)? {
+            Ok(())
+        } else {
+            Err(Error::VerificationFailed)
+        }
+    }
+
+    /// Verifies the proof like [`verify`](Verifiable::verify), with the additional checks enabled in
+    /// `options`. The [default](VerifyOptions::default) options verify exactly like `verify`.
+    fn verify_with_options(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+        options: &VerifyOptions,
+    ) -> bool {
+        if options.reject_degenerate
+            && (com_proof.xcoms.is_degenerate() || com_proof.ycoms.is_degenerate())
+        {
+            return false;
+        }
+        if options.check_subgroup && com_proof.check().is_err() {
+            return false;
+        }
+        if options.constant_time {
+            #[cfg(feature = "subtle")]
+            return self.verify_ct(com_proof, crs).into();
+            #[cfg(not(feature = "subtle"))]
+            return false;
+        }
+        self.verify(com_proof, crs)
+    }
+
+    /// Verifies the proof like [`verify`](Verifiable::verify), but compares all four `GT` components of
+    /// the verification equation without short-circuiting, returning the result as a [`subtle::Choice`].
+    ///
+    /// The running time does not depend on which component (if any) differs. It still depends on the
+    /// shape of the proof, which is public, so a malformed proof is rejected early.
+    #[cfg(feature = "subtle")]
+    fn verify_ct(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> subtle::Choice {
+        use subtle::ConstantTimeEq;
+
+        match self.verification_terms(com_proof, crs) {
+            Ok((lhs, rhs)) => lhs.ct_eq(&rhs),
+            Err(_) => subtle::Choice::from(0),
+        }
+    }
+
+    /// The two sides `(lhs, rhs)` in [`BT`](crate::data_structures::ComT) of the verification equation,
+    /// which are equal if and only if the proof is valid, or an error if the proof is malformed.
+    fn verification_terms(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError>;
+}
+
+// Checks that the variables of the proof were committed under `crs`.
+fn check_crs<E: Pairing>(com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError> {
+    if com_proof.crs_fingerprint != crs.fingerprint() {
+        return Err(VerificationError::CrsMismatch);
+    }
+    Ok(())
 }
 
+// Checks the parts of the proof that are assumed by every equation type before any pairing is computed.
+fn check_proof_shape<E: Pairing>(
+    equ_type: EquType,
+    gamma: &Matrix<E::ScalarField>,
+    com_proof: &CProof<E>,
+) -> Result<(), VerificationError> {
+    if com_proof.equ_proofs.len() != 1 {
+        return Err(VerificationError::ProofCount(com_proof.equ_proofs.len()));
+    }
+    if equ_type != com_proof.equ_proofs[0].equ_type {
+        return Err(VerificationError::EquTypeMismatch {
+            expected: equ_type,
+            found: com_proof.equ_proofs[0].equ_type.clone(),
+        });
+    }
+    // Every row of gamma is multiplied with the column vector of y commitments
+    let n = com_proof.ycoms.coms.len();
+    if !gamma.is_empty() && !gamma[0].is_empty() && n != 0 && gamma.iter().any(|row| row.len() != n)
+    {
+        return Err(VerificationError::GammaDimension);
+    }
+    Ok(())
+}
+
+// Computes `Γ d` for the column vector `d` of `y` commitments, moving the commitments into the column
+// vector when they are owned and collapsing the product without cloning it
+fn gamma_ycoms<E: Pairing>(
+    gamma: &Matrix<E::ScalarField>,
+    ycoms: Cow<'_, [Com2<E>]>,
+    is_parallel: bool,
+) -> Vec<Com2<E>> {
+    // Without y variables, an m x 0 matrix Γ maps d to the m x 1 zero vector
+    if ycoms.is_empty() && gamma.iter().all(|row| row.is_empty()) {
+        return vec![Com2::<E>::zero(); gamma.len()];
+    }
+    let ycol: Matrix<Com2<E>> = match ycoms {
+        Cow::Borrowed(ycoms) => vec_to_col_vec(ycoms),
+        Cow::Owned(ycoms) => ycoms.into_iter().map(|com| vec![com]).collect(),
+    };
+    // Γ d is an m x 1 column vector (or empty), so its rows flatten into the vector of its entries
+    ycol.left_mul(gamma, is_parallel)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// An [`EquProof`](crate::prover::EquProof) with its [`B2`](crate::data_structures::Com2) elements, as
+/// well as the CRS key `v` that `θ` is paired with, prepared for pairing.
+///
+/// Verifying with a prepared proof (see e.g. [`PPE::verify_prepared`]) skips preparing `π` and `v`,
+/// which dominates the cost of pairing on curves with expensive `G2` preparation. The prepared proof
+/// is tied to the CRS it was prepared with.
+#[derive(Clone, Debug)]
+pub struct PreparedProof<E: Pairing> {
+    pi: Vec<Com2Prepared<E>>,
+    theta: Vec<Com1<E>>,
+    v: Vec<Com2Prepared<E>>,
+    equ_type: EquType,
+    crs_fingerprint: u64,
+}
+
+impl<E: Pairing> EquProof<E> {
+    /// Prepares `π` and the CRS key `v` for pairing once, to verify this proof repeatedly with
+    /// `verify_prepared` (e.g. [`PPE::verify_prepared`]).
+    pub fn prepare_g2(&self, crs: &CRS<E>) -> PreparedProof<E> {
+        PreparedProof {
+            pi: self.pi.iter().map(|com| (*com).into()).collect(),
+            theta: self.theta.clone(),
+            v: crs.v.iter().map(|com| (*com).into()).collect(),
+            equ_type: self.equ_type.clone(),
+            crs_fingerprint: crs.fingerprint(),
+        }
+    }
+}
+
+// The equation proof in the verification equation, either as is or prepared
+#[derive(Clone, Copy)]
+enum ProofTerms<'a, E: Pairing> {
+    Plain(&'a EquProof<E>),
+    Prepared(&'a PreparedProof<E>),
+}
+
+impl<E: Pairing> ProofTerms<'_, E> {
+    fn pi_len(&self) -> usize {
+        match self {
+            ProofTerms::Plain(proof) => proof.pi.len(),
+            ProofTerms::Prepared(proof) => proof.pi.len(),
+        }
+    }
+
+    fn theta_len(&self) -> usize {
+        match self {
+            ProofTerms::Plain(proof) => proof.theta.len(),
+            ProofTerms::Prepared(proof) => proof.theta.len(),
+        }
+    }
+
+    // Subtracts the pairings of the first `u_len` keys of `u` with `π` and of `θ` with the first `v_len`
+    // keys of `v`, or returns an error if the proof does not have as many elements
+    fn sub_from(
+        self,
+        acc: &mut ComMillerAccumulator<E>,
+        crs: &CRS<E>,
+        u_len: usize,
+        v_len: usize,
+    ) -> Result<(), PairingSumError> {
+        match self {
+            ProofTerms::Plain(proof) => {
+                acc.sub_com_sum(&crs.u[..u_len], &proof.pi)?;
+                acc.sub_com_sum(&proof.theta, &crs.v[..v_len])
+            }
+            ProofTerms::Prepared(proof) => {
+                acc.sub_com_prepared_sum(&crs.u[..u_len], &proof.pi)?;
+                acc.sub_com_prepared_sum(&proof.theta, &proof.v[..v_len])
+            }
+        }
+    }
+}
+
+macro_rules! impl_verify_variants {
+    ($($equ:ident),*) => {
+        $(
+            impl<E: Pairing> $equ<E> {
+                /// Verifies the proof like [`verify`](Verifiable::verify), but takes ownership of the equation and of
+                /// the proof (including its `x` and `y` commitments).
+                ///
+                /// The `y` commitments are moved into the column vector multiplied with `Γ` instead of being cloned,
+                /// which saves one copy of every [`Com2`](crate::data_structures::Com2) in the proof. It does not
+                /// save allocations: the column vector is allocated either way, and the allocations of both paths
+                /// are dominated by the pairings. Use this when the proof is no longer needed after verification.
+                pub fn verify_consuming(self, mut com_proof: CProof<E>, crs: &CRS<E>) -> bool {
+                    if check_crs(&com_proof, crs).is_err()
+                        || check_proof_shape(self.get_type(), &self.gamma, &com_proof).is_err()
+                    {
+                        return false;
+                    }
+                    let ycoms = ark_std::mem::take(&mut com_proof.ycoms.coms);
+                    let equ_proofs = ark_std::mem::take(&mut com_proof.equ_proofs);
+                    self.terms(&com_proof, Cow::Owned(ycoms), ProofTerms::Plain(&equ_proofs[0]), crs)
+                        .map(|(lhs, rhs)| lhs == rhs)
+                        .unwrap_or(false)
+                }
+
+                /// Verifies the proof like [`verify`](Verifiable::verify), but with `prepared` (e.g. from
+                /// [`prepare_g2`](crate::prover::EquProof::prepare_g2)) in place of the equation proof in
+                /// `com_proof`, which is then only checked for its shape.
+                ///
+                /// Rejects a `prepared` proof for a different type of equation or CRS.
+                pub fn verify_prepared(
+                    &self,
+                    com_proof: &CProof<E>,
+                    prepared: &PreparedProof<E>,
+                    crs: &CRS<E>,
+                ) -> bool {
+                    let crs_fingerprint = crs.fingerprint();
+                    if com_proof.crs_fingerprint != crs_fingerprint
+                        || prepared.crs_fingerprint != crs_fingerprint
+                        || check_proof_shape(self.get_type(), &self.gamma, com_proof).is_err()
+                        || prepared.equ_type != self.get_type()
+                    {
+                        return false;
+                    }
+                    self.terms(
+                        com_proof,
+                        Cow::Borrowed(&com_proof.ycoms.coms),
+                        ProofTerms::Prepared(prepared),
+                        crs,
+                    )
+                    .map(|(lhs, rhs)| lhs == rhs)
+                    .unwrap_or(false)
+                }
+
+                // Verifies a proof that the verifier assembled itself from commitments that carry no CRS
+                // fingerprint, e.g. the parts of a gadget proof, so that there is no fingerprint to check
+                pub(crate) fn verify_assembled(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
+                    if check_proof_shape(self.get_type(), &self.gamma, com_proof).is_err() {
+                        return false;
+                    }
+                    self.terms(
+                        com_proof,
+                        Cow::Borrowed(&com_proof.ycoms.coms),
+                        ProofTerms::Plain(&com_proof.equ_proofs[0]),
+                        crs,
+                    )
+                    .map(|(lhs, rhs)| lhs == rhs)
+                    .unwrap_or(false)
+                }
+            }
+        )*
+    };
+}
+
+impl_verify_variants!(PPE, MSMEG1, MSMEG2, QuadEqu);
+
 impl<E: Pairing> Verifiable<E> for PPE<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
+    fn verification_terms(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
+        check_crs(com_proof, crs)?;
+        check_proof_shape(self.get_type(), &self.gamma, com_proof)?;
+        self.terms(
+            com_proof,
+            Cow::Borrowed(&com_proof.ycoms.coms),
+            ProofTerms::Plain(&com_proof.equ_proofs[0]),
+            crs,
+        )
+    }
+}
+
+impl<E: Pairing> PPE<E> {
+    // The verification terms of a well-formed proof, with its `y` commitments passed separately so that
+    // they can be moved out of a consumed proof, and its equation proof so that it can be prepared
+    fn terms(
+        &self,
+        com_proof: &CProof<E>,
+        ycoms: Cow<'_, [Com2<E>]>,
+        proof: ProofTerms<'_, E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
         let is_parallel = true;
+        // Reject a misshapen proof before computing anything
+        self.check_proof_lens(proof.pi_len(), proof.theta_len())?;
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_linear_map(&self.a_consts),
-            &com_proof.ycoms.coms,
-        );
+        // Accumulate the pairings of the lhs minus those of the proof, sharing the final exponentiations
+        let mut acc = ComMillerAccumulator::<E>::new();
+        let (lin_a, lin_b) = batch_linear_map_both::<E>(&self.a_consts, &self.b_consts);
+        acc.add_com_sum(&lin_a, &ycoms)?;
+        acc.add_com_sum(&com_proof.xcoms.coms, &lin_b)?;
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
-            &com_proof.xcoms.coms,
-            &Com2::<E>::batch_linear_map(&self.b_consts),
-        );
+        let stmt_com_y = gamma_ycoms(&self.gamma, ycoms, is_parallel);
+        acc.add_com_sum(&com_proof.xcoms.coms, &stmt_com_y)?;
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+        // The proof of a linear equation in only one kind of variable leaves out its zero half
+        let u_len = if proof.pi_len() == 0 { 0 } else { 2 };
+        let v_len = if proof.theta_len() == 0 { 0 } else { 2 };
+        proof.sub_from(&mut acc, crs, u_len, v_len)?;
 
         let lin_t = ComT::<E>::linear_map_PPE(&self.target);
 
-        let com1_pf2 = ComT::<E>::pairing_sum(&crs.u, &com_proof.equ_proofs[0].pi);
+        Ok((acc.finalize(), lin_t))
+    }
+}
 
-        let pf1_com2 = ComT::<E>::pairing_sum(&com_proof.equ_proofs[0].theta, &crs.v);
+impl<E: Pairing> PPE<E> {
+    /// The two sides of the verification equation `ι(A) · d + c · ι(B) + c · Γd = ι_T(t) + u · π + θ · v`
+    /// as `1 x 1` matrices over [`BT`](crate::data_structures::ComT), which are equal if and only if
+    /// the proof is valid, e.g. to inspect which side of a failing verification is off.
+    ///
+    /// Each side is assembled term by term with [`Mat`] arithmetic, and the entry of each side is itself
+    /// the `2 x 2` matrix over `GT` (see [`as_matrix`](BT::as_matrix)) that [`verify`](Verifiable::verify)
+    /// compares entry-wise. Unlike `verify`, this computes every pairing sum separately, so it is slower.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the commitments or the proof do not match the shape of the equation.
+    pub fn verification_matrix(
+        &self,
+        proof: &EquProof<E>,
+        xcoms: &Commit1<E>,
+        ycoms: &Commit2<E>,
+        crs: &CRS<E>,
+    ) -> (Matrix<ComT<E>>, Matrix<ComT<E>>) {
+        let is_parallel = true;
+        let term = |x: &[Com1<E>], y: &[Com2<E>]| vec![vec![ComT::<E>::pairing_sum(x, y)]];
+
+        let stmt_com_y = gamma_ycoms(&self.gamma, Cow::Borrowed(&ycoms.coms), is_parallel);
+        let (lin_a, lin_b) = batch_linear_map_both::<E>(&self.a_consts, &self.b_consts);
+        let lhs = term(&lin_a, &ycoms.coms)
+            .add(&term(&xcoms.coms, &lin_b))
+            .add(&term(&xcoms.coms, &stmt_com_y));
+
+        // The proof of a linear equation in only one kind of variable leaves out its zero half
+        let u: &[Com1<E>] = if proof.pi.is_empty() { &[] } else { &crs.u };
+        let v: &[Com2<E>] = if proof.theta.is_empty() { &[] } else { &crs.v };
+        let rhs = vec![vec![ComT::<E>::linear_map_PPE(&self.target)]]
+            .add(&term(u, &proof.pi))
+            .add(&term(&proof.theta, v));
+
+        (lhs, rhs)
+    }
+}
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+impl<E: Pairing> PPE<E> {
+    /// Verifies `proof` like [`verify`](Verifiable::verify), but for the `x` and `y` commitments as they
+    /// arrive from `xcoms` and `ycoms` rather than buffered in a [`CProof`], e.g. for an equation over a
+    /// huge number of variables received over a stream.
+    ///
+    /// The `x` commitments are consumed first. Every commitment is added to the Miller loop of the
+    /// verification equation as it arrives, and only `Γ^T c` for the `x` commitments `c`, i.e. one
+    /// [`Com1`] per `y` variable, is kept until the `y` commitments arrive (nothing if `Γ` is zero).
+    /// Unlike a [`CProof`], the commitments carry no CRS fingerprint to check.
+    ///
+    /// Returns `false` if there are not exactly as many commitments as variables of the equation.
+    pub fn verify_streaming<I1, I2>(
+        &self,
+        proof: &EquProof<E>,
+        xcoms: I1,
+        ycoms: I2,
+        crs: &CRS<E>,
+    ) -> bool
+    where
+        I1: IntoIterator<Item = Com1<E>>,
+        I2: IntoIterator<Item = Com2<E>>,
+    {
+        let (m, n) = (self.b_consts.len(), self.a_consts.len());
+        if proof.equ_type != EquType::PairingProduct
+            || self.gamma.len() != m
+            || self.gamma.iter().any(|row| row.len() != n)
+        {
+            return false;
+        }
+        let is_linear = self.is_linear();
+        let mut acc = ComMillerAccumulator::<E>::new();
+
+        // c · ι(B), accumulating Γ^T c along the way
+        let mut gamma_t_xcoms = vec![Com1Projective::<E>::zero(); if is_linear { 0 } else { n }];
+        let mut x_count = 0;
+        for (i, com) in xcoms.into_iter().enumerate() {
+            if i == m {
+                return false;
+            }
+            acc.add_com(com, Com2::<E>::linear_map(&self.b_consts[i]));
+            let com = Com1Projective::<E>::from(com);
+            for (gamma_t_com, gamma) in gamma_t_xcoms.iter_mut().zip(self.gamma[i].iter()) {
+                *gamma_t_com += com.scalar_mul(gamma);
+            }
+            x_count += 1;
+        }
+        if x_count != m {
+            return false;
+        }
+        let gamma_t_xcoms = Com1Projective::<E>::normalize_batch(&gamma_t_xcoms);
+
+        // (ι(A) + Γ^T c) · d, i.e. ι(A) · d + c · Γd
+        let mut y_count = 0;
+        for (j, com) in ycoms.into_iter().enumerate() {
+            if j == n {
+                return false;
+            }
+            let mut lhs = Com1::<E>::linear_map(&self.a_consts[j]);
+            if let Some(gamma_t_com) = gamma_t_xcoms.get(j) {
+                lhs += *gamma_t_com;
+            }
+            acc.add_com(lhs, com);
+            y_count += 1;
+        }
+        if y_count != n {
+            return false;
+        }
+
+        // The proof of a linear equation in only one kind of variable leaves out its zero half
+        let u_len = if proof.pi.is_empty() { 0 } else { 2 };
+        let v_len = if proof.theta.is_empty() { 0 } else { 2 };
+        if ProofTerms::Plain(proof)
+            .sub_from(&mut acc, crs, u_len, v_len)
+            .is_err()
+        {
+            return false;
+        }
+        acc.finalize() == ComT::<E>::linear_map_PPE(&self.target)
+    }
+}
 
-        lhs == rhs
+#[cfg(feature = "prf")]
+impl<E: Pairing> AggregatePPE<E> {
+    /// Verifies that the committed `x` and `y` variables satisfy every equation, by verifying the
+    /// proof for the [`combined`](AggregatePPE::combined) equation of the commitments in `com_proof`.
+    pub fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
+        self.combined(&com_proof.xcoms, &com_proof.ycoms, crs)
+            .verify(com_proof, crs)
     }
 }
 
 impl<E: Pairing> Verifiable<E> for MSMEG1<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
-        let is_parallel = true;
+    fn verification_terms(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
+        check_crs(com_proof, crs)?;
+        check_proof_shape(self.get_type(), &self.gamma, com_proof)?;
+        self.terms(
+            com_proof,
+            Cow::Borrowed(&com_proof.ycoms.coms),
+            ProofTerms::Plain(&com_proof.equ_proofs[0]),
+            crs,
+        )
+    }
+}
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_linear_map(&self.a_consts),
-            &com_proof.ycoms.coms,
-        );
+impl<E: Pairing> MSMEG1<E> {
+    // The verification terms of a well-formed proof, with its `y` commitments passed separately so that
+    // they can be moved out of a consumed proof, and its equation proof so that it can be prepared
+    fn terms(
+        &self,
+        com_proof: &CProof<E>,
+        ycoms: Cow<'_, [Com2<E>]>,
+        proof: ProofTerms<'_, E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
+        let is_parallel = true;
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
+        // Accumulate the pairings of the lhs minus those of the proof, sharing the final exponentiations
+        let mut acc = ComMillerAccumulator::<E>::new();
+        acc.add_com_sum(&Com1::<E>::batch_linear_map(&self.a_consts), &ycoms)?;
+        acc.add_com_sum(
             &com_proof.xcoms.coms,
             &Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs),
-        );
+        )?;
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+        let stmt_com_y = gamma_ycoms(&self.gamma, ycoms, is_parallel);
+        acc.add_com_sum(&com_proof.xcoms.coms, &stmt_com_y)?;
 
-        let lin_t = ComT::<E>::linear_map_MSMEG1(&self.target, crs);
+        // theta consists of a single element, paired with v_1
+        proof.sub_from(&mut acc, crs, 2, 1)?;
 
-        let com1_pf2 = ComT::<E>::pairing_sum(&crs.u, &com_proof.equ_proofs[0].pi);
-
-        let pf1_com2 = ComT::<E>::pairing(com_proof.equ_proofs[0].theta[0], crs.v[0]);
-
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+        let lin_t = ComT::<E>::linear_map_MSMEG1(&self.target, crs);
 
-        lhs == rhs
+        Ok((acc.finalize(), lin_t))
     }
 }
 
 impl<E: Pairing> Verifiable<E> for MSMEG2<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
+    fn verification_terms(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
+        check_crs(com_proof, crs)?;
+        check_proof_shape(self.get_type(), &self.gamma, com_proof)?;
+        self.terms(
+            com_proof,
+            Cow::Borrowed(&com_proof.ycoms.coms),
+            ProofTerms::Plain(&com_proof.equ_proofs[0]),
+            crs,
+        )
+    }
+}
+
+impl<E: Pairing> MSMEG2<E> {
+    // The verification terms of a well-formed proof, with its `y` commitments passed separately so that
+    // they can be moved out of a consumed proof, and its equation proof so that it can be prepared
+    fn terms(
+        &self,
+        com_proof: &CProof<E>,
+        ycoms: Cow<'_, [Com2<E>]>,
+        proof: ProofTerms<'_, E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
         let is_parallel = true;
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
+        // Accumulate the pairings of the lhs minus those of the proof, sharing the final exponentiations
+        let mut acc = ComMillerAccumulator::<E>::new();
+        acc.add_com_sum(
             &Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs),
-            &com_proof.ycoms.coms,
-        );
-
-        let com_x_lin_b = ComT::<E>::pairing_sum(
+            &ycoms,
+        )?;
+        acc.add_com_sum(
             &com_proof.xcoms.coms,
             &Com2::<E>::batch_linear_map(&self.b_consts),
-        );
-
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
-
-        let lin_t = ComT::<E>::linear_map_MSMEG2(&self.target, crs);
+        )?;
 
-        let com1_pf2 = ComT::<E>::pairing(crs.u[0], com_proof.equ_proofs[0].pi[0]);
+        let stmt_com_y = gamma_ycoms(&self.gamma, ycoms, is_parallel);
+        acc.add_com_sum(&com_proof.xcoms.coms, &stmt_com_y)?;
 
-        let pf1_com2 = ComT::<E>::pairing_sum(&com_proof.equ_proofs[0].theta, &crs.v);
+        // pi consists of a single element, paired with u_1
+        proof.sub_from(&mut acc, crs, 1, 2)?;
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+        let lin_t = ComT::<E>::linear_map_MSMEG2(&self.target, crs);
 
-        lhs == rhs
+        Ok((acc.finalize(), lin_t))
     }
 }
 
 impl<E: Pairing> Verifiable<E> for QuadEqu<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
+    fn verification_terms(
+        &self,
+        com_proof: &CProof<E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
+        check_crs(com_proof, crs)?;
+        check_proof_shape(self.get_type(), &self.gamma, com_proof)?;
+        self.terms(
+            com_proof,
+            Cow::Borrowed(&com_proof.ycoms.coms),
+            ProofTerms::Plain(&com_proof.equ_proofs[0]),
+            crs,
+        )
+    }
+}
+
+impl<E: Pairing> QuadEqu<E> {
+    // The verification terms of a well-formed proof, with its `y` commitments passed separately so that
+    // they can be moved out of a consumed proof, and its equation proof so that it can be prepared
+    fn terms(
+        &self,
+        com_proof: &CProof<E>,
+        ycoms: Cow<'_, [Com2<E>]>,
+        proof: ProofTerms<'_, E>,
+        crs: &CRS<E>,
+    ) -> Result<(ComT<E>, ComT<E>), VerificationError> {
         let is_parallel = true;
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
+        // Accumulate the pairings of the lhs minus those of the proof, sharing the final exponentiations
+        let mut acc = ComMillerAccumulator::<E>::new();
+        acc.add_com_sum(
             &Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs),
-            &com_proof.ycoms.coms,
-        );
-
-        let com_x_lin_b = ComT::<E>::pairing_sum(
+            &ycoms,
+        )?;
+        acc.add_com_sum(
             &com_proof.xcoms.coms,
             &Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs),
-        );
+        )?;
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+        let stmt_com_y = gamma_ycoms(&self.gamma, ycoms, is_parallel);
+        acc.add_com_sum(&com_proof.xcoms.coms, &stmt_com_y)?;
 
-        let lin_t = ComT::<E>::linear_map_quad(&self.target, crs);
+        // pi and theta each consist of a single element, paired with u_1 and v_1 respectively
+        proof.sub_from(&mut acc, crs, 1, 1)?;
 
-        let com1_pf2 = ComT::<E>::pairing(crs.u[0], com_proof.equ_proofs[0].pi[0]);
-
-        let pf1_com2 = ComT::<E>::pairing(com_proof.equ_proofs[0].theta[0], crs.v[0]);
-
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+        let lin_t = ComT::<E>::linear_map_quad(&self.target, crs);
 
-        lhs == rhs
+        Ok((acc.finalize(), lin_t))
     }
 }
 
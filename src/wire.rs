@@ -0,0 +1,353 @@
+//! A stable, documented byte format for proofs and commitments, e.g. for verifiers written in other
+//! languages.
+//!
+//! Unlike the [`CanonicalSerialize`] encoding, whose framing follows the layout of the Rust types, every
+//! encoding here starts with a header identifying the format and the curve, and holds only the public
+//! parts of a proof (never the prover's randomness). Integers are big-endian. Group elements are in
+//! their compressed encoding, which for BLS12-381 is the standard (ZCash) encoding of 48 bytes in `G1`
+//! and 96 bytes in `G2`. An element of [`B1`](crate::data_structures::Com1) (resp.
+//! [`B2`](crate::data_structures::Com2)) is its two group elements, in order.
+//!
+//! Every encoding starts with the 6-byte header
+//!
+//! | size | field |
+//! |-----:|-------|
+//! | 4 | magic bytes, `GSPF` for a proof and `GSCM` for commitments |
+//! | 1 | format version, currently [`VERSION`] |
+//! | 1 | curve identifier, see [`Curve::id`] |
+//!
+//! A proof ([`encode_proof`]) for a single equation continues with
+//!
+//! | size | field |
+//! |-----:|-------|
+//! | 1 | equation type tag: `0` for PPE, `1` for MSMEG1, `2` for MSMEG2 and `3` for QuadEqu |
+//! | 8 | the [`fingerprint`](crate::generator::CRS::fingerprint) of the CRS |
+//! | 4 | `m`, the number of `x` commitments |
+//! | 4 | `n`, the number of `y` commitments |
+//! | 1 | `p`, the number of elements of `π` |
+//! | 1 | `q`, the number of elements of `θ` |
+//! | | the `m` `x` commitments in `B1`, the `n` `y` commitments in `B2`, then the `p` elements of `π` in `B2` and the `q` elements of `θ` in `B1` |
+//!
+//! and commitments ([`encode_commitments`]) continue with
+//!
+//! | size | field |
+//! |-----:|-------|
+//! | 4 | `m`, the number of `x` commitments |
+//! | 4 | `n`, the number of `y` commitments |
+//! | | the `m` `x` commitments in `B1`, then the `n` `y` commitments in `B2` |
+
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_serialize::CanonicalSerialize;
+use ark_std::fmt::{self, Display, Formatter};
+
+use crate::data_structures::{Com1, Com2, ShapeError};
+use crate::prover::{CProof, Commit1, Commit2, EquProof};
+use crate::statement::EquType;
+
+/// The version of the format written by [`encode_proof`] and [`encode_commitments`].
+pub const VERSION: u8 = 1;
+
+const PROOF_MAGIC: [u8; 4] = *b"GSPF";
+const COMMITMENTS_MAGIC: [u8; 4] = *b"GSCM";
+
+// The compressed generator of G1 on BLS12-381, which identifies the curve
+const BLS12_381_G1_GEN: [u8; 48] = [
+    0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+];
+
+/// The curves with an identifier in the format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Curve {
+    Bls12_381,
+}
+
+impl Curve {
+    /// The identifier of the curve in the header.
+    pub fn id(self) -> u8 {
+        match self {
+            Curve::Bls12_381 => 1,
+        }
+    }
+
+    /// The curve with the identifier `id`, if any.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Curve::Bls12_381),
+            _ => None,
+        }
+    }
+
+    /// The curve of the pairing `E`, recognized by its generator of `G1`, if it has an identifier.
+    pub fn of<E: Pairing>() -> Option<Self> {
+        let mut bytes = Vec::new();
+        E::G1Affine::generator()
+            .serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+        (bytes == BLS12_381_G1_GEN).then_some(Curve::Bls12_381)
+    }
+}
+
+/// The reasons encoding or decoding in the [`wire`](self) format may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireError {
+    /// The pairing has no [`Curve`] identifier.
+    UnsupportedCurve,
+    /// The bytes do not start with the magic bytes of the expected kind of encoding.
+    Magic,
+    /// The format version is not one this implementation reads.
+    UnknownVersion(u8),
+    /// The curve identifier is not one of a [`Curve`].
+    UnknownCurve(u8),
+    /// The bytes encode values on a different curve than the one decoded into.
+    CurveMismatch { expected: Curve, found: Curve },
+    /// The equation type tag is not one of an [`EquType`].
+    UnknownEquType(u8),
+    /// The proof does not contain exactly one equation proof.
+    ProofCount(usize),
+    /// There are too many elements of some kind for their count to fit in the format.
+    TooManyElements,
+    /// The bytes end before the encoded value does.
+    Truncated,
+    /// There are bytes after the encoded value.
+    TrailingBytes,
+    /// A group element is not a valid compressed encoding of a point in its subgroup.
+    InvalidElement,
+    /// `π` or `θ` has the wrong number of elements for the type of equation.
+    Shape(ShapeError),
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::UnsupportedCurve => write!(f, "the curve has no identifier in the format"),
+            WireError::Magic => write!(f, "unexpected magic bytes"),
+            WireError::UnknownVersion(version) => write!(f, "unknown format version {}", version),
+            WireError::UnknownCurve(id) => write!(f, "unknown curve identifier {}", id),
+            WireError::CurveMismatch { expected, found } => {
+                write!(f, "expected values on {:?}, found {:?}", expected, found)
+            }
+            WireError::UnknownEquType(tag) => write!(f, "unknown equation type tag {}", tag),
+            WireError::ProofCount(count) => {
+                write!(f, "expected exactly 1 equation proof, found {}", count)
+            }
+            WireError::TooManyElements => write!(f, "too many elements to encode"),
+            WireError::Truncated => write!(f, "unexpected end of the encoding"),
+            WireError::TrailingBytes => write!(f, "trailing bytes after the encoded value"),
+            WireError::InvalidElement => write!(f, "invalid group element"),
+            WireError::Shape(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl ark_std::error::Error for WireError {}
+
+impl From<ShapeError> for WireError {
+    fn from(err: ShapeError) -> Self {
+        WireError::Shape(err)
+    }
+}
+
+/// Encodes the public parts of a proof for a single equation, i.e. its commitments and `(π, θ)`.
+///
+/// Returns an error if the curve of `E` has no identifier, if the proof is not for exactly one
+/// equation, or if it has more variables than the format can count.
+pub fn encode_proof<E: Pairing>(proof: &CProof<E>) -> Result<Vec<u8>, WireError> {
+    let equ_proof = match proof.equ_proofs.as_slice() {
+        [equ_proof] => equ_proof,
+        equ_proofs => return Err(WireError::ProofCount(equ_proofs.len())),
+    };
+    let mut bytes = header::<E>(PROOF_MAGIC)?;
+    bytes.push(match equ_proof.equ_type {
+        EquType::PairingProduct => 0,
+        EquType::MultiScalarG1 => 1,
+        EquType::MultiScalarG2 => 2,
+        EquType::Quadratic => 3,
+    });
+    bytes.extend_from_slice(&proof.crs_fingerprint.to_be_bytes());
+    push_count_u32(&mut bytes, proof.xcoms.coms.len())?;
+    push_count_u32(&mut bytes, proof.ycoms.coms.len())?;
+    push_count_u8(&mut bytes, equ_proof.pi().len())?;
+    push_count_u8(&mut bytes, equ_proof.theta().len())?;
+    push_com1s(&mut bytes, &proof.xcoms.coms);
+    push_com2s(&mut bytes, &proof.ycoms.coms);
+    push_com2s(&mut bytes, equ_proof.pi());
+    push_com1s(&mut bytes, equ_proof.theta());
+    Ok(bytes)
+}
+
+/// Decodes a proof from [`encode_proof`]. The proof has no randomness, as in
+/// [`CProof::without_randomness`].
+///
+/// Returns an error if the bytes are not a complete encoding of a proof in a known version of the
+/// format, on the curve of `E`.
+pub fn decode_proof<E: Pairing>(bytes: &[u8]) -> Result<CProof<E>, WireError> {
+    let mut reader = Reader::new::<E>(bytes, PROOF_MAGIC)?;
+    let equ_type = match reader.u8()? {
+        0 => EquType::PairingProduct,
+        1 => EquType::MultiScalarG1,
+        2 => EquType::MultiScalarG2,
+        3 => EquType::Quadratic,
+        tag => return Err(WireError::UnknownEquType(tag)),
+    };
+    let crs_fingerprint = u64::from_be_bytes(reader.array()?);
+    let m = u32::from_be_bytes(reader.array()?) as usize;
+    let n = u32::from_be_bytes(reader.array()?) as usize;
+    let p = reader.u8()? as usize;
+    let q = reader.u8()? as usize;
+    let xcoms = reader.com1s::<E>(m)?;
+    let ycoms = reader.com2s::<E>(n)?;
+    let pi = reader.com2s::<E>(p)?;
+    let theta = reader.com1s::<E>(q)?;
+    reader.finish()?;
+
+    Ok(CProof::<E> {
+        xcoms: Commit1::<E>::from_coms(xcoms),
+        ycoms: Commit2::<E>::from_coms(ycoms),
+        equ_proofs: vec![EquProof::<E>::from_parts(pi, theta, equ_type)?],
+        crs_fingerprint,
+    })
+}
+
+/// Encodes the commitments to `x` and `y` variables, without their randomness.
+///
+/// Returns an error if the curve of `E` has no identifier, or if there are more commitments than the
+/// format can count.
+pub fn encode_commitments<E: Pairing>(
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+) -> Result<Vec<u8>, WireError> {
+    let mut bytes = header::<E>(COMMITMENTS_MAGIC)?;
+    push_count_u32(&mut bytes, xcoms.coms.len())?;
+    push_count_u32(&mut bytes, ycoms.coms.len())?;
+    push_com1s(&mut bytes, &xcoms.coms);
+    push_com2s(&mut bytes, &ycoms.coms);
+    Ok(bytes)
+}
+
+/// Decodes the commitments to `x` and `y` variables from [`encode_commitments`], without their randomness.
+///
+/// Returns an error if the bytes are not a complete encoding of commitments in a known version of
+/// the format, on the curve of `E`.
+pub fn decode_commitments<E: Pairing>(bytes: &[u8]) -> Result<(Commit1<E>, Commit2<E>), WireError> {
+    let mut reader = Reader::new::<E>(bytes, COMMITMENTS_MAGIC)?;
+    let m = u32::from_be_bytes(reader.array()?) as usize;
+    let n = u32::from_be_bytes(reader.array()?) as usize;
+    let xcoms = reader.com1s::<E>(m)?;
+    let ycoms = reader.com2s::<E>(n)?;
+    reader.finish()?;
+    Ok((
+        Commit1::<E>::from_coms(xcoms),
+        Commit2::<E>::from_coms(ycoms),
+    ))
+}
+
+fn header<E: Pairing>(magic: [u8; 4]) -> Result<Vec<u8>, WireError> {
+    let curve = Curve::of::<E>().ok_or(WireError::UnsupportedCurve)?;
+    let mut bytes = magic.to_vec();
+    bytes.push(VERSION);
+    bytes.push(curve.id());
+    Ok(bytes)
+}
+
+fn push_count_u32(bytes: &mut Vec<u8>, count: usize) -> Result<(), WireError> {
+    let count = u32::try_from(count).map_err(|_| WireError::TooManyElements)?;
+    bytes.extend_from_slice(&count.to_be_bytes());
+    Ok(())
+}
+
+fn push_count_u8(bytes: &mut Vec<u8>, count: usize) -> Result<(), WireError> {
+    bytes.push(u8::try_from(count).map_err(|_| WireError::TooManyElements)?);
+    Ok(())
+}
+
+fn push_element<T: CanonicalSerialize>(bytes: &mut Vec<u8>, elem: &T) {
+    elem.serialize_compressed(bytes)
+        .expect("serializing into a Vec cannot fail");
+}
+
+fn push_com1s<E: Pairing>(bytes: &mut Vec<u8>, coms: &[Com1<E>]) {
+    for com in coms {
+        push_element(bytes, &com.0);
+        push_element(bytes, &com.1);
+    }
+}
+
+fn push_com2s<E: Pairing>(bytes: &mut Vec<u8>, coms: &[Com2<E>]) {
+    for com in coms {
+        push_element(bytes, &com.0);
+        push_element(bytes, &com.1);
+    }
+}
+
+// Reads the fields of an encoding in order, after checking its header
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new<E: Pairing>(bytes: &'a [u8], magic: [u8; 4]) -> Result<Self, WireError> {
+        let mut reader = Self { bytes };
+        if reader.array::<4>()? != magic {
+            return Err(WireError::Magic);
+        }
+        match reader.u8()? {
+            VERSION => {}
+            version => return Err(WireError::UnknownVersion(version)),
+        }
+        let id = reader.u8()?;
+        let found = Curve::from_id(id).ok_or(WireError::UnknownCurve(id))?;
+        let expected = Curve::of::<E>().ok_or(WireError::UnsupportedCurve)?;
+        if found != expected {
+            return Err(WireError::CurveMismatch { expected, found });
+        }
+        Ok(reader)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], WireError> {
+        if self.bytes.len() < len {
+            return Err(WireError::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        Ok(self.take(N)?.try_into().expect("took exactly N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    // Reads a group element of the size of its compressed generator
+    fn element<G: AffineRepr>(&mut self) -> Result<G, WireError> {
+        let bytes = self.take(G::generator().compressed_size())?;
+        G::deserialize_compressed(bytes).map_err(|_| WireError::InvalidElement)
+    }
+
+    // The counts are untrusted, so the vectors grow with the elements actually read
+    fn com1s<E: Pairing>(&mut self, count: usize) -> Result<Vec<Com1<E>>, WireError> {
+        (0..count)
+            .map(|_| Ok(Com1::<E>(self.element()?, self.element()?)))
+            .collect()
+    }
+
+    fn com2s<E: Pairing>(&mut self, count: usize) -> Result<Vec<Com2<E>>, WireError> {
+        (0..count)
+            .map(|_| Ok(Com2::<E>(self.element()?, self.element()?)))
+            .collect()
+    }
+
+    fn finish(self) -> Result<(), WireError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(WireError::TrailingBytes)
+        }
+    }
+}
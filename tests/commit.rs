@@ -7,12 +7,14 @@ mod SXDH_commit_tests {
     use ark_ec::pairing::Pairing;
     use ark_ec::CurveGroup;
     use ark_ff::UniformRand;
+    use ark_serialize::CanonicalSerialize;
     use ark_std::ops::Mul;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
     use ark_std::test_rng;
 
     use groth_sahai::data_structures::*;
-    use groth_sahai::{AbstractCrs, CRS};
-    //    use groth_sahai::commit::*;
+    use groth_sahai::prelude::*;
+    use groth_sahai::prover::*;
 
     type G1Projective = <F as Pairing>::G1;
     type G2Projective = <F as Pairing>::G2;
@@ -83,4 +85,102 @@ mod SXDH_commit_tests {
 
         assert_eq!(bt_lin_bilin, bt_bilin_lin);
     }
+
+    #[test]
+    fn commitments_are_pinned_for_a_fixed_seed() {
+        let mut rng = StdRng::seed_from_u64(1134);
+        let key = CRS::<F>::generate_crs(&mut rng);
+        let xvars: Vec<_> = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect();
+        let yvars: Vec<_> = (0..3)
+            .map(|_| G2Projective::rand(&mut rng).into_affine())
+            .collect();
+        let scalars: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+
+        // Every commit function, drawing its randomness from the same RNG in a fixed order
+        let mut b1_coms: Vec<Com1<F>> = commit_G1(&xvars[0], &key, &mut rng).coms;
+        b1_coms.extend(batch_commit_G1(&xvars, &key, &mut rng).coms);
+        b1_coms.extend(commit_scalar_to_B1(&scalars[0], &key, &mut rng).coms);
+        b1_coms.extend(batch_commit_scalar_to_B1(&scalars, &key, &mut rng).coms);
+        b1_coms.extend(batch_commit_scalar_to_B1_compact(&scalars, &key, &mut rng).coms);
+        let mut b2_coms: Vec<Com2<F>> = commit_G2(&yvars[0], &key, &mut rng).coms;
+        b2_coms.extend(batch_commit_G2(&yvars, &key, &mut rng).coms);
+        b2_coms.extend(commit_scalar_to_B2(&scalars[0], &key, &mut rng).coms);
+        b2_coms.extend(batch_commit_scalar_to_B2(&scalars, &key, &mut rng).coms);
+
+        // The compressed commitments, as output by the commit functions before any of them was optimized
+        const B1_COMS: [&str; 11] = [
+            "88b8f3a064201a93d5ee448b57245c08f84877c260e2e498f5a6459d2a8c7789e007e57886118ce5ae272e0ce3d04804\
+             ab68e09c738921dbae59b043b9502b14e89a8d4dff3b15ec56ac6f7ae682d48fc56ca9f00efb89afbc78d6e72cbffa34",
+            "8bcb0c4205156a7d469aa531b24f1bfeee63f955428a453fa2e4abd19add04036b82e3c63cf43d2995d70c0a960d89d0\
+             aea3a96d515cccdb6c95f9c908576fa005deb1efd1afdf0d2e8c54b78023154541fbc04a1258f4a38b6ff4fddae5dcd3",
+            "b6b46f1be4653bc3777835d1a7c2193ea7dc2d740bd8e2dd54c688a62516741408f0a43bee410336995579825b0a9f3b\
+             a84395fabea070cb7938d77244e023382350911bcf8d58f066c73a1a83a783588d30938e9872c9fb3f5e0586f0a37042",
+            "b2aa59d5771255dcebf6dd8f25a1b7da8ddee24b8b8a1a8e50f44facb512200d8f3bb4d1bb2596587e998580a8b2d1dc\
+             af8a97a5f99b21127258db5202c30254d63f3f612232ede7788909074aa9e28f8ca64b407d8f117db8075e23d04c7a8c",
+            "9031ed7a41111839601f075d543e3cb093677963e531e66e92b60d5a56357dddda562929c88a7d10981017301470394d\
+             810284e0a366b994ca38529a67ff22ff21c474d897932fee5c6c4f8204a682bb7e294a470e2f5ada9dd40aadad9a518a",
+            "b9577f6661ca9ad38e58e85e0bd1ecda7676940301d6c3022c7a024d40454a4bff56fb5a8dad73807081959d16545e65\
+             9296d8245c3340fac7862db3c2f0e389c4d19162384bb00c8bf9b17b1645823c4bb28e834bab9fe051be12b1572c82e9",
+            "84dafa35592c7eada7e9b4b9c255dc3bbdc109645972b81110b9ff86dec75805992d090ff14af1560aa4abc4e4f86e05\
+             a5a11118cf49a86bfbf67e562c60ea216c96509f27f26ef84ccb77d020ed90f736264c8b20a514248473a1bfdbf9ef62",
+            "afe88a4010314179a34a92ebeb08e30befd07cdb54a1a481f492286ecb5ff548717090f59462a54cfc22d8f7835eee44\
+             b6ad041738cd18974d17a171b6bec72c1c18c893603763855e301aa5d171546dbe99acaca70b497375c2935a99116229",
+            "abcd0870c74d4cf752cef7ee5106f9ee834586176363b414f5b91b8457ef43038e8adcd68e050b011d6a2d499c6fee1f\
+             807222a19f82b15bb45a0a2224bbbb5c5cec4a0d3f6db39ecfad54b86d87347d709d382e08f722865d08db064b15d0dc",
+            "9278db95388c415965c782bde7303fd5b149e5d67c72bd61ff9f775eac1550213df4c80aa9bd91ee41c90967d447aa3d\
+             966d237fce0016b86cf907cab730f6eb695e05ba38266f1a90fe5e77b1e1b083db0011c09a6dfb3d012e744c1a07d4ef",
+            "b7b7e23553574ecb8b27044fc02dec331641005affc6dd1e7938a4d2e17116e857e939a9eebd0a9114f6f08e124d4fac\
+             a22a2343a2f5b76a5bb83c1b6c38c264b23fa21da8cad821d90401dd9f5052acf113e3bcb3e6275199a632e8d9b2920b",
+        ];
+        const B2_COMS: [&str; 8] = [
+            "87032f3bfbb6a09ffa38cc043d68f6a423819432ede6ebf40e8a5bd79b23510a9ddc0f5621b47e5919cadf27ab6e9af8\
+             09e6446c8baa30213d6429106355bda03f6c3cedfe8f65f15d3f08162a1ecbd7ed99593542ac6a8aeb12250ee8166a01\
+             b74f05461fece223e678788b6dbca60055600512f9675d993c440708bced9962ad83c6e2e80dc4afad3b4656d17f0ec5\
+             02a0174b1c3e57ddc439955972d4c6fdf0554cbc832646c7ce5f004ba2dad0e7acf8998251627da72de1512f99a2730c",
+            "b183757536bfb7baead2c1e3674aa1118d1906621e1058dc6a976b0438bfac536f55dc935f9a26ebd0357e54291746e0\
+             13ff4633824be6d595d496d263820c9f9bc4d079232a1312cf2a012a0c21fe72f6c1ea6bc900c80515cde6c90b99d483\
+             ae5b33beff566aef7ea854ac729a88a24c5caba01d2de3433ad9d6e26775148b2bf581133cdbeeac49647df4d4906337\
+             108a72f0995140d9a91fc4b9d1e66f0598a0aabbc7deb9679aec3ef2436921d12bd3719faa9a4c6e56b3ba20f886dd16",
+            "aa70a86a122e18b5609cb1d1b2dccd4710c5bd26552d391210c4c51fdfec05c1631b70758214a63c4287e8fc2a2dd456\
+             1348d8b6da91c63e2c0fdbf9ea2c220fc46e85234666eb6123a0d77c66d8292b109961fda768402b81cda4f88ad32f52\
+             b7e0db0762a2992e0144926f9578f1c1bf9cde9e9333ff19857263a99c033e5ad1f07926deb1960bdc295219104741b5\
+             035f88eecbbb26c55870d9bb4028c7c66055fb6ffdffcde48382212c702460608472775cda6de87ccbd83b9948c13efc",
+            "a0f544dd3c59cd2585dc41c7eca356be6b5bde69bbb19d84cfa8931a5b613bd3b720dd05f3caa32da07b4676ca4d8f81\
+             0c65b5db7e98ad885bcc8ad8d5bd68bb24863066481f7e01109050c3952bf707265eda0a2217068b1fa08d1b9f15a29f\
+             af1d4902a1b620bd5d73905f766589a11d13574c2b21123d2ed8dc789cecf99a609ca2016c49cca6ecaafe2b1b2be7bf\
+             0819866ef162ed8e8da30c19fde2f2d67b85511fd926f6132ed6b22b3393c5a1fcbc3013dc7302d39dd7bbd3b86ead14",
+            "913b9c19c15c2268e41affc8063b976a4b3eb71d41a014cc76d2ce91d288d0c8073a19c7d0a62d199d976ceedda24d4e\
+             04523fbf16a977432a47844776fdb13c9412144918221d10261260ffa400d19f8103cdc85183f7e19ab5cbecfa727b37\
+             878de155d8ab0fea88f4a5f9b1f96db78f71136cdfebab2b5a7f5114cd08d7b2db81004a04dd73bd4ec7675a15707818\
+             1260ecf45972add29adb706e8f742d5ae48ea97a9c3c8f78b12db05ba23c1fa64ddbde30f38a4ae74921ef0d3bc05d99",
+            "89291368a4fe206769096454cfe11858ba2d607d39109661b72943af2486dbfbf222fbeb04b65a1c5f280882d6d1c92f\
+             00a291b4b1b71e56d64b3486e2a48d52fedce73d7ee4aa4709d945c41486c79fb83b7aaacd90e8177bdc8f27860dad68\
+             b4d05ce13b8a485d809c852806c93a76ca97aa8dc7d1e09cbd243427adb690225a4872ec3f4e953a3a6d50cc6f804808\
+             1489e87abfca11af5bf479c6faf88710c7283f0a23e2814961f807145024be99751effb095a4d72bb28aee988691cb3b",
+            "96f485c6ff079fbf68ba0c3e760c03dc5c1d0491a0c3d1ceccd18fe2495867cb5df41fe332448b886b7a006301d946c3\
+             0ce8e463d435a26d201b126f0e283f6f9662104ddc682d5d82847a05bc7ecd775fd1d9ab77f63d7a79419ca4392456c0\
+             8f4474879ee455ad53f0a0a5b053971cd336949c99569a1581650e8f2446137ba82a4d8c123dc2cdec8ba2308cc44942\
+             0ebcab3497edb84d51f7fbb7608f39fbb9d2d54bafde5a8203765e8ead538fd60f4603113ae8382d2fb6824c2c5d0397",
+            "8aaa3815116dee368331f34548a1d15f91c5200b22c33f88bd3a80f0b72461eda37932a5cdb7d368c7663e7cbbc33477\
+             0ed02357507e85e0fe74efc8a67845075ee2f24f169ea4ce801fa061dda5f037030c5754bd41d6d379184279ac1f0278\
+             971d535aaf9ae66344cc9e1c77a30f391145d278913d63f16d5d18e0020a14b4cb14bbf18265c00fad11376887acc053\
+             01928f88ae50932266047ea67b69f355378a0a7a0d913fa1e5e7021ca216d17f87006972c7f252abc9e356163a8bf5f1",
+        ];
+        assert_eq!(to_hex(&b1_coms), B1_COMS);
+        assert_eq!(to_hex(&b2_coms), B2_COMS);
+    }
+
+    // The hex encoding of the compressed serialization of each element
+    fn to_hex<T: CanonicalSerialize>(elems: &[T]) -> Vec<String> {
+        elems
+            .iter()
+            .map(|elem| {
+                let mut bytes = Vec::new();
+                elem.serialize_compressed(&mut bytes).unwrap();
+                bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,107 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "ark-crypto-primitives")]
+
+#[cfg(test)]
+mod SXDH_commitment_scheme_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_crypto_primitives::commitment::CommitmentScheme;
+    use ark_ec::pairing::Pairing;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::{test_rng, UniformRand};
+
+    use groth_sahai::commitment_scheme::*;
+    use groth_sahai::data_structures::Matrix;
+    use groth_sahai::prelude::*;
+    use groth_sahai::prover::batch_verify_openings_G1;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+
+    fn to_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        value.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    // Commits through the trait with randomness sampled from a seeded RNG, and checks that the crate
+    // function drawing its randomness from the same seed gives the same commitment
+    fn assert_matches_direct<C: CommitmentScheme<Parameters = CRS<F>>>(
+        input: &[u8],
+        crs: &CRS<F>,
+        direct: impl Fn(&mut StdRng) -> C::Output,
+    ) -> (C::Output, C::Randomness) {
+        let r = C::Randomness::rand(&mut StdRng::seed_from_u64(1147));
+        let com = C::commit(crs, input, &r).unwrap();
+        assert_eq!(com, direct(&mut StdRng::seed_from_u64(1147)));
+        (com, r)
+    }
+
+    #[test]
+    fn group_element_commitments_match_direct_calls() {
+        let mut rng = test_rng();
+        let crs = GsCommitmentG1::<F>::setup(&mut rng).unwrap();
+
+        let x = G1Affine::rand(&mut rng);
+        let (com, r) = assert_matches_direct::<GsCommitmentG1<F>>(&to_bytes(&x), &crs, |rng| {
+            commit_G1(&x, &crs, rng).coms[0]
+        });
+        assert!(GsCommitmentG1::<F>::verify(&crs, &to_bytes(&x), &com, &r).unwrap());
+        let other = to_bytes(&G1Affine::rand(&mut rng));
+        assert!(!GsCommitmentG1::<F>::verify(&crs, &other, &com, &r).unwrap());
+        // The randomness is the 1 x 2 matrix of the batched opening check
+        let rand: Matrix<Fr> = r.into();
+        assert!(batch_verify_openings_G1(
+            &[com],
+            &[x],
+            &[rand],
+            &crs,
+            &mut rng
+        ));
+
+        let y = G2Affine::rand(&mut rng);
+        let (com, s) = assert_matches_direct::<GsCommitmentG2<F>>(&to_bytes(&y), &crs, |rng| {
+            commit_G2(&y, &crs, rng).coms[0]
+        });
+        assert!(GsCommitmentG2::<F>::verify(&crs, &to_bytes(&y), &com, &s).unwrap());
+        let wrong = GsRandomness { r1: s.r2, r2: s.r1 };
+        assert!(!GsCommitmentG2::<F>::verify(&crs, &to_bytes(&y), &com, &wrong).unwrap());
+    }
+
+    #[test]
+    fn scalar_commitments_match_direct_calls() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let x = Fr::rand(&mut rng);
+        let (com, r) =
+            assert_matches_direct::<GsCommitmentScalarB1<F>>(&to_bytes(&x), &crs, |rng| {
+                commit_scalar_to_B1(&x, &crs, rng).coms[0]
+            });
+        assert!(GsCommitmentScalarB1::<F>::verify(&crs, &to_bytes(&x), &com, &r).unwrap());
+
+        let y = Fr::rand(&mut rng);
+        let (com, s) =
+            assert_matches_direct::<GsCommitmentScalarB2<F>>(&to_bytes(&y), &crs, |rng| {
+                commit_scalar_to_B2(&y, &crs, rng).coms[0]
+            });
+        assert!(GsCommitmentScalarB2::<F>::verify(&crs, &to_bytes(&y), &com, &s).unwrap());
+        assert!(!GsCommitmentScalarB2::<F>::verify(&crs, &to_bytes(&x), &com, &s).unwrap());
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let r = GsRandomness::<Fr>::rand(&mut rng);
+
+        let mut bytes = to_bytes(&G1Affine::rand(&mut rng));
+        assert!(GsCommitmentG1::<F>::commit(&crs, &bytes[..bytes.len() - 1], &r).is_err());
+        bytes.push(0);
+        assert!(GsCommitmentG1::<F>::commit(&crs, &bytes, &r).is_err());
+        // A G1 element is not a G2 element
+        assert!(GsCommitmentG2::<F>::commit(&crs, &bytes[..bytes.len() - 1], &r).is_err());
+    }
+}
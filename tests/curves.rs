@@ -0,0 +1,365 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod SXDH_curve_tests {
+
+    use ark_ec::pairing::{Pairing, PairingOutput};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+    use ark_std::{rand::Rng, test_rng};
+
+    use groth_sahai::data_structures::*;
+    use groth_sahai::prelude::*;
+    use groth_sahai::wire::{self, Curve, WireError};
+
+    // The number of X and Y variables in the equations, all of whose terms are nonzero
+    const M: usize = 2;
+    const N: usize = 3;
+
+    fn rand_vec<T: UniformRand, R: Rng>(len: usize, rng: &mut R) -> Vec<T> {
+        (0..len).map(|_| T::rand(rng)).collect()
+    }
+
+    fn rand_gamma<E: Pairing, R: Rng>(rng: &mut R) -> Matrix<E::ScalarField> {
+        (0..M).map(|_| rand_vec(N, rng)).collect()
+    }
+
+    // e(A, Y) * e(X, B) * e(X, Γ Y) = t over random variables and constants
+    fn ppe<E: Pairing, R: Rng>(
+        xvars: &[E::G1Affine],
+        yvars: &[E::G2Affine],
+        rng: &mut R,
+    ) -> PPE<E> {
+        let a_consts: Vec<E::G1Affine> = rand_vec(yvars.len(), rng);
+        let b_consts: Vec<E::G2Affine> = rand_vec(xvars.len(), rng);
+        let gamma: Matrix<E::ScalarField> = (0..xvars.len())
+            .map(|_| rand_vec(yvars.len(), rng))
+            .collect();
+        let mut target = PairingOutput::<E>::zero();
+        for (a, y) in a_consts.iter().zip(yvars) {
+            target += E::pairing(*a, *y);
+        }
+        for (i, x) in xvars.iter().enumerate() {
+            target += E::pairing(*x, b_consts[i]);
+            for (j, y) in yvars.iter().enumerate() {
+                target += E::pairing(*x, *y) * gamma[i][j];
+            }
+        }
+        PPE::<E> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        }
+    }
+
+    fn ppe_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+        let xvars: Vec<E::G1Affine> = rand_vec(M, &mut rng);
+        let yvars: Vec<E::G2Affine> = rand_vec(N, &mut rng);
+        let equ = ppe::<E, _>(&xvars, &yvars, &mut rng);
+
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+        assert!(equ.verify_streaming(
+            &proof.equ_proofs[0],
+            proof.xcoms.coms.iter().copied(),
+            proof.ycoms.coms.iter().copied(),
+            &crs
+        ));
+
+        let bad_equ = PPE::<E> {
+            target: equ.target + crs.gt_gen,
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    fn linear_ppe_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+
+        let xvars: Vec<E::G1Affine> = rand_vec(M, &mut rng);
+        let yvars: Vec<E::G2Affine> = rand_vec(N, &mut rng);
+
+        // e(X, B) = t, with zero coefficients on Y, whose proof leaves out θ
+        let x_only = ppe::<E, _>(&xvars, &[], &mut rng);
+        let equ = PPE::<E> {
+            a_consts: vec![E::G1Affine::zero(); N],
+            gamma: vec![vec![E::ScalarField::zero(); N]; M],
+            ..x_only
+        };
+        assert!(equ.is_linear());
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(proof.equ_proofs[0].theta().is_empty());
+        assert!(equ.verify(&proof, &crs));
+
+        // e(A, Y) = t, with zero coefficients on X, whose proof leaves out π
+        let y_only = ppe::<E, _>(&[], &yvars, &mut rng);
+        let equ = PPE::<E> {
+            b_consts: vec![E::G2Affine::zero(); M],
+            gamma: vec![vec![E::ScalarField::zero(); N]; M],
+            ..y_only
+        };
+        assert!(equ.is_linear());
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(proof.equ_proofs[0].pi().is_empty());
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    fn msmeg1_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+        let xvars: Vec<E::G1Affine> = rand_vec(M, &mut rng);
+        let scalar_yvars: Vec<E::ScalarField> = rand_vec(N, &mut rng);
+        let a_consts: Vec<E::G1Affine> = rand_vec(N, &mut rng);
+        let b_consts: Vec<E::ScalarField> = rand_vec(M, &mut rng);
+        let gamma = rand_gamma::<E, _>(&mut rng);
+
+        // A y + X b + X Γ y = t
+        let mut target = E::G1::zero();
+        for j in 0..N {
+            target += a_consts[j] * scalar_yvars[j];
+        }
+        for i in 0..M {
+            target += xvars[i] * b_consts[i];
+            for j in 0..N {
+                target += xvars[i] * (gamma[i][j] * scalar_yvars[j]);
+            }
+        }
+        let equ = MSMEG1::<E> {
+            a_consts,
+            b_consts,
+            gamma,
+            target: target.into_affine(),
+        };
+        let proof = equ.commit_and_prove(&xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        let bad_equ = MSMEG1::<E> {
+            target: (equ.target + crs.g1_gen).into_affine(),
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    fn msmeg2_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+        let scalar_xvars: Vec<E::ScalarField> = rand_vec(M, &mut rng);
+        let yvars: Vec<E::G2Affine> = rand_vec(N, &mut rng);
+        let a_consts: Vec<E::ScalarField> = rand_vec(N, &mut rng);
+        let b_consts: Vec<E::G2Affine> = rand_vec(M, &mut rng);
+        let gamma = rand_gamma::<E, _>(&mut rng);
+
+        // a Y + x B + x Γ Y = t
+        let mut target = E::G2::zero();
+        for j in 0..N {
+            target += yvars[j] * a_consts[j];
+        }
+        for i in 0..M {
+            target += b_consts[i] * scalar_xvars[i];
+            for j in 0..N {
+                target += yvars[j] * (scalar_xvars[i] * gamma[i][j]);
+            }
+        }
+        let equ = MSMEG2::<E> {
+            a_consts,
+            b_consts,
+            gamma,
+            target: target.into_affine(),
+        };
+        let proof = equ.commit_and_prove(&scalar_xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        let bad_equ = MSMEG2::<E> {
+            target: (equ.target + crs.g2_gen).into_affine(),
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    fn quad_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+        let scalar_xvars: Vec<E::ScalarField> = rand_vec(M, &mut rng);
+        let scalar_yvars: Vec<E::ScalarField> = rand_vec(N, &mut rng);
+        let a_consts: Vec<E::ScalarField> = rand_vec(N, &mut rng);
+        let b_consts: Vec<E::ScalarField> = rand_vec(M, &mut rng);
+        let gamma = rand_gamma::<E, _>(&mut rng);
+
+        // a y + x b + x Γ y = t
+        let mut target = E::ScalarField::zero();
+        for j in 0..N {
+            target += a_consts[j] * scalar_yvars[j];
+        }
+        for i in 0..M {
+            target += scalar_xvars[i] * b_consts[i];
+            for j in 0..N {
+                target += scalar_xvars[i] * gamma[i][j] * scalar_yvars[j];
+            }
+        }
+        let equ = QuadEqu::<E> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        let proof = equ.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        let bad_equ = QuadEqu::<E> {
+            target: equ.target + E::ScalarField::one(),
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    fn zero_points_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+
+        // Variables and constants at infinity, so that every pairing is trivial
+        let xvars = vec![E::G1Affine::zero(); M];
+        let yvars = vec![E::G2Affine::zero(); N];
+        let equ = PPE::<E> {
+            a_consts: vec![E::G1Affine::zero(); N],
+            b_consts: vec![E::G2Affine::zero(); M],
+            gamma: rand_gamma::<E, _>(&mut rng),
+            target: PairingOutput::<E>::zero(),
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        // A zero scalar variable
+        let equ = QuadEqu::<E>::scalar_equality();
+        let zero = [E::ScalarField::zero()];
+        let proof = equ.commit_and_prove(&zero, &zero, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    fn serialization_roundtrip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::generate_crs(&mut rng);
+        let xvars: Vec<E::G1Affine> = rand_vec(M, &mut rng);
+        let yvars: Vec<E::G2Affine> = rand_vec(N, &mut rng);
+        let equ = ppe::<E, _>(&xvars, &yvars, &mut rng);
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            crs.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), crs.serialized_size(compress));
+            let crs_de =
+                CRS::<E>::deserialize_with_mode(&bytes[..], compress, Validate::Yes).unwrap();
+            assert_eq!(crs_de.fingerprint(), crs.fingerprint());
+
+            let mut bytes = Vec::new();
+            proof.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), proof.serialized_size(compress));
+            let proof_de =
+                CProof::<E>::deserialize_with_mode(&bytes[..], compress, Validate::Yes).unwrap();
+            assert_eq!(proof_de, proof);
+            assert!(equ.verify(&proof_de, &crs_de));
+
+            // The commitment groups are pairs of group elements, whatever their size on the curve
+            let com1 = proof.xcoms.coms[0];
+            assert_eq!(
+                com1.serialized_size(compress),
+                2 * E::G1Affine::generator().serialized_size(compress)
+            );
+            let comt = ComT::<E>::pairing(com1, proof.ycoms.coms[0]);
+            assert_eq!(
+                ComT::<E>::from_bytes(&comt.to_bytes(compress), compress).unwrap(),
+                comt
+            );
+        }
+
+        // The wire format only identifies some curves
+        match Curve::of::<E>() {
+            Some(_) => {
+                let bytes = wire::encode_proof(&proof).unwrap();
+                assert_eq!(
+                    wire::decode_proof::<E>(&bytes).unwrap(),
+                    proof.without_randomness()
+                );
+            }
+            None => assert_eq!(
+                wire::encode_proof(&proof).unwrap_err(),
+                WireError::UnsupportedCurve
+            ),
+        }
+    }
+
+    fn crs_self_test<E: Pairing>() {
+        let mut rng = test_rng();
+        assert!(CRS::<E>::self_test(&mut rng));
+
+        // Over the curve's standard generators rather than sampled ones
+        let crs = CRS::<E>::generate_crs_with_generators(
+            E::G1Affine::generator(),
+            E::G2Affine::generator(),
+            &mut rng,
+        );
+        let xvars: Vec<E::G1Affine> = rand_vec(M, &mut rng);
+        let yvars: Vec<E::G2Affine> = rand_vec(N, &mut rng);
+        let equ = ppe::<E, _>(&xvars, &yvars, &mut rng);
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    macro_rules! curve_tests {
+        ($($curve:ident: $E:ty),*) => {
+            $(
+                mod $curve {
+                    #[test]
+                    fn ppe_roundtrip() {
+                        super::ppe_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn linear_ppe_roundtrip() {
+                        super::linear_ppe_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn msmeg1_roundtrip() {
+                        super::msmeg1_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn msmeg2_roundtrip() {
+                        super::msmeg2_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn quad_roundtrip() {
+                        super::quad_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn zero_points_roundtrip() {
+                        super::zero_points_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn serialization_roundtrip() {
+                        super::serialization_roundtrip::<$E>();
+                    }
+
+                    #[test]
+                    fn crs_self_test() {
+                        super::crs_self_test::<$E>();
+                    }
+                }
+            )*
+        }
+    }
+    curve_tests!(
+        bls12_381: ark_bls12_381::Bls12_381,
+        bn254: ark_bn254::Bn254,
+        bls12_377: ark_bls12_377::Bls12_377
+    );
+}
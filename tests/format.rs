@@ -0,0 +1,247 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod SXDH_format_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::{Pairing, PairingOutput};
+    use ark_ec::AffineRepr;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::{UniformRand, Zero};
+
+    use groth_sahai::data_structures::{
+        UnsupportedVersion, FORMAT_MAGIC, FORMAT_VERSION, MAX_SERIALIZED_LEN,
+    };
+    use groth_sahai::prelude::*;
+    use groth_sahai::verifier::ProofBundle;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+    type GT = PairingOutput<F>;
+
+    // Compressed serializations of the CRS and (randomness-free) proof of `fixture`, in each format version
+    const CRS_V0: &[u8] = include_bytes!("fixtures/crs_v0.bin");
+    const PROOF_V0: &[u8] = include_bytes!("fixtures/proof_v0.bin");
+    const CRS_V1: &[u8] = include_bytes!("fixtures/crs_v1.bin");
+    const PROOF_V1: &[u8] = include_bytes!("fixtures/proof_v1.bin");
+
+    // e(A_1, Y_1) * e(X_1, B_1) * e(X_1, Y_1)^3 = t, and a proof for it
+    fn fixture() -> (CRS<F>, PPE<F>, CProof<F>) {
+        let mut rng = StdRng::seed_from_u64(1143);
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let a = G1Affine::rand(&mut rng);
+        let b = G2Affine::rand(&mut rng);
+        let equ = PPE::<F> {
+            a_consts: vec![a],
+            b_consts: vec![b],
+            gamma: vec![vec![Fr::from(3u64)]],
+            target: F::pairing(xvars[0], yvars[0]) * Fr::from(3u64)
+                + F::pairing(a, yvars[0])
+                + F::pairing(xvars[0], b),
+        };
+        let proof = equ
+            .commit_and_prove(&xvars, &yvars, &crs, &mut rng)
+            .without_randomness();
+        (crs, equ, proof)
+    }
+
+    // The magic and the current version
+    fn header() -> Vec<u8> {
+        [&FORMAT_MAGIC[..], &[FORMAT_VERSION]].concat()
+    }
+
+    fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        value.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    // The version 0 encoding of an equation, i.e. its fields as `Vec` and `Vec<Vec<_>>`
+    fn ppe_v0(equ: &PPE<F>) -> Vec<u8> {
+        [
+            serialize(&equ.a_consts),
+            serialize(&equ.b_consts),
+            serialize(&equ.gamma),
+            serialize(&equ.target),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn current_version_matches_fixtures() {
+        let (crs, equ, proof) = fixture();
+        assert_eq!(FORMAT_VERSION, 1);
+        assert_eq!(serialize(&crs), CRS_V1);
+        assert_eq!(serialize(&proof), PROOF_V1);
+        assert_eq!(CRS_V1[..9], header());
+
+        let crs = CRS::<F>::deserialize_compressed(CRS_V1).unwrap();
+        let proof = CProof::<F>::deserialize_compressed(PROOF_V1).unwrap();
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn unversioned_fixtures_still_deserialize() {
+        let (crs, equ, proof) = fixture();
+        let crs_v0 = CRS::<F>::deserialize_compressed(CRS_V0).unwrap();
+        let proof_v0 = CProof::<F>::deserialize_compressed(PROOF_V0).unwrap();
+        assert_eq!(crs_v0.fingerprint(), crs.fingerprint());
+        assert_eq!(proof_v0, proof);
+        assert!(equ.verify(&proof_v0, &crs_v0));
+
+        // And re-serialize in the current version
+        assert_eq!(serialize(&crs_v0), CRS_V1);
+        assert_eq!(serialize(&proof_v0), PROOF_V1);
+    }
+
+    #[test]
+    fn uncompressed_unchecked_round_trip() {
+        let (crs, equ, proof) = fixture();
+        let mut crs_bytes = Vec::new();
+        crs.serialize_uncompressed(&mut crs_bytes).unwrap();
+        let mut proof_bytes = Vec::new();
+        proof.serialize_uncompressed(&mut proof_bytes).unwrap();
+        // Both coordinates of every point, behind the same version header
+        assert!(crs_bytes.len() > CRS_V1.len());
+        assert!(proof_bytes.len() > PROOF_V1.len());
+        assert_eq!(crs_bytes[..9], header());
+
+        let crs_checked = CRS::<F>::deserialize_uncompressed(&crs_bytes[..]).unwrap();
+        let crs_unchecked = CRS::<F>::deserialize_uncompressed_unchecked(&crs_bytes[..]).unwrap();
+        assert_eq!(crs_checked.fingerprint(), crs.fingerprint());
+        assert_eq!(crs_unchecked.fingerprint(), crs.fingerprint());
+        let proof_unchecked =
+            CProof::<F>::deserialize_uncompressed_unchecked(&proof_bytes[..]).unwrap();
+        assert_eq!(proof_unchecked, proof);
+        assert!(equ.verify(&proof_unchecked, &crs_unchecked));
+
+        let equ_proof = &proof.equ_proofs[0];
+        let mut bytes = Vec::new();
+        equ_proof.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(
+            &EquProof::<F>::deserialize_uncompressed_unchecked(&bytes[..]).unwrap(),
+            equ_proof
+        );
+    }
+
+    #[test]
+    fn unsupported_version_is_reported() {
+        let mut bytes = CRS_V1.to_vec();
+        bytes[8] = 7;
+        let err = CRS::<F>::deserialize_compressed(&bytes[..]).unwrap_err();
+        let SerializationError::IoError(err) = err else {
+            panic!("expected an I/O error, found {:?}", err);
+        };
+        assert_eq!(err.to_string(), UnsupportedVersion { found: 7 }.to_string());
+
+        let mut bytes = serialize(&fixture().2.equ_proofs[0]);
+        bytes[8] = 2;
+        assert!(EquProof::<F>::deserialize_compressed(&bytes[..]).is_err());
+
+        let (_, equ, proof) = fixture();
+        let mut bytes = serialize(&ProofBundle::<F, PPE<F>>::new(equ, &proof));
+        bytes[8] = 2;
+        let err = ProofBundle::<F, PPE<F>>::deserialize_compressed(&bytes[..]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            SerializationError::from(UnsupportedVersion { found: 2 }).to_string()
+        );
+    }
+
+    #[test]
+    fn proof_bundle_is_versioned() {
+        let (_, equ, proof) = fixture();
+        let bundle = ProofBundle::<F, PPE<F>>::new(equ.clone(), &proof);
+        let bytes = serialize(&bundle);
+        assert_eq!(bytes[..9], header());
+        assert_eq!(bytes[9..], [serialize(&equ), serialize(&proof)].concat());
+        assert_eq!(
+            ProofBundle::<F, PPE<F>>::deserialize_compressed(&bytes[..]).unwrap(),
+            bundle
+        );
+        // A bundle without the version header is of version 0
+        let bytes_v0 = [ppe_v0(&equ), PROOF_V0.to_vec()].concat();
+        assert_eq!(
+            ProofBundle::<F, PPE<F>>::deserialize_compressed(&bytes_v0[..]).unwrap(),
+            bundle
+        );
+    }
+
+    #[test]
+    fn unversioned_bundles_with_long_vectors_deserialize() {
+        // The first byte of a version 0 bundle is the low byte of its first vector's length, which
+        // used to be mistaken for a version byte from 128 entries on
+        let (_, _, proof) = fixture();
+        for n in [128, 129] {
+            let equ = PPE::<F> {
+                a_consts: vec![G1Affine::zero(); n],
+                b_consts: vec![],
+                gamma: vec![],
+                target: GT::zero(),
+            };
+            let bundle = ProofBundle::<F, PPE<F>>::new(equ.clone(), &proof);
+            let bytes_v0 = [ppe_v0(&equ), PROOF_V0.to_vec()].concat();
+            assert_eq!(bytes_v0[0], n as u8);
+            assert_eq!(
+                ProofBundle::<F, PPE<F>>::deserialize_compressed(&bytes_v0[..]).unwrap(),
+                bundle
+            );
+            assert_eq!(
+                ProofBundle::<F, PPE<F>>::deserialize_compressed(&serialize(&bundle)[..]).unwrap(),
+                bundle
+            );
+        }
+    }
+
+    #[test]
+    fn absurd_lengths_are_rejected_before_allocating() {
+        // A version 0 proof whose x commitments claim u64::MAX entries, which `Vec::with_capacity`
+        // would fail to allocate
+        let bytes = u64::MAX.to_le_bytes();
+        assert!(CProof::<F>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(PPE::<F>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(CRS::<F>::deserialize_compressed(&bytes[..]).is_err());
+
+        // And the same in the current version, whose lengths are u32
+        let versioned = |fields: &[&[u8]]| [&header()[..], &fields.concat()].concat();
+        let max = u32::MAX.to_le_bytes();
+        let bytes = versioned(&[&header(), &max]);
+        assert!(CProof::<F>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(PPE::<F>::deserialize_compressed(&versioned(&[&max])[..]).is_err());
+        // π has at most two elements
+        let three = 3u32.to_le_bytes();
+        assert!(EquProof::<F>::deserialize_compressed(&versioned(&[&three])[..]).is_err());
+
+        // A length below the bound only allocates as its entries are read, so a short input fails
+        let many = (MAX_SERIALIZED_LEN as u32).to_le_bytes();
+        assert!(PPE::<F>::deserialize_compressed(&versioned(&[&many])[..]).is_err());
+    }
+
+    #[test]
+    fn matrix_dimensions_are_checked() {
+        let (_, equ, _) = fixture();
+        // Γ has a column per constant in A
+        let wide = PPE::<F> {
+            gamma: vec![vec![Fr::from(3u64), Fr::from(1u64)]],
+            ..equ.clone()
+        };
+        assert!(PPE::<F>::deserialize_compressed(&serialize(&wide)[..]).is_err());
+        assert!(PPE::<F>::deserialize_compressed(&ppe_v0(&wide)[..]).is_err());
+        // and a row per constant in B
+        let tall = PPE::<F> {
+            gamma: vec![vec![Fr::from(3u64)], vec![Fr::from(1u64)]],
+            ..equ.clone()
+        };
+        assert!(PPE::<F>::deserialize_compressed(&serialize(&tall)[..]).is_err());
+        assert!(PPE::<F>::deserialize_compressed(&ppe_v0(&tall)[..]).is_err());
+
+        assert_eq!(
+            PPE::<F>::deserialize_compressed(&ppe_v0(&equ)[..]).unwrap(),
+            equ
+        );
+    }
+}
@@ -0,0 +1,517 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod SXDH_gadget_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::hashing::{
+        curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+    };
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::field_hashers::DefaultFieldHasher;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::ops::Mul;
+    use ark_std::{test_rng, UniformRand};
+
+    use groth_sahai::gadgets::bit::{self, BitProof};
+    use groth_sahai::gadgets::bls::{self, PossessionProof};
+    use groth_sahai::gadgets::dh_tuple::{self, DhTupleProof};
+    use groth_sahai::gadgets::elgamal::{self, Ciphertext};
+    use groth_sahai::gadgets::linear;
+    use groth_sahai::gadgets::scalar_link;
+    use groth_sahai::gadgets::sps::{self, Signature, SignatureProof};
+    use groth_sahai::gadgets::venc;
+    use groth_sahai::prelude::*;
+    use groth_sahai::prover::{prove_gt_exponent, prove_scalar_equality};
+    use groth_sahai::verifier::verify_gt_exponent;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+
+    // H(m) in G1, hashed to the curve as in the BLS signature standard
+    fn hash_to_G1(msg: &[u8]) -> G1Affine {
+        MapToCurveBasedHasher::<
+            <F as Pairing>::G1,
+            DefaultFieldHasher<sha2::Sha256, 128>,
+            WBMap<ark_bls12_381::g1::Config>,
+        >::new(b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_")
+        .unwrap()
+        .hash(msg)
+        .unwrap()
+    }
+
+    #[test]
+    fn dh_tuple_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (g1, g2) = (G1Affine::rand(&mut rng), G2Affine::rand(&mut rng));
+        let x = Fr::rand(&mut rng);
+        let (u, v) = (g1.mul(x).into_affine(), g2.mul(x).into_affine());
+
+        let proof = dh_tuple::prove(&x, &g1, &u, &g2, &v, &crs, &mut rng);
+        assert!(dh_tuple::verify(&g1, &u, &g2, &v, &proof, &crs));
+        // The proof is for this tuple only
+        assert!(!dh_tuple::verify(
+            &g1,
+            &u,
+            &g2,
+            &(v + g2).into_affine(),
+            &proof,
+            &crs
+        ));
+        assert!(!dh_tuple::verify(
+            &g1,
+            &u,
+            &g2,
+            &v,
+            &proof,
+            &CRS::<F>::generate_crs(&mut rng)
+        ));
+    }
+
+    #[test]
+    fn dh_tuple_with_mismatched_exponents_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (g1, g2) = (G1Affine::rand(&mut rng), G2Affine::rand(&mut rng));
+        let (x, y) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let (u, v) = (g1.mul(x).into_affine(), g2.mul(y).into_affine());
+
+        // Proving with either exponent fails one of the two multi-scalar equations
+        let proof = dh_tuple::prove(&x, &g1, &u, &g2, &v, &crs, &mut rng);
+        assert!(!dh_tuple::verify(&g1, &u, &g2, &v, &proof, &crs));
+        let proof = dh_tuple::prove(&y, &g1, &u, &g2, &v, &crs, &mut rng);
+        assert!(!dh_tuple::verify(&g1, &u, &g2, &v, &proof, &crs));
+    }
+
+    #[test]
+    fn dh_tuple_proof_serializes() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+        let x = Fr::rand(&mut rng);
+        let (u, v) = (g1.mul(x).into_affine(), g2.mul(x).into_affine());
+        let proof = dh_tuple::prove(&x, &g1, &u, &g2, &v, &crs, &mut rng);
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), proof.compressed_size());
+        let decoded = DhTupleProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(dh_tuple::verify(&g1, &u, &g2, &v, &decoded, &crs));
+    }
+
+    #[test]
+    fn bls_signature_possession_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // A BLS keypair and signature sigma = sk H(m), with pk = sk g2
+        let sk = Fr::rand(&mut rng);
+        let pk = G2Affine::generator().mul(sk).into_affine();
+        let msg_hash = hash_to_G1(b"message");
+        let sig = msg_hash.mul(sk).into_affine();
+        assert_eq!(
+            F::pairing(sig, G2Affine::generator()),
+            F::pairing(msg_hash, pk)
+        );
+
+        let bundle = bls::prove_possession(sig, msg_hash, pk, &crs, &mut rng);
+        assert!(bls::verify_possession(msg_hash, pk, &bundle, &crs));
+        // The proof is for this message and key only
+        assert!(!bls::verify_possession(
+            hash_to_G1(b"other message"),
+            pk,
+            &bundle,
+            &crs
+        ));
+        let other_pk = G2Affine::generator().mul(Fr::rand(&mut rng)).into_affine();
+        assert!(!bls::verify_possession(msg_hash, other_pk, &bundle, &crs));
+
+        let mut bytes = Vec::new();
+        bundle.serialize_compressed(&mut bytes).unwrap();
+        let decoded = PossessionProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(bls::verify_possession(msg_hash, pk, &decoded, &crs));
+    }
+
+    #[test]
+    fn invalid_bls_signature_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let sk = Fr::rand(&mut rng);
+        let pk = G2Affine::generator().mul(sk).into_affine();
+        let msg_hash = hash_to_G1(b"message");
+
+        // A signature under another key, and one on another message
+        let forged = msg_hash.mul(Fr::rand(&mut rng)).into_affine();
+        let bundle = bls::prove_possession(forged, msg_hash, pk, &crs, &mut rng);
+        assert!(!bls::verify_possession(msg_hash, pk, &bundle, &crs));
+        let other_sig = hash_to_G1(b"other message").mul(sk).into_affine();
+        let bundle = bls::prove_possession(other_sig, msg_hash, pk, &crs, &mut rng);
+        assert!(!bls::verify_possession(msg_hash, pk, &bundle, &crs));
+    }
+
+    #[test]
+    fn sps_signature_knowledge_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (sk, vk) = sps::keygen::<F, _>(3, &mut rng);
+        let msg: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+        let sig = sps::sign(&sk, &msg, &mut rng);
+        assert!(sps::verify(&vk, &msg, &sig));
+        for (equ, target) in vk.equations().iter().zip([
+            F::pairing(G1Affine::generator(), vk.z),
+            F::pairing(G1Affine::generator(), G2Affine::generator()),
+        ]) {
+            assert_eq!(equ.target, target);
+        }
+
+        let msg_coms: Commit1<F> = batch_commit_G1(&msg, &crs, &mut rng);
+        let proof = sps::prove_signature_knowledge(&vk, &msg, &msg_coms, &sig, &crs, &mut rng);
+        let public_coms = Commit1::<F>::from_coms(msg_coms.coms.clone());
+        assert!(sps::verify_signature_knowledge(
+            &vk,
+            &public_coms,
+            &proof,
+            &crs
+        ));
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        let decoded = SignatureProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(sps::verify_signature_knowledge(
+            &vk,
+            &public_coms,
+            &decoded,
+            &crs
+        ));
+
+        // The proof is for the committed message and the key only
+        let other_msg: Commit1<F> = batch_commit_G1(&msg, &crs, &mut rng);
+        assert!(!sps::verify_signature_knowledge(
+            &vk, &other_msg, &proof, &crs
+        ));
+        let (_, other_vk) = sps::keygen::<F, _>(3, &mut rng);
+        assert!(!sps::verify_signature_knowledge(
+            &other_vk,
+            &public_coms,
+            &proof,
+            &crs
+        ));
+    }
+
+    #[test]
+    fn forged_sps_signature_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (sk, vk) = sps::keygen::<F, _>(2, &mut rng);
+        let msg: Vec<G1Affine> = (0..2).map(|_| G1Affine::rand(&mut rng)).collect();
+        let msg_coms: Commit1<F> = batch_commit_G1(&msg, &crs, &mut rng);
+
+        // A signature on another message, and one with a mismatched T
+        let other_msg: Vec<G1Affine> = (0..2).map(|_| G1Affine::rand(&mut rng)).collect();
+        let sig = sps::sign(&sk, &other_msg, &mut rng);
+        let honest = sps::sign(&sk, &msg, &mut rng);
+        let forged = Signature::<F> {
+            t: G2Affine::rand(&mut rng),
+            ..honest
+        };
+        for sig in [sig, forged] {
+            assert!(!sps::verify(&vk, &msg, &sig));
+            let proof = sps::prove_signature_knowledge(&vk, &msg, &msg_coms, &sig, &crs, &mut rng);
+            assert!(!sps::verify_signature_knowledge(
+                &vk, &msg_coms, &proof, &crs
+            ));
+        }
+    }
+
+    #[test]
+    fn linked_scalars_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // x is the second scalar committed in B1 and the first committed in B2
+        let (a, x, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let com_b1: Commit1<F> = batch_commit_scalar_to_B1(&[a, x], &crs, &mut rng);
+        let com_b2: Commit2<F> = batch_commit_scalar_to_B2(&[x, b], &crs, &mut rng);
+
+        let proof = scalar_link::prove_same_scalar(&x, &com_b1, &com_b2, 1, 0, &crs, &mut rng);
+        let (pub_b1, pub_b2) = (
+            Commit1::<F>::from_coms(com_b1.coms.clone()),
+            Commit2::<F>::from_coms(com_b2.coms.clone()),
+        );
+        assert!(scalar_link::verify_same_scalar(
+            &pub_b1, &pub_b2, 1, 0, &proof, &crs
+        ));
+        // The proof is for these positions only
+        assert!(!scalar_link::verify_same_scalar(
+            &pub_b1, &pub_b2, 0, 0, &proof, &crs
+        ));
+        assert!(!scalar_link::verify_same_scalar(
+            &pub_b1, &pub_b2, 1, 1, &proof, &crs
+        ));
+        assert!(!scalar_link::verify_same_scalar(
+            &pub_b1, &pub_b2, 2, 0, &proof, &crs
+        ));
+    }
+
+    #[test]
+    fn unlinked_scalars_are_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let (x, y) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let com_b1: Commit1<F> = commit_scalar_to_B1(&x, &crs, &mut rng);
+        let com_b2: Commit2<F> = commit_scalar_to_B2(&y, &crs, &mut rng);
+        for z in [x, y] {
+            let proof = scalar_link::prove_same_scalar(&z, &com_b1, &com_b2, 0, 0, &crs, &mut rng);
+            assert!(!scalar_link::verify_same_scalar(
+                &com_b1, &com_b2, 0, 0, &proof, &crs
+            ));
+        }
+    }
+
+    #[test]
+    fn linked_scalar_composes_with_a_pairing_product_equation() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // x is committed in B1 (e.g. by another party's protocol) and in B2, where it is used as the
+        // exponent of e(g, h)^x = t, whose reduction includes a pairing-product equation
+        let x = Fr::rand(&mut rng);
+        let com_b1: Commit1<F> = commit_scalar_to_B1(&x, &crs, &mut rng);
+        let com_b2: Commit2<F> = commit_scalar_to_B2(&x, &crs, &mut rng);
+        let (g, h) = (G1Affine::rand(&mut rng), G2Affine::rand(&mut rng));
+        let target = F::pairing(g, h) * x;
+
+        let link = scalar_link::prove_same_scalar(&x, &com_b1, &com_b2, 0, 0, &crs, &mut rng);
+        let exponent = prove_gt_exponent(&g, &h, &target, &x, &com_b2, &crs, &mut rng);
+        assert!(scalar_link::verify_same_scalar(
+            &com_b1, &com_b2, 0, 0, &link, &crs
+        ));
+        assert!(verify_gt_exponent(
+            &g, &h, &target, &com_b2, &exponent, &crs
+        ));
+
+        // So the scalar behind com_b1 is the exponent of t, which another exponent is not
+        let other_target = F::pairing(g, h) * (x + x);
+        let exponent = prove_gt_exponent(&g, &h, &other_target, &x, &com_b2, &crs, &mut rng);
+        assert!(!verify_gt_exponent(
+            &g,
+            &h,
+            &other_target,
+            &com_b2,
+            &exponent,
+            &crs
+        ));
+    }
+
+    #[test]
+    fn committed_bits_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        for b in [false, true] {
+            let (com1, com2, proof) = bit::prove(b, &crs, &mut rng);
+            // The verifier only sees the commitments, not their randomness
+            let (com1, com2) = (
+                Commit1::<F>::from_coms(com1.coms),
+                Commit2::<F>::from_coms(com2.coms),
+            );
+            assert!(bit::verify(&com1, &com2, &proof, &crs));
+
+            let mut bytes = Vec::new();
+            proof.serialize_compressed(&mut bytes).unwrap();
+            let decoded = BitProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+            assert!(bit::verify(&com1, &com2, &decoded, &crs));
+        }
+    }
+
+    #[test]
+    fn committed_non_bit_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // A cheating prover runs the same steps as bit::prove for b = 2
+        let b = Fr::from(2u64);
+        let com1 = commit_scalar_to_B1(&b, &crs, &mut rng);
+        let com2 = commit_scalar_to_B2(&b, &crs, &mut rng);
+        let proof = BitProof::<F> {
+            bit_proof: QuadEqu::<F>::bit()
+                .prove(&[b], &[b], &com1, &com2, &crs, &mut rng)
+                .without_randomness(),
+            equality_proof: prove_scalar_equality(&com1, &com2, &b, &crs, &mut rng)
+                .without_randomness(),
+        };
+        assert!(!bit::verify(&com1, &com2, &proof, &crs));
+
+        // Nor does a bit proof verify for commitments to different bits
+        let (com1, _, proof) = bit::prove(false, &crs, &mut rng);
+        let (_, com2, _) = bit::prove(true, &crs, &mut rng);
+        assert!(!bit::verify(&com1, &com2, &proof, &crs));
+    }
+
+    #[test]
+    fn linear_relations_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // Three amounts that stay within a budget of 100
+        let amounts: Vec<Fr> = [20u64, 30, 50].into_iter().map(Fr::from).collect();
+        let coms = batch_commit_scalar_to_B2(&amounts, &crs, &mut rng);
+        let public = Commit2::<F>::from_coms(coms.coms.clone());
+
+        let ones = vec![Fr::from(1u64); 3];
+        let sum = linear::prove_relation(&ones, &amounts, &coms, Fr::from(100u64), &crs, &mut rng);
+        assert!(linear::verify_relation(
+            &ones,
+            Fr::from(100u64),
+            &public,
+            &sum,
+            &crs
+        ));
+
+        // The same commitments in another relation: 2 y_1 + y_2 - y_3 = 20
+        let coeffs = vec![Fr::from(2u64), Fr::from(1u64), -Fr::from(1u64)];
+        let proof =
+            linear::prove_relation(&coeffs, &amounts, &coms, Fr::from(20u64), &crs, &mut rng);
+        assert!(linear::verify_relation(
+            &coeffs,
+            Fr::from(20u64),
+            &public,
+            &proof,
+            &crs
+        ));
+
+        // Each proof is for its own relation only
+        assert!(!linear::verify_relation(
+            &coeffs,
+            Fr::from(100u64),
+            &public,
+            &sum,
+            &crs
+        ));
+        assert!(!linear::verify_relation(
+            &ones,
+            Fr::from(20u64),
+            &public,
+            &proof,
+            &crs
+        ));
+        assert!(!linear::verify_relation(
+            &ones[..2],
+            Fr::from(100u64),
+            &public,
+            &sum,
+            &crs
+        ));
+    }
+
+    #[test]
+    fn unsatisfied_linear_relation_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let amounts: Vec<Fr> = [20u64, 30, 51].into_iter().map(Fr::from).collect();
+        let coms = batch_commit_scalar_to_B2(&amounts, &crs, &mut rng);
+        let ones = vec![Fr::from(1u64); 3];
+        let proof =
+            linear::prove_relation(&ones, &amounts, &coms, Fr::from(100u64), &crs, &mut rng);
+        assert!(!linear::verify_relation(
+            &ones,
+            Fr::from(100u64),
+            &coms,
+            &proof,
+            &crs
+        ));
+    }
+
+    #[test]
+    fn elgamal_plaintext_equality_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let pk = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let m = G1Affine::rand(&mut rng);
+        let r = Fr::rand(&mut rng);
+        let cipher = Ciphertext::<F>::encrypt(&m, &r, &pk, &crs);
+        let m_com = commit_G1(&m, &crs, &mut rng);
+
+        let proof = elgamal::prove_plaintext_equality(&m, &r, &pk, &cipher, &m_com, &crs, &mut rng);
+        let public = Commit1::<F>::from_coms(m_com.coms.clone());
+        assert!(elgamal::verify_plaintext_equality(
+            &pk, &cipher, &public, &proof, &crs
+        ));
+        // The proof is for this ciphertext and key only
+        let other_pk = G1Affine::rand(&mut rng);
+        assert!(!elgamal::verify_plaintext_equality(
+            &other_pk, &cipher, &public, &proof, &crs
+        ));
+        let other = Ciphertext::<F>::encrypt(&m, &Fr::rand(&mut rng), &pk, &crs);
+        assert!(!elgamal::verify_plaintext_equality(
+            &pk, &other, &public, &proof, &crs
+        ));
+    }
+
+    #[test]
+    fn elgamal_plaintext_mismatch_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let pk = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let (m, other_m) = (G1Affine::rand(&mut rng), G1Affine::rand(&mut rng));
+        let r = Fr::rand(&mut rng);
+        let cipher = Ciphertext::<F>::encrypt(&other_m, &r, &pk, &crs);
+        let m_com = commit_G1(&m, &crs, &mut rng);
+
+        let proof = elgamal::prove_plaintext_equality(&m, &r, &pk, &cipher, &m_com, &crs, &mut rng);
+        assert!(!elgamal::verify_plaintext_equality(
+            &pk, &cipher, &m_com, &proof, &crs
+        ));
+    }
+
+    #[test]
+    fn verifiable_encryption_decrypts_to_the_committed_element() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let sk = Fr::rand(&mut rng);
+        let pk = crs.g1_gen.mul(sk).into_affine();
+        let m = G1Affine::rand(&mut rng);
+        let (cipher, m_com, proof) = venc::encrypt_and_prove(&m, &pk, &crs, &mut rng);
+
+        let public = Commit1::<F>::from_coms(m_com.coms.clone());
+        assert!(venc::verify(&pk, &cipher, &public, &proof, &crs));
+        // The opener recovers the committed element
+        assert_eq!(venc::decrypt(&sk, &cipher), m);
+        assert_ne!(venc::decrypt(&Fr::rand(&mut rng), &cipher), m);
+    }
+
+    #[test]
+    fn verifiable_encryption_with_swapped_ciphertext_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let pk = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let m = G1Affine::rand(&mut rng);
+        let (cipher, m_com, proof) = venc::encrypt_and_prove(&m, &pk, &crs, &mut rng);
+        let (other, _, _) = venc::encrypt_and_prove(&m, &pk, &crs, &mut rng);
+
+        // Another encryption of the same element, or with its components swapped
+        assert!(!venc::verify(&pk, &other, &m_com, &proof, &crs));
+        let swapped = Ciphertext::<F> {
+            c1: cipher.c2,
+            c2: cipher.c1,
+        };
+        assert!(!venc::verify(&pk, &swapped, &m_com, &proof, &crs));
+        assert!(venc::verify(&pk, &cipher, &m_com, &proof, &crs));
+    }
+}
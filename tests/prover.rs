@@ -6,15 +6,19 @@ mod SXDH_prover_tests {
     use ark_bls12_381::Bls12_381 as F;
     use ark_ec::pairing::{Pairing, PairingOutput};
     use ark_ec::{AffineRepr, CurveGroup};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use ark_std::ops::Mul;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
     use ark_std::str::FromStr;
-    use ark_std::{test_rng, UniformRand, Zero};
+    use ark_std::{test_rng, One, UniformRand, Zero};
 
     use groth_sahai::data_structures::*;
+    use groth_sahai::prelude::*;
     use groth_sahai::prover::*;
-    use groth_sahai::statement::*;
-    use groth_sahai::verifier::Verifiable;
-    use groth_sahai::{AbstractCrs, CRS};
+    use groth_sahai::statement::AggregatePPE;
+    use groth_sahai::verifier::{
+        verify_gt_exponent, verify_scalar_equality, ProofBundle, VerificationError, VerifyOptions,
+    };
 
     type G1Affine = <F as Pairing>::G1Affine;
     type G2Affine = <F as Pairing>::G2Affine;
@@ -45,7 +49,8 @@ mod SXDH_prover_tests {
             crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
         ];
         // Gamma = [ 5, 0 ] (i.e. only e(X_1, Y_1)^5 term)
-        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
         // Target -> all together (n.b. e(X_1, Y_1)^5 = e(X_1, 5 Y_1) = e(5 X_1, Y_1) by the properties of non-degenerate bilinear maps)
         let target: GT = F::pairing(xvars[1], b_consts[1])
             + F::pairing(a_consts[0], yvars[0])
@@ -61,6 +66,1062 @@ mod SXDH_prover_tests {
         assert!(equ.verify(&proof, &crs));
     }
 
+    #[test]
+    fn pairing_product_equation_consuming_verification_agrees() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^3 * e(X_2, Y_2) * e(X_2, Y_1)^2 = t for random variables
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng), G2Affine::rand(&mut rng)];
+        let gamma = Matrix::<Fr>::from_rows(vec![
+            vec![Fr::from(3u64), Fr::zero()],
+            vec![Fr::from(2u64), Fr::one()],
+        ]);
+        let target: GT = F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine())
+            + F::pairing(xvars[1], yvars[1])
+            + F::pairing(xvars[1], yvars[0].mul(gamma[1][0]).into_affine());
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero(); 2],
+            b_consts: vec![G2Affine::zero(); 2],
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+        assert!(equ.clone().verify_consuming(proof.clone(), &crs));
+
+        // Both reject a proof of a different target
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: equ.target + F::pairing(crs.g1_gen, crs.g2_gen),
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+        assert!(!bad_equ.verify_consuming(proof.clone(), &crs));
+
+        // And a malformed proof
+        let mut malformed = proof;
+        malformed.equ_proofs.clear();
+        assert!(!equ.verify(&malformed, &crs));
+        assert!(!equ.verify_consuming(malformed, &crs));
+    }
+
+    #[cfg(feature = "prf")]
+    #[test]
+    fn pairing_product_equation_deterministic_proof() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^3 * e(X_2, c_2) = t for random variables
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let b_consts: Vec<G2Affine> = vec![G2Affine::zero(), G2Affine::rand(&mut rng)];
+        let gamma = Matrix::<Fr>::from_rows(vec![vec![Fr::from(3u64)], vec![Fr::zero()]]);
+        let target: GT = F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine())
+            + F::pairing(xvars[1], b_consts[1]);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts,
+            gamma,
+            target,
+        };
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        // The same key, witness and statement give the same proof, which verifies
+        let equ_proof = equ.prove_deterministic(&xvars, &yvars, &xcoms, &ycoms, &crs, b"key");
+        assert_eq!(
+            equ_proof,
+            equ.prove_deterministic(&xvars, &yvars, &xcoms, &ycoms, &crs, b"key")
+        );
+        let proof = CProof::<F> {
+            xcoms: xcoms.clone(),
+            ycoms: ycoms.clone(),
+            equ_proofs: vec![equ_proof.clone()],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(equ.verify(&proof, &crs));
+
+        // A different key, or different commitments to the same variables, give a different proof
+        assert_ne!(
+            equ_proof,
+            equ.prove_deterministic(&xvars, &yvars, &xcoms, &ycoms, &crs, b"other key")
+        );
+        let other_xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        assert_ne!(
+            equ_proof,
+            equ.prove_deterministic(&xvars, &yvars, &other_xcoms, &ycoms, &crs, b"key")
+        );
+        // as does a different witness for the same statement
+        let other_xvars = vec![xvars[1], xvars[0]];
+        assert_ne!(
+            equ_proof,
+            equ.prove_deterministic(&other_xvars, &yvars, &xcoms, &ycoms, &crs, b"key")
+        );
+    }
+
+    #[test]
+    fn pairing_product_equation_permuted_variables_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^3 * e(X_2, Y_2)^2 * e(X_3, c_3) * e(c_2, Y_2) = t for random variables
+        let xvars: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+        let yvars: Vec<G2Affine> = (0..2).map(|_| G2Affine::rand(&mut rng)).collect();
+        let a_consts: Vec<G1Affine> = vec![G1Affine::zero(), G1Affine::rand(&mut rng)];
+        let b_consts: Vec<G2Affine> =
+            vec![G2Affine::zero(), G2Affine::zero(), G2Affine::rand(&mut rng)];
+        let gamma = Matrix::<Fr>::from_rows(vec![
+            vec![Fr::from(3u64), Fr::zero()],
+            vec![Fr::zero(), Fr::from(2u64)],
+            vec![Fr::zero(), Fr::zero()],
+        ]);
+        let target: GT = F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine())
+            + F::pairing(xvars[1], yvars[1].mul(gamma[1][1]).into_affine())
+            + F::pairing(xvars[2], b_consts[2])
+            + F::pairing(a_consts[1], yvars[1]);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        // Reorder the variables, and the rows (resp. columns) of gamma and constants paired with them
+        let (xperm, yperm) = ([2, 0, 1], [1, 0]);
+        fn permute<T: Copy>(vars: &[T], perm: &[usize]) -> Vec<T> {
+            perm.iter().map(|&i| vars[i]).collect()
+        }
+        let permuted_equ: PPE<F> = PPE::<F> {
+            a_consts: permute(&equ.a_consts, &yperm),
+            b_consts: permute(&equ.b_consts, &xperm),
+            gamma: equ
+                .gamma
+                .permute_rows(&xperm)
+                .and_then(|gamma| gamma.permute_cols(&yperm))
+                .unwrap(),
+            target: equ.target,
+        };
+        let permuted_xvars: Vec<G1Affine> = permute(&xvars, &xperm);
+        let permuted_yvars: Vec<G2Affine> = permute(&yvars, &yperm);
+        let (permuted_xcoms, permuted_ycoms) = (xcoms.permute(&xperm), ycoms.permute(&yperm));
+
+        for (equ, xvars, yvars, xcoms, ycoms) in [
+            (&equ, &xvars, &yvars, xcoms, ycoms),
+            (
+                &permuted_equ,
+                &permuted_xvars,
+                &permuted_yvars,
+                permuted_xcoms,
+                permuted_ycoms,
+            ),
+        ] {
+            let equ_proof = equ.prove(xvars, yvars, &xcoms, &ycoms, &crs, &mut rng);
+            let proof = CProof::<F> {
+                xcoms,
+                ycoms,
+                equ_proofs: vec![equ_proof],
+                crs_fingerprint: crs.fingerprint(),
+            };
+            assert!(equ.verify(&proof, &crs));
+        }
+    }
+
+    #[test]
+    fn pairing_product_equation_verification_matrix() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_2, c_2) * e(c_1, Y_1) * e(X_1, Y_1)^5 = t, as in pairing_product_equation_verifies
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let a_consts: Vec<G1Affine> = vec![G1Affine::rand(&mut rng)];
+        let b_consts: Vec<G2Affine> = vec![G2Affine::zero(), G2Affine::rand(&mut rng)];
+        let gamma = Matrix::<Fr>::from_rows(vec![vec![Fr::from(5u64)], vec![Fr::zero()]]);
+        let target: GT = F::pairing(xvars[1], b_consts[1])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+
+        let (lhs, rhs) =
+            equ.verification_matrix(&proof.equ_proofs[0], &proof.xcoms, &proof.ycoms, &crs);
+        assert_eq!((lhs.len(), lhs[0].len()), (1, 1));
+        assert_eq!(lhs, rhs);
+        // The same identity that verify checks, up to moving the proof terms to the lhs
+        let (acc, lin_t) = equ.verification_terms(&proof, &crs).unwrap();
+        assert_eq!(rhs[0][0], lin_t + (lhs[0][0] - acc));
+
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: equ.target + crs.gt_gen,
+            ..equ.clone()
+        };
+        let (lhs, rhs) =
+            bad_equ.verification_matrix(&proof.equ_proofs[0], &proof.xcoms, &proof.ycoms, &crs);
+        assert_ne!(lhs, rhs);
+        // Only the entry of the target differs
+        assert_eq!(
+            rhs[0][0] - lhs[0][0],
+            ComT::<F>::linear_map_PPE(&crs.gt_gen)
+        );
+    }
+
+    #[test]
+    fn prepared_verification_agrees() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let other_crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^3 * e(X_2, Y_1) = t for random variables
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let gamma = Matrix::<Fr>::from_rows(vec![vec![Fr::from(3u64)], vec![Fr::one()]]);
+        let ppe: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero(); 2],
+            target: F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine())
+                + F::pairing(xvars[1], yvars[0]),
+            gamma,
+        };
+        let proof: CProof<F> = ppe.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        let prepared = proof.equ_proofs[0].prepare_g2(&crs);
+        assert!(ppe.verify(&proof, &crs));
+        assert!(ppe.verify_prepared(&proof, &prepared, &crs));
+        // The prepared proof can be reused
+        assert!(ppe.verify_prepared(&proof, &prepared, &crs));
+
+        // Both reject a different target
+        let bad_ppe: PPE<F> = PPE::<F> {
+            target: ppe.target + crs.gt_gen,
+            ..ppe.clone()
+        };
+        assert!(!bad_ppe.verify(&proof, &crs));
+        assert!(!bad_ppe.verify_prepared(&proof, &prepared, &crs));
+
+        // A proof prepared with another CRS is rejected
+        let misprepared = proof.equ_proofs[0].prepare_g2(&other_crs);
+        assert!(!ppe.verify_prepared(&proof, &misprepared, &crs));
+
+        // A linear equation's proof leaves out theta: e(X_1, c_1) = t
+        let b_consts = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let linear: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: b_consts.clone(),
+            gamma: vec![vec![Fr::zero()]],
+            target: F::pairing(xvars[0], b_consts[0]),
+        };
+        let proof: CProof<F> = linear.commit_and_prove(&xvars[..1], &yvars, &crs, &mut rng);
+        assert!(proof.equ_proofs[0].theta.is_empty());
+        let prepared = proof.equ_proofs[0].prepare_g2(&crs);
+        assert!(linear.verify(&proof, &crs));
+        assert!(linear.verify_prepared(&proof, &prepared, &crs));
+
+        // An MSMEG1 equation y_1 * X_1 = t, which rejects a prepared PPE proof
+        let scalar_yvars = vec![Fr::rand(&mut rng)];
+        let msmeg1: MSMEG1<F> = MSMEG1::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![Fr::zero()],
+            gamma: Matrix::<Fr>::from_rows(vec![vec![Fr::one()]]),
+            target: xvars[0].mul(scalar_yvars[0]).into_affine(),
+        };
+        let proof: CProof<F> = msmeg1.commit_and_prove(&xvars[..1], &scalar_yvars, &crs, &mut rng);
+        assert!(msmeg1.verify(&proof, &crs));
+        assert!(msmeg1.verify_prepared(&proof, &proof.equ_proofs[0].prepare_g2(&crs), &crs));
+        assert!(!msmeg1.verify_prepared(&proof, &prepared, &crs));
+
+        // A quadratic equation x_1 * y_1 = t
+        let scalar_xvars = vec![Fr::rand(&mut rng)];
+        let quad: QuadEqu<F> = QuadEqu::<F> {
+            a_consts: vec![Fr::zero()],
+            b_consts: vec![Fr::zero()],
+            gamma: Matrix::<Fr>::from_rows(vec![vec![Fr::one()]]),
+            target: scalar_xvars[0] * scalar_yvars[0],
+        };
+        let proof: CProof<F> = quad.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(quad.verify(&proof, &crs));
+        assert!(quad.verify_prepared(&proof, &proof.equ_proofs[0].prepare_g2(&crs), &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_verifies_with_padded_commitments() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_2, c_2) * e(c_1, Y_1) * e(X_1, Y_1)^5 = t, as in pairing_product_equation_verifies
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine(),
+            crs.g1_gen.mul(Fr::from_str("3").unwrap()).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::from_str("4").unwrap()).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            G2Affine::zero(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
+        let target: GT = F::pairing(xvars[1], b_consts[1])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let mut padded_xcoms = xcoms.clone();
+        padded_xcoms.pad_to(3);
+        let mut padded_ycoms = ycoms.clone();
+        padded_ycoms.pad_to(2);
+        assert_eq!(padded_xcoms.coms[..2], xcoms.coms[..]);
+        assert_eq!(padded_xcoms.coms[2], Com1::<F>::zero());
+        assert_eq!(padded_ycoms.coms[..1], ycoms.coms[..]);
+        assert_eq!(padded_ycoms.coms[1], Com2::<F>::zero());
+
+        // Padding to a shorter length does nothing
+        let mut unchanged = padded_xcoms.clone();
+        unchanged.pad_to(1);
+        assert_eq!(unchanged, padded_xcoms);
+
+        // The equation extended with zero coefficients for the padding is the same equation
+        let padded_equ: PPE<F> = PPE::<F> {
+            a_consts: vec![a_consts[0], G1Affine::zero()],
+            b_consts: vec![b_consts[0], b_consts[1], G2Affine::zero()],
+            gamma: Matrix::<Fr>::from_rows(vec![
+                vec![gamma[0][0], Fr::zero()],
+                vec![gamma[1][0], Fr::zero()],
+                vec![Fr::zero(), Fr::zero()],
+            ]),
+            target,
+        };
+        let padded_xvars: Vec<G1Affine> = vec![xvars[0], xvars[1], G1Affine::zero()];
+        let padded_yvars: Vec<G2Affine> = vec![yvars[0], G2Affine::zero()];
+        let proof: CProof<F> = CProof::<F> {
+            xcoms: padded_xcoms.clone(),
+            ycoms: padded_ycoms.clone(),
+            equ_proofs: vec![padded_equ.prove(
+                &padded_xvars,
+                &padded_yvars,
+                &padded_xcoms,
+                &padded_ycoms,
+                &crs,
+                &mut rng,
+            )],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(padded_equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn empty_pairing_product_equation_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // The fully-empty equation (no variables, no constants, 0 x 0 gamma) is trivially 1 = 1
+        let xvars: Vec<G1Affine> = vec![];
+        let yvars: Vec<G2Affine> = vec![];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![],
+            b_consts: vec![],
+            gamma: vec![],
+            target: GT::zero(),
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(proof.xcoms.coms.is_empty());
+        assert!(proof.ycoms.coms.is_empty());
+        // As a linear equation without variables, its proof is empty
+        assert!(proof.equ_proofs[0].pi.is_empty());
+        assert!(proof.equ_proofs[0].theta.is_empty());
+        assert!(equ.verify(&proof, &crs));
+
+        // But it cannot prove a non-trivial target
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: F::pairing(crs.g1_gen, crs.g2_gen),
+            ..equ
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn linear_pairing_product_equation_has_smaller_proof() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, c_1) * e(X_2, c_2) = t, with a linear equation in X variables only
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let b_consts: Vec<G2Affine> = vec![G2Affine::rand(&mut rng), G2Affine::rand(&mut rng)];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            target: F::pairing(xvars[0], b_consts[0]) + F::pairing(xvars[1], b_consts[1]),
+            b_consts,
+            gamma: vec![vec![Fr::zero()]; 2],
+        };
+        assert!(equ.is_linear());
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert_eq!(proof.equ_proofs[0].pi.len(), 2);
+        assert!(proof.equ_proofs[0].theta.is_empty());
+        assert!(equ.verify(&proof, &crs));
+
+        // The same equation with a (trivial) pairing of X_1 and Y_1 has a full proof
+        let quad_equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: equ.b_consts.clone(),
+            gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+            target: equ.target + F::pairing(xvars[0], yvars[0]),
+        };
+        assert!(!quad_equ.is_linear());
+        let quad_proof: CProof<F> = quad_equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(quad_equ.verify(&quad_proof, &crs));
+        assert!(proof.equ_proofs[0].compressed_size() < quad_proof.equ_proofs[0].compressed_size());
+
+        // A linear equation in Y variables only leaves out pi instead
+        let a_consts: Vec<G1Affine> = vec![G1Affine::rand(&mut rng)];
+        let y_equ: PPE<F> = PPE::<F> {
+            target: F::pairing(a_consts[0], yvars[0]),
+            a_consts,
+            b_consts: vec![G2Affine::zero(); 2],
+            gamma: vec![vec![Fr::zero()]; 2],
+        };
+        let y_proof: CProof<F> = y_equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(y_proof.equ_proofs[0].pi.is_empty());
+        assert_eq!(y_proof.equ_proofs[0].theta.len(), 2);
+        assert!(y_equ.verify(&y_proof, &crs));
+
+        // The reduced proof is still sound: it does not verify a different target
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: equ.target + F::pairing(crs.g1_gen, crs.g2_gen),
+            ..equ
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_malformed_proof_is_rejected() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, c_2) * e(c_1, Y_1) = t
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::from_str("4").unwrap()).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let gamma = Matrix::<Fr>::from_rows(vec![vec![Fr::zero()]]);
+        let target: GT = F::pairing(xvars[0], b_consts[0]) + F::pairing(a_consts[0], yvars[0]);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert_eq!(equ.verify_detailed(&proof, &crs), Ok(true));
+
+        // A missing x commitment is reported instead of panicking
+        let mut missing_xcom = proof.clone();
+        missing_xcom.xcoms.coms.pop();
+        assert_eq!(
+            equ.verify_detailed(&missing_xcom, &crs),
+            Err(VerificationError::PairingSum(PairingSumError {
+                x_len: 0,
+                y_len: 1
+            }))
+        );
+        assert!(!equ.verify(&missing_xcom, &crs));
+
+        // As is a truncated proof, whose shape is checked before any pairing is computed
+        let mut missing_pi = proof.clone();
+        missing_pi.equ_proofs[0].pi.pop();
+        assert!(!missing_pi.equ_proofs[0].check_shape(&equ));
+        assert_eq!(
+            equ.verify_detailed(&missing_pi, &crs),
+            Err(VerificationError::ProofShape(ShapeError {
+                expected: (2, 1)
+            }))
+        );
+        assert!(!equ.verify(&missing_pi, &crs));
+
+        // As is a proof without any equation proofs
+        let mut no_equ_proof = proof;
+        no_equ_proof.equ_proofs.clear();
+        assert_eq!(
+            equ.verify_detailed(&no_equ_proof, &crs),
+            Err(VerificationError::ProofCount(0))
+        );
+        assert!(!equ.verify(&no_equ_proof, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_proof_shape_is_checked() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, c) = t is linear in only x variables, so its proof leaves out theta
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let linear: PPE<F> = PPE::<F> {
+            a_consts: vec![],
+            b_consts: vec![c],
+            gamma: vec![vec![]],
+            target: F::pairing(xvars[0], c),
+        };
+        let proof: CProof<F> = linear.commit_and_prove(&xvars, &[], &crs, &mut rng);
+        assert!(proof.equ_proofs[0].theta().is_empty());
+        assert!(proof.equ_proofs[0].check_shape(&linear));
+        assert!(linear.verify(&proof, &crs));
+
+        // The same proof is misshapen for an equation that is not linear
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let quadratic: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![c],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], c) + F::pairing(xvars[0], yvars[0]),
+        };
+        assert!(!proof.equ_proofs[0].check_shape(&quadratic));
+        let mut com_proof = quadratic.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(com_proof.equ_proofs[0].check_shape(&quadratic));
+        com_proof.equ_proofs[0] = proof.equ_proofs[0].clone();
+        assert_eq!(
+            quadratic.verify_detailed(&com_proof, &crs),
+            Err(VerificationError::ProofShape(ShapeError {
+                expected: (2, 1)
+            }))
+        );
+
+        // A proof of another type of equation never has the shape of a PPE proof
+        let msmeg1: MSMEG1<F> = MSMEG1::<F> {
+            a_consts: vec![],
+            b_consts: vec![Fr::one()],
+            gamma: vec![vec![]],
+            target: xvars[0],
+        };
+        let other = msmeg1.commit_and_prove(&xvars, &[], &crs, &mut rng);
+        assert!(!other.equ_proofs[0].check_shape(&quadratic));
+    }
+
+    #[test]
+    fn failures_convert_into_the_crate_error() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1) = t
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.try_verify(&proof, &crs).is_ok());
+
+        // A well-formed proof of another statement fails verification
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: equ.target + crs.pt(),
+            ..equ.clone()
+        };
+        let err = bad_equ.try_verify(&proof, &crs).unwrap_err();
+        assert!(matches!(err, groth_sahai::Error::VerificationFailed));
+        assert_eq!(err.to_string(), "the proof does not verify");
+
+        // A malformed proof keeps its reason
+        let mut no_equ_proof = proof.clone();
+        no_equ_proof.equ_proofs.clear();
+        let err = equ.try_verify(&no_equ_proof, &crs).unwrap_err();
+        assert!(matches!(
+            err,
+            groth_sahai::Error::Verification(VerificationError::ProofCount(0))
+        ));
+        assert_eq!(
+            err.to_string(),
+            "malformed proof: expected exactly 1 equation proof, found 0"
+        );
+
+        // As do commitment and CRS errors
+        let err: groth_sahai::Error =
+            try_batch_commit_G1_with_public_zeros(&xvars, &[true], &crs, &mut rng)
+                .unwrap_err()
+                .into();
+        assert!(matches!(
+            err,
+            groth_sahai::Error::Commit(CommitError::NotIdentity(0))
+        ));
+        let err: groth_sahai::Error =
+            CRS::<F>::try_generate_crs_with_generators(crs.g1_gen, G2Affine::zero(), &mut rng)
+                .unwrap_err()
+                .into();
+        assert_eq!(
+            err.to_string(),
+            "invalid CRS: a bilinear group generator is the identity"
+        );
+    }
+
+    #[test]
+    fn pairing_product_equation_with_constant_commitment_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1) * e(X_2, Y_1) = t, where X_2 is a publicly-known constant
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero(), G2Affine::zero()],
+            gamma: vec![vec![Fr::one()], vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]) + F::pairing(xvars[1], yvars[0]),
+        };
+
+        let mut xcoms: Commit1<F> = commit_G1(&xvars[0], &crs, &mut rng);
+        let mut const_com: Commit1<F> = commit_G1_const(&xvars[1]);
+        // The constant's commitment can be recomputed by anyone
+        assert_eq!(const_com.coms, vec![Com1::<F>::linear_map(&xvars[1])]);
+        xcoms.append(&mut const_com);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        let equ_proof = equ.prove(&xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        let proof = CProof::<F> {
+            xcoms,
+            ycoms,
+            equ_proofs: vec![equ_proof],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_with_public_zero_commitment_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1) * e(X_2, c_2) = t, where X_1 is publicly known to be the identity, i.e. the
+        // [O, X_2] pattern of variables mirroring constants B = [O, c_2]
+        let xvars: Vec<G1Affine> = vec![
+            G1Affine::zero(),
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            G2Affine::zero(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            target: F::pairing(xvars[1], b_consts[1]),
+            b_consts,
+            gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+        };
+
+        let xcoms: Commit1<F> = batch_commit_G1_with_public_zeros(
+            &xvars,
+            &[true, false],
+            &crs,
+            &mut StdRng::seed_from_u64(1137),
+        );
+        // The identity is committed without randomness, which is only drawn for X_2
+        assert_eq!(xcoms.coms[0], Com1::<F>::zero());
+        let hidden: Commit1<F> =
+            batch_commit_G1(&xvars[1..], &crs, &mut StdRng::seed_from_u64(1137));
+        assert_eq!(xcoms.coms[1..], hidden.coms);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        let equ_proof = equ.prove(&xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        let proof = CProof::<F> {
+            xcoms,
+            ycoms,
+            equ_proofs: vec![equ_proof],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_with_committed_constant_verifies_once_opened() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(A_1, Y_1) * e(X_1, B_1) * e(X_1, Y_1)^2 = t, where B_1 is only revealed when verifying
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let a = G1Affine::rand(&mut rng);
+        let b = G2Affine::rand(&mut rng);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![a],
+            b_consts: vec![b],
+            gamma: vec![vec![Fr::from(2u64)]],
+            target: F::pairing(a, yvars[0])
+                + F::pairing(xvars[0], b)
+                + F::pairing(xvars[0], yvars[0]) * Fr::from(2u64),
+        };
+
+        // B_1 is proven about as the last Y variable, so the committed equation does not contain it
+        let committed_equ = equ.with_committed_b_const(0);
+        assert_eq!(committed_equ.b_consts, vec![G2Affine::zero()]);
+        assert_eq!(committed_equ.gamma, vec![vec![Fr::from(2u64), Fr::one()]]);
+        let committed_yvars = vec![yvars[0], b];
+        let proof: CProof<F> =
+            committed_equ.commit_and_prove(&xvars, &committed_yvars, &crs, &mut rng);
+        assert!(committed_equ.verify(&proof, &crs));
+
+        // Revealing B_1 and its randomness opens the last commitment, but not to any other constant
+        let rand = proof.ycoms.randomness(1).unwrap();
+        assert!(verify_opening_G2(&proof.ycoms.coms[1], &b, rand, &crs));
+        let other = G2Affine::rand(&mut rng);
+        assert!(!verify_opening_G2(&proof.ycoms.coms[1], &other, rand, &crs));
+        assert!(!verify_opening_G2(
+            &proof.ycoms.coms[1],
+            &b,
+            &rand[..1],
+            &crs
+        ));
+        // A verifier's copy of the commitments has no randomness to reveal
+        assert!(Commit2::<F>::from_coms(proof.ycoms.coms.clone())
+            .randomness(1)
+            .is_none());
+
+        // Likewise for A_1 as the last X variable
+        let committed_equ = equ.with_committed_a_const(0);
+        assert_eq!(committed_equ.a_consts, vec![G1Affine::zero()]);
+        assert_eq!(
+            committed_equ.gamma,
+            vec![vec![Fr::from(2u64)], vec![Fr::one()]]
+        );
+        let committed_xvars = vec![xvars[0], a];
+        let proof: CProof<F> =
+            committed_equ.commit_and_prove(&committed_xvars, &yvars, &crs, &mut rng);
+        assert!(committed_equ.verify(&proof, &crs));
+        let rand = proof.xcoms.randomness(1).unwrap();
+        assert!(verify_opening_G1(&proof.xcoms.coms[1], &a, rand, &crs));
+        assert!(!verify_opening_G1(&proof.xcoms.coms[0], &a, rand, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equations_with_shared_witness_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // X_1 (and Y_1) appear in both e(X_1, Y_1) * e(X_2, c) = t_1 and e(X_1, Y_1)^2 = t_2
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let first: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero(), c],
+            gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+            target: F::pairing(xvars[0], yvars[0]) + F::pairing(xvars[1], c),
+        };
+        let second: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero(); 2],
+            gamma: vec![vec![Fr::from(2u64)], vec![Fr::zero()]],
+            target: F::pairing(xvars[0], yvars[0].mul(Fr::from(2u64)).into_affine()),
+        };
+
+        let secret = format!("{:?}", xvars[0]);
+        let witness = SharedWitness::<F>::commit(xvars, yvars, &crs, &mut rng);
+        // The variables are left out of the debug output
+        assert!(!format!("{:?}", witness).contains(&secret));
+        let first_proof = witness.prove_for(&first, &crs, &mut rng);
+        let second_proof = witness.prove_for(&second, &crs, &mut rng);
+        assert!(first.verify(&first_proof, &crs));
+        assert!(second.verify(&second_proof, &crs));
+
+        // Both proofs reference the identical commitments
+        assert_eq!(first_proof.xcoms.coms, witness.xcoms().coms);
+        assert_eq!(first_proof.xcoms.coms, second_proof.xcoms.coms);
+        assert_eq!(first_proof.ycoms.coms, second_proof.ycoms.coms);
+        // So the proof of one equation does not verify the other
+        assert!(!second.verify(&first_proof, &crs));
+    }
+
+    #[test]
+    fn aggregate_is_linear_combination_of_equations() {
+        let mut rng = test_rng();
+
+        let equs: Vec<PPE<F>> = (0..3)
+            .map(|_| PPE::<F> {
+                a_consts: vec![G1Affine::rand(&mut rng)],
+                b_consts: vec![G2Affine::rand(&mut rng); 2],
+                gamma: vec![vec![Fr::rand(&mut rng)], vec![Fr::rand(&mut rng)]],
+                target: PairingOutput::<F>::rand(&mut rng),
+            })
+            .collect();
+
+        // Aggregating with a unit vector selects a single equation
+        let selected = AggregatePPE::<F>::aggregate(&equs, &[Fr::zero(), Fr::one(), Fr::zero()]);
+        assert_eq!(selected, equs[1]);
+
+        let two = Fr::from(2u64);
+        let combined = AggregatePPE::<F>::aggregate(&equs, &[Fr::one(), two, Fr::zero()]);
+        assert_eq!(
+            combined.a_consts[0],
+            (equs[0].a_consts[0] + equs[1].a_consts[0].mul(two)).into_affine()
+        );
+        assert_eq!(
+            combined.gamma[1][0],
+            equs[0].gamma[1][0] + equs[1].gamma[1][0] * two
+        );
+        assert_eq!(combined.target, equs[0].target + equs[1].target * two);
+    }
+
+    #[cfg(feature = "prf")]
+    #[test]
+    fn aggregate_pairing_product_equation_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1) * e(X_2, c) = t_1, e(X_1, Y_1)^2 = t_2 and e(d, Y_1) * e(X_2, c)^3 = t_3
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let d = G1Affine::rand(&mut rng);
+        let equs: Vec<PPE<F>> = vec![
+            PPE::<F> {
+                a_consts: vec![G1Affine::zero()],
+                b_consts: vec![G2Affine::zero(), c],
+                gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+                target: F::pairing(xvars[0], yvars[0]) + F::pairing(xvars[1], c),
+            },
+            PPE::<F> {
+                a_consts: vec![G1Affine::zero()],
+                b_consts: vec![G2Affine::zero(); 2],
+                gamma: vec![vec![Fr::from(2u64)], vec![Fr::zero()]],
+                target: F::pairing(xvars[0], yvars[0]) * Fr::from(2u64),
+            },
+            PPE::<F> {
+                a_consts: vec![d],
+                b_consts: vec![G2Affine::zero(), c.mul(Fr::from(3u64)).into_affine()],
+                gamma: vec![vec![Fr::zero()]; 2],
+                target: F::pairing(d, yvars[0]) + F::pairing(xvars[1], c) * Fr::from(3u64),
+            },
+        ];
+
+        let aggregate = AggregatePPE::<F>::new(equs.clone());
+        let proof: CProof<F> = aggregate.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert_eq!(proof.equ_proofs.len(), 1);
+        assert!(aggregate.verify(&proof, &crs));
+        assert!(aggregate
+            .combined(&proof.xcoms, &proof.ycoms, &crs)
+            .verify(&proof, &crs));
+
+        // A single corrupted equation is caught, whether the prover or the verifier holds it
+        let mut bad_equs = equs;
+        bad_equs[1].target += F::pairing(crs.g1_gen, crs.g2_gen);
+        let bad_aggregate = AggregatePPE::<F>::new(bad_equs);
+        assert!(!bad_aggregate.verify(&proof, &crs));
+        let bad_proof: CProof<F> = bad_aggregate.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(!bad_aggregate.verify(&bad_proof, &crs));
+        assert!(!aggregate.verify(&bad_proof, &crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_streaming_verification_matches_buffered() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(A_1, Y_1) * e(A_2, Y_2) * e(X_1, B_1) * e(X_1, Y_2)^2 * e(X_2, Y_1)^5 * ... = t over 3 X and 2 Y
+        let m = 3;
+        let n = 2;
+        let xvars: Vec<G1Affine> = (0..m).map(|_| G1Affine::rand(&mut rng)).collect();
+        let yvars: Vec<G2Affine> = (0..n).map(|_| G2Affine::rand(&mut rng)).collect();
+        let a_consts: Vec<G1Affine> = (0..n).map(|_| G1Affine::rand(&mut rng)).collect();
+        let b_consts: Vec<G2Affine> = (0..m).map(|_| G2Affine::rand(&mut rng)).collect();
+        let gamma: Matrix<Fr> = (0..m)
+            .map(|_| (0..n).map(|_| Fr::rand(&mut rng)).collect())
+            .collect();
+        let mut target = GT::zero();
+        for j in 0..n {
+            target += F::pairing(a_consts[j], yvars[j]);
+        }
+        for i in 0..m {
+            target += F::pairing(xvars[i], b_consts[i]);
+            for j in 0..n {
+                target += F::pairing(xvars[i], yvars[j]) * gamma[i][j];
+            }
+        }
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        let streamed = |equ: &PPE<F>, proof: &CProof<F>| {
+            equ.verify_streaming(
+                &proof.equ_proofs[0],
+                proof.xcoms.coms.iter().copied(),
+                proof.ycoms.coms.iter().copied(),
+                &crs,
+            )
+        };
+        assert!(equ.verify(&proof, &crs));
+        assert!(streamed(&equ, &proof));
+
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: equ.target + crs.gt_gen,
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+        assert!(!streamed(&bad_equ, &proof));
+
+        // Too few or too many commitments
+        let xcoms = &proof.xcoms.coms;
+        let ycoms = &proof.ycoms.coms;
+        let equ_proof = &proof.equ_proofs[0];
+        assert!(!equ.verify_streaming(
+            equ_proof,
+            xcoms[1..].iter().copied(),
+            ycoms.iter().copied(),
+            &crs
+        ));
+        assert!(!equ.verify_streaming(
+            equ_proof,
+            xcoms.iter().chain(xcoms.iter()).copied(),
+            ycoms.iter().copied(),
+            &crs
+        ));
+        assert!(!equ.verify_streaming(
+            equ_proof,
+            xcoms.iter().copied(),
+            ycoms[1..].iter().copied(),
+            &crs
+        ));
+
+        // A linear equation in only y, whose proof leaves out pi
+        let lin_equ: PPE<F> = PPE::<F> {
+            a_consts: equ.a_consts.clone(),
+            b_consts: vec![],
+            gamma: vec![],
+            target: F::pairing(equ.a_consts[0], yvars[0]) + F::pairing(equ.a_consts[1], yvars[1]),
+        };
+        let lin_proof: CProof<F> = lin_equ.commit_and_prove(&[], &yvars, &crs, &mut rng);
+        assert!(lin_proof.equ_proofs[0].pi().is_empty());
+        assert!(lin_equ.verify(&lin_proof, &crs));
+        assert!(streamed(&lin_equ, &lin_proof));
+    }
+
+    #[test]
+    fn pairing_product_equation_bundle_round_trip_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1) = t
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+
+        let bundle = ProofBundle::<F, PPE<F>>::new(equ, &proof);
+        // Only the public parts of the proof are bundled
+        assert_ne!(bundle.proof, proof);
+        assert_eq!(bundle.proof.xcoms.coms, proof.xcoms.coms);
+
+        let mut bytes = Vec::new();
+        bundle.serialize_compressed(&mut bytes).unwrap();
+        let received = ProofBundle::<F, PPE<F>>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(received, bundle);
+        assert!(received.verify(&crs));
+    }
+
+    #[test]
+    fn pairing_product_equation_rejects_proof_from_other_crs() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let other_crs = CRS::<F>::generate_crs(&mut rng);
+        assert!(!crs.is_compatible(&other_crs));
+
+        // e(X_1, Y_1) = t
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+        assert!(!equ.verify(&proof, &other_crs));
+        assert_eq!(
+            equ.verify_detailed(&proof, &other_crs),
+            Err(VerificationError::CrsMismatch)
+        );
+    }
+
+    #[test]
+    fn product_group_variable_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // A variable P = (P_1, P_2) in G1 x G1 with linked components P_2 = k P_1, committed as the two
+        // consecutive X variables P_1 and P_2
+        let k = Fr::rand(&mut rng);
+        let p1 = G1Affine::rand(&mut rng);
+        let pair: Vec<G1Affine> = vec![p1, p1.mul(k).into_affine()];
+        let xcoms: Commit1<F> = batch_commit_G1(&pair, &crs, &mut rng);
+        let no_ycoms = Commit2::<F>::from_coms(vec![]);
+
+        // P_2 - k P_1 = 0 as a multi-scalar equation over both positions
+        let link: MSMEG1<F> = MSMEG1::<F> {
+            a_consts: vec![],
+            b_consts: vec![-k, Fr::one()],
+            gamma: vec![vec![], vec![]],
+            target: G1Affine::zero(),
+        };
+        // e(P, B) = t for B = (B_1, B_2) in G2 x G2, paired component-wise
+        let b: Vec<G2Affine> = vec![G2Affine::rand(&mut rng), G2Affine::rand(&mut rng)];
+        let pairing: PPE<F> = PPE::<F> {
+            a_consts: vec![],
+            b_consts: b.clone(),
+            gamma: vec![vec![], vec![]],
+            target: F::pairing(pair[0], b[0]) + F::pairing(pair[1], b[1]),
+        };
+
+        let public = CProof::<F> {
+            xcoms: Commit1::<F>::from_coms(xcoms.coms.clone()),
+            ycoms: no_ycoms.clone(),
+            equ_proofs: vec![],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        let link_proof = CProof::<F> {
+            equ_proofs: vec![link.prove(&pair, &[], &xcoms, &no_ycoms, &crs, &mut rng)],
+            ..public.clone()
+        };
+        let pairing_proof = CProof::<F> {
+            equ_proofs: vec![pairing.prove(&pair, &[], &xcoms, &no_ycoms, &crs, &mut rng)],
+            ..public.clone()
+        };
+        assert!(link.verify(&link_proof, &crs));
+        assert!(pairing.verify(&pairing_proof, &crs));
+
+        // Components that are not linked do not satisfy the relation
+        let unlinked: Vec<G1Affine> = vec![p1, G1Affine::rand(&mut rng)];
+        let xcoms: Commit1<F> = batch_commit_G1(&unlinked, &crs, &mut rng);
+        let bad_proof = CProof::<F> {
+            xcoms: xcoms.clone(),
+            ycoms: no_ycoms.clone(),
+            equ_proofs: vec![link.prove(&unlinked, &[], &xcoms, &no_ycoms, &crs, &mut rng)],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(!link.verify(&bad_proof, &crs));
+    }
+
     #[test]
     fn multi_scalar_mult_equation_G1_verifies() {
         let mut rng = test_rng();
@@ -82,7 +1143,8 @@ mod SXDH_prover_tests {
         // B = [ 0, c_2 ] (i.e. only c_2 * X_2 term in equation)
         let b_consts: Vec<Fr> = vec![Fr::zero(), Fr::rand(&mut rng)];
         // Gamma = [ 5, 0 ] (i.e. only (y_1 * X_1)*5 term)
-        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
         // Target -> all together
         let target: G1Affine = (xvars[1].mul(b_consts[1])
             + a_consts[0].mul(scalar_yvars[0])
@@ -99,6 +1161,146 @@ mod SXDH_prover_tests {
         assert!(equ.verify(&proof, &crs));
     }
 
+    #[test]
+    fn multi_scalar_mult_equation_G1_proof_from_parts_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // (y_1 * X_1)*5 = t, with X_1 = 2 g1 and y_1 = 4
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("4").unwrap()];
+        let gamma = Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()]]);
+        let target: G1Affine = xvars[0].mul(scalar_yvars[0] * gamma[0][0]).into_affine();
+        let equ: MSMEG1<F> = MSMEG1::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![Fr::zero()],
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &scalar_yvars, &crs, &mut rng);
+
+        // pi has two elements and theta one, as the y variables are scalars
+        let equ_proof = &proof.equ_proofs[0];
+        assert_eq!(equ_proof.pi().len(), 2);
+        assert_eq!(equ_proof.theta().len(), 1);
+
+        let rebuilt = EquProof::<F>::from_parts(
+            equ_proof.pi().to_vec(),
+            equ_proof.theta().to_vec(),
+            EquType::MultiScalarG1,
+        )
+        .unwrap();
+        let rebuilt_proof: CProof<F> = CProof::<F> {
+            equ_proofs: vec![rebuilt],
+            ..proof.without_randomness()
+        };
+        assert!(equ.verify(&rebuilt_proof, &crs));
+
+        // Parts of the wrong shape for the type of equation are rejected
+        assert_eq!(
+            EquProof::<F>::from_parts(
+                equ_proof.pi().to_vec(),
+                equ_proof.theta().to_vec(),
+                EquType::PairingProduct,
+            ),
+            Err(ShapeError { expected: (2, 1) })
+        );
+        assert_eq!(
+            EquProof::<F>::from_parts(
+                equ_proof.pi()[..1].to_vec(),
+                equ_proof.theta().to_vec(),
+                EquType::MultiScalarG1,
+            ),
+            Err(ShapeError { expected: (2, 1) })
+        );
+    }
+
+    #[test]
+    fn multi_scalar_mult_equation_G1_verifies_with_mixed_commit() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // An equation of the form c_2 * X_2 + (y_1 * X_1)*5 = t where X_1 = 2 g1 is a group variable and
+        // X_2 = x_2 g1 is given by the scalar x_2 = 3, and y_1 = 4
+        let group_vars: Vec<G1Affine> =
+            vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let scalar_vars: Vec<Fr> = vec![Fr::from_str("3").unwrap()];
+        let xcoms: Commit1<F> = commit_mixed_B1(
+            &group_vars,
+            &scalar_vars,
+            &[MixedVar::Group, MixedVar::Scalar],
+            &crs,
+            &mut rng,
+        );
+        let xvars: Vec<G1Affine> =
+            vec![group_vars[0], crs.g1_gen.mul(scalar_vars[0]).into_affine()];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("4").unwrap()];
+        let ycoms: Commit2<F> = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+
+        let b_consts: Vec<Fr> = vec![Fr::zero(), Fr::rand(&mut rng)];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
+        let target: G1Affine =
+            (xvars[1].mul(b_consts[1]) + xvars[0].mul(scalar_yvars[0] * gamma[0][0])).into_affine();
+        let equ: MSMEG1<F> = MSMEG1::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let proof: CProof<F> = CProof::<F> {
+            xcoms: xcoms.clone(),
+            ycoms: ycoms.clone(),
+            equ_proofs: vec![equ.prove(&xvars, &scalar_yvars, &xcoms, &ycoms, &crs, &mut rng)],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(equ.verify(&proof, &crs));
+    }
+
+    #[test]
+    fn multi_scalar_mult_equation_G1_lifted_to_PPE_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // Same equation as above: x_2 * c_2 + c_1 * y_1 + (X_1 * y_1)*5 = t
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine(),
+            crs.g1_gen.mul(Fr::from_str("3").unwrap()).into_affine(),
+        ];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("4").unwrap()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<Fr> = vec![Fr::zero(), Fr::rand(&mut rng)];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
+        let target: G1Affine = (xvars[1].mul(b_consts[1])
+            + a_consts[0].mul(scalar_yvars[0])
+            + xvars[0].mul(scalar_yvars[0] * gamma[0][0]))
+        .into_affine();
+        let equ: MSMEG1<F> = MSMEG1::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        // The lifted PPE is proven about the scalar variables embedded in G2, i.e. Y_1 = y_1 g2
+        let ppe: PPE<F> = equ.to_ppe(&crs);
+        assert_eq!(ppe.target, F::pairing(equ.target, crs.g2_gen));
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(scalar_yvars[0]).into_affine()];
+        let ppe_proof: CProof<F> = ppe.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(ppe.verify(&ppe_proof, &crs));
+
+        // A different witness does not satisfy the lifted equation
+        let bad_yvars: Vec<G2Affine> =
+            vec![crs.g2_gen.mul(Fr::from_str("5").unwrap()).into_affine()];
+        let bad_proof: CProof<F> = ppe.commit_and_prove(&xvars, &bad_yvars, &crs, &mut rng);
+        assert!(!ppe.verify(&bad_proof, &crs));
+    }
+
     #[test]
     fn multi_scalar_mult_equation_G2_verifies() {
         let mut rng = test_rng();
@@ -120,7 +1322,8 @@ mod SXDH_prover_tests {
             crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
         ];
         // Gamma = [ 5, 0 ] (i.e. only (x_1 * Y_1)*5 term)
-        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
         // Target -> all together
         let target: G2Affine = (b_consts[1].mul(scalar_xvars[1])
             + yvars[0].mul(a_consts[0])
@@ -137,6 +1340,68 @@ mod SXDH_prover_tests {
         assert!(equ.verify(&proof, &crs));
     }
 
+    #[test]
+    fn multi_scalar_mult_equation_G2_without_y_variables_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // x_1 * c_1 = t, where Gamma is a 1 x 0 matrix as there are no Y variables
+        let scalar_xvars: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let equ: MSMEG2<F> = MSMEG2::<F> {
+            a_consts: vec![],
+            b_consts: vec![c],
+            gamma: vec![vec![]],
+            target: c.mul(scalar_xvars[0]).into_affine(),
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &[], &crs, &mut rng);
+        assert_eq!(equ.verify_detailed(&proof, &crs), Ok(true));
+    }
+
+    #[test]
+    fn multi_scalar_mult_equation_G2_verifies_with_compact_commit() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // Same equation as above, but the scalar variables are committed in compact form:
+        // x_2 * c_2 + c_1 * Y_1 + (x_1 * Y_1)*5 = t
+        let scalar_xvars: Vec<Fr> = vec![Fr::from_str("2").unwrap(), Fr::from_str("3").unwrap()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::from_str("4").unwrap()).into_affine()];
+
+        let a_consts: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let b_consts: Vec<G2Affine> = vec![
+            G2Affine::zero(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
+        let target: G2Affine = (b_consts[1].mul(scalar_xvars[1])
+            + yvars[0].mul(a_consts[0])
+            + yvars[0].mul(scalar_xvars[0] * gamma[0][0]))
+        .into_affine();
+        let equ: MSMEG2<F> = MSMEG2::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let compact: CompactCommit1<F> =
+            batch_commit_scalar_to_B1_compact(&scalar_xvars, &crs, &mut rng);
+        let xcoms: Commit1<F> = compact.into();
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let equ_proof = equ.prove(&scalar_xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+
+        let proof = CProof::<F> {
+            xcoms,
+            ycoms,
+            equ_proofs: vec![equ_proof],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(equ.verify(&proof, &crs));
+    }
+
     #[test]
     fn quadratic_equation_verifies() {
         let mut rng = test_rng();
@@ -155,7 +1420,8 @@ mod SXDH_prover_tests {
         // B = [ 0, c_2 ] (i.e. only c_2 * x2 term in equation)
         let b_consts: Vec<Fr> = vec![Fr::zero(), Fr::rand(&mut rng)];
         // Gamma = [ 5, 0 ] (i.e. only (x_1 * y_1)*5 term)
-        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]];
+        let gamma =
+            Matrix::<Fr>::from_rows(vec![vec![Fr::from_str("5").unwrap()], vec![Fr::zero()]]);
         // Target -> all together
         let target: Fr = b_consts[1] * scalar_xvars[1]
             + scalar_yvars[0] * a_consts[0]
@@ -170,4 +1436,288 @@ mod SXDH_prover_tests {
         let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
         assert!(equ.verify(&proof, &crs));
     }
+
+    #[test]
+    fn scalar_equality_across_commitment_groups_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let scalar = Fr::rand(&mut rng);
+        let com1: Commit1<F> = commit_scalar_to_B1(&scalar, &crs, &mut rng);
+        let com2: Commit2<F> = commit_scalar_to_B2(&scalar, &crs, &mut rng);
+
+        let proof: EquProof<F> = prove_scalar_equality(&com1, &com2, &scalar, &crs, &mut rng);
+        assert_eq!(proof.equ_type, EquType::Quadratic);
+        assert!(verify_scalar_equality(&com1, &com2, &proof, &crs));
+
+        // Commitments to distinct scalars cannot be proven equal
+        let other = scalar + Fr::one();
+        let other_com2: Commit2<F> = commit_scalar_to_B2(&other, &crs, &mut rng);
+        let bad_proof: EquProof<F> =
+            prove_scalar_equality(&com1, &other_com2, &scalar, &crs, &mut rng);
+        assert!(!verify_scalar_equality(
+            &com1,
+            &other_com2,
+            &bad_proof,
+            &crs
+        ));
+        // nor does a proof carry over to other commitments
+        assert!(!verify_scalar_equality(&com1, &other_com2, &proof, &crs));
+    }
+
+    #[test]
+    fn gt_exponent_relation_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(g, h)^z = t for a hidden z
+        let (g, h) = (G1Affine::rand(&mut rng), G2Affine::rand(&mut rng));
+        let z = Fr::rand(&mut rng);
+        let target: GT = F::pairing(g, h) * z;
+        let z_com: Commit2<F> = commit_scalar_to_B2(&z, &crs, &mut rng);
+
+        let proof = prove_gt_exponent(&g, &h, &target, &z, &z_com, &crs, &mut rng);
+        assert!(verify_gt_exponent(&g, &h, &target, &z_com, &proof, &crs));
+        // The proof is for this target and base only
+        assert!(!verify_gt_exponent(
+            &g,
+            &h,
+            &(target + crs.pt()),
+            &z_com,
+            &proof,
+            &crs
+        ));
+        assert!(!verify_gt_exponent(
+            &crs.g1_gen,
+            &h,
+            &target,
+            &z_com,
+            &proof,
+            &crs
+        ));
+
+        // Nor can a commitment to another exponent be proven to satisfy the relation
+        let other = z + Fr::one();
+        let other_com: Commit2<F> = commit_scalar_to_B2(&other, &crs, &mut rng);
+        assert!(!verify_gt_exponent(
+            &g, &h, &target, &other_com, &proof, &crs
+        ));
+        let bad_proof = prove_gt_exponent(&g, &h, &target, &other, &other_com, &crs, &mut rng);
+        assert!(!verify_gt_exponent(
+            &g, &h, &target, &other_com, &bad_proof, &crs
+        ));
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn quadratic_equation_constant_time_verification_agrees() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // x_1 * y_1 = t where t = 2 * 4
+        let scalar_xvars: Vec<Fr> = vec![Fr::from_str("2").unwrap()];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("4").unwrap()];
+        let equ: QuadEqu<F> = QuadEqu::<F> {
+            a_consts: vec![Fr::zero()],
+            b_consts: vec![Fr::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: Fr::from_str("8").unwrap(),
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+        assert!(bool::from(equ.verify_ct(&proof, &crs)));
+
+        // An unsatisfied equation
+        let bad_equ: QuadEqu<F> = QuadEqu::<F> {
+            target: Fr::from_str("9").unwrap(),
+            ..equ.clone()
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+        assert!(!bool::from(bad_equ.verify_ct(&proof, &crs)));
+
+        // A malformed proof
+        let mut no_equ_proof = proof.clone();
+        no_equ_proof.equ_proofs.clear();
+        assert!(!equ.verify(&no_equ_proof, &crs));
+        assert!(!bool::from(equ.verify_ct(&no_equ_proof, &crs)));
+    }
+
+    #[test]
+    fn degenerate_commitments_are_rejected_under_the_option() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(a, Y_1) * e(X_1, b) * e(X_1, Y_1) = 1 holds for X_1 = 0 and Y_1 = 0
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::rand(&mut rng)],
+            b_consts: vec![G2Affine::rand(&mut rng)],
+            gamma: vec![vec![Fr::one()]],
+            target: GT::zero(),
+        };
+        // so all-zero commitments (i.e. zero openings with zero randomness) with an all-zero proof form a
+        // valid proof, which the option rejects as a matter of policy
+        let zero_opening = CProof::<F> {
+            xcoms: Commit1::<F>::from_coms(vec![Com1::<F>::zero()]),
+            ycoms: Commit2::<F>::from_coms(vec![Com2::<F>::zero()]),
+            equ_proofs: vec![EquProof::<F>::from_parts(
+                vec![Com2::<F>::zero(); 2],
+                vec![Com1::<F>::zero(); 2],
+                EquType::PairingProduct,
+            )
+            .unwrap()],
+            crs_fingerprint: crs.fingerprint(),
+        };
+        assert!(zero_opening.xcoms.is_degenerate() && zero_opening.ycoms.is_degenerate());
+        assert!(equ.verify(&zero_opening, &crs));
+        assert!(equ.verify_with_options(&zero_opening, &crs, &VerifyOptions::default()));
+
+        let strict = VerifyOptions {
+            reject_degenerate: true,
+            ..Default::default()
+        };
+        assert!(!equ.verify_with_options(&zero_opening, &crs, &strict));
+
+        // Honest commitments are not degenerate
+        let xvars = vec![G1Affine::zero()];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ = PPE::<F> {
+            target: F::pairing(equ.a_consts[0], yvars[0]),
+            ..equ
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(!proof.xcoms.is_degenerate());
+        assert!(equ.verify_with_options(&proof, &crs, &strict));
+    }
+
+    #[test]
+    fn subgroup_check_is_optional() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![],
+            b_consts: vec![c],
+            gamma: vec![vec![]],
+            target: F::pairing(xvars[0], c),
+        };
+        let proof = equ.commit_and_prove(&xvars, &[], &crs, &mut rng);
+        let strict = VerifyOptions {
+            check_subgroup: true,
+            ..Default::default()
+        };
+        assert!(equ.verify_with_options(&proof, &crs, &strict));
+
+        // A point on the curve, outside of the prime-order subgroup
+        let outside = (1u64..)
+            .filter_map(|x| G1Affine::get_point_from_x_unchecked(ark_bls12_381::Fq::from(x), false))
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut bad_proof = proof;
+        bad_proof.xcoms.coms[0].0 = outside;
+        assert!(!equ.verify_with_options(&bad_proof, &crs, &strict));
+        // A proof with such a point is rejected when deserialized with validation
+        let mut bytes = Vec::new();
+        bad_proof.serialize_compressed(&mut bytes).unwrap();
+        assert!(CProof::<F>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(CProof::<F>::deserialize_compressed_unchecked(&bytes[..]).is_ok());
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn constant_time_option_verifies_like_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        let ct = VerifyOptions {
+            constant_time: true,
+            ..Default::default()
+        };
+        assert!(equ.verify_with_options(&proof, &crs, &ct));
+
+        let bad_equ = PPE::<F> {
+            target: equ.target + crs.pt(),
+            ..equ
+        };
+        assert!(!bad_equ.verify_with_options(&proof, &crs, &ct));
+    }
+
+    #[cfg(not(feature = "subtle"))]
+    #[test]
+    fn constant_time_option_rejects_without_subtle() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: F::pairing(xvars[0], yvars[0]),
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify_with_options(&proof, &crs, &VerifyOptions::default()));
+        let ct = VerifyOptions {
+            constant_time: true,
+            ..Default::default()
+        };
+        assert!(!equ.verify_with_options(&proof, &crs, &ct));
+    }
+
+    #[test]
+    fn simulated_proof_verifies_under_hiding_crs() {
+        let mut rng = test_rng();
+        let (crs, trapdoor) = CRS::<F>::generate_hiding_crs(&mut rng);
+        assert!(trapdoor.is_for(&crs));
+        assert!(!trapdoor.is_for(&CRS::<F>::generate_crs(&mut rng)));
+
+        // e(-X_1, Y_1) * e(X_1, Y_1) = 1, for any X_1 and Y_1
+        let xvars = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ = PPE::<F> {
+            a_consts: vec![(-xvars[0].into_group()).into_affine()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: GT::zero(),
+        };
+        assert!(equ.verify(&equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng), &crs));
+
+        let mut sim_rng = StdRng::seed_from_u64(7);
+        let simulated = equ.simulate(&trapdoor, &crs, &mut sim_rng);
+        assert!(equ.verify(&simulated, &crs));
+
+        // The simulation is `prove_with_rand` on the identity with the simulation randomness
+        let mut sim_rng = StdRng::seed_from_u64(7);
+        let rand = equ.simulation_randomness(&trapdoor, &crs, &mut sim_rng);
+        let proof = equ.prove_with_rand(&[G1Affine::zero()], &[G2Affine::zero()], &rand, &crs);
+        assert_eq!(proof, simulated);
+
+        // With the same randomness, the real witness gives the same kind of proof
+        assert!(equ.verify(&equ.prove_with_rand(&xvars, &yvars, &rand, &crs), &crs));
+    }
+
+    #[test]
+    #[should_panic(expected = "target is not the identity")]
+    fn simulation_needs_identity_target() {
+        let mut rng = test_rng();
+        let (crs, trapdoor) = CRS::<F>::generate_hiding_crs(&mut rng);
+        let equ = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::one()]],
+            target: crs.gt_gen,
+        };
+        equ.simulate(&trapdoor, &crs, &mut rng);
+    }
 }
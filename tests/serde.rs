@@ -0,0 +1,223 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod SXDH_serde_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::{Pairing, PairingOutput};
+    use ark_ec::AffineRepr;
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_std::test_rng;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use groth_sahai::data_structures::*;
+    use groth_sahai::prelude::*;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+    type GT = PairingOutput<F>;
+
+    // Round-trips through a human-readable and a binary format
+    fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> (T, T) {
+        let json = serde_json::to_string(value).unwrap();
+        let bytes = bincode::serialize(value).unwrap();
+        (
+            serde_json::from_str(&json).unwrap(),
+            bincode::deserialize(&bytes).unwrap(),
+        )
+    }
+
+    fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) {
+        let (from_json, from_bincode) = round_trip(value);
+        assert_eq!(&from_json, value);
+        assert_eq!(&from_bincode, value);
+    }
+
+    #[test]
+    fn commitment_groups_round_trip() {
+        let mut rng = test_rng();
+        assert_round_trip(&Com1::<F>(
+            G1Affine::rand(&mut rng),
+            G1Affine::rand(&mut rng),
+        ));
+        assert_round_trip(&Com2::<F>(G2Affine::rand(&mut rng), G2Affine::zero()));
+        assert_round_trip(&ComT::<F>(
+            GT::rand(&mut rng),
+            GT::rand(&mut rng),
+            GT::rand(&mut rng),
+            GT::zero(),
+        ));
+        assert_round_trip(&FieldMatrix::<Fr>(vec![
+            vec![Fr::rand(&mut rng), Fr::one()],
+            vec![Fr::zero(), Fr::rand(&mut rng)],
+        ]));
+        assert_round_trip(&EquType::MultiScalarG2);
+    }
+
+    #[test]
+    fn proof_and_statement_round_trip() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^2 = t
+        let xvars = vec![G1Affine::rand(&mut rng)];
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero()],
+            gamma: vec![vec![Fr::from(2u64)]],
+            target: F::pairing(xvars[0], yvars[0]) * Fr::from(2u64),
+        };
+        let proof = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+
+        assert_round_trip(&equ);
+        assert_round_trip(&proof);
+        assert_round_trip(&proof.xcoms);
+        assert_round_trip(&proof.ycoms);
+        assert_round_trip(&proof.equ_proofs[0]);
+        assert_round_trip(&QuadEqu::<F>::scalar_equality());
+
+        // The deserialized CRS, equation and proof still verify together
+        let (crs_json, crs_bincode) = round_trip(&crs);
+        assert_eq!(crs_json.fingerprint(), crs.fingerprint());
+        assert_eq!(crs_bincode.fingerprint(), crs.fingerprint());
+        let (equ, proof) = (round_trip(&equ).0, round_trip(&proof).1);
+        assert!(equ.verify(&proof, &crs_json));
+    }
+
+    #[test]
+    fn json_encoding_is_stable() {
+        // Compressed G1 generator and identity, as in the canonical serialization
+        const COM1_JSON: &str = "\"\
+            97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+            c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\"";
+        const EQU_TYPE_JSON: &str = "\"03\"";
+        const MATRIX_JSON: &str = "\"\
+            0100000000000000\
+            0200000000000000\
+            0100000000000000000000000000000000000000000000000000000000000000\
+            0200000000000000000000000000000000000000000000000000000000000000\"";
+
+        let com = Com1::<F>(G1Affine::generator(), G1Affine::zero());
+        assert_eq!(serde_json::to_string(&com).unwrap(), COM1_JSON);
+        assert_eq!(serde_json::from_str::<Com1<F>>(COM1_JSON).unwrap(), com);
+        assert_eq!(
+            serde_json::to_string(&EquType::Quadratic).unwrap(),
+            EQU_TYPE_JSON
+        );
+        assert_eq!(
+            serde_json::from_str::<EquType>(EQU_TYPE_JSON).unwrap(),
+            EquType::Quadratic
+        );
+        let mat = FieldMatrix::<Fr>(vec![vec![Fr::one(), Fr::from(2u64)]]);
+        assert_eq!(serde_json::to_string(&mat).unwrap(), MATRIX_JSON);
+        assert_eq!(
+            serde_json::from_str::<FieldMatrix<Fr>>(MATRIX_JSON).unwrap(),
+            mat
+        );
+    }
+
+    #[test]
+    fn proof_json_encoding_is_stable() {
+        // Compressed generators and identities, vector lengths and matrix dimensions as little-endian u32,
+        // and the version header
+        const G1_GEN: &str = "\
+            97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+        const G2_GEN: &str = "\
+            93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+            024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+        const VERSION: &str = "ff477253616869ff01";
+        let g1_zero = format!("c0{}", "0".repeat(94));
+        let g2_zero = format!("c0{}", "0".repeat(190));
+        let gt_one = format!("01{}", "0".repeat(1150));
+        let len = |n: u8| format!("{:02x}000000", n);
+        // The 0 x 0 dimensions of the left out randomness
+        let no_rand = "0".repeat(16);
+
+        let (g, h) = (G1Affine::generator(), G2Affine::generator());
+        let crs = CRS::<F> {
+            u: vec![
+                Com1::<F>(g, G1Affine::zero()),
+                Com1::<F>(G1Affine::zero(), g),
+            ],
+            v: vec![
+                Com2::<F>(h, G2Affine::zero()),
+                Com2::<F>(G2Affine::zero(), h),
+            ],
+            g1_gen: g,
+            g2_gen: h,
+            gt_gen: GT::zero(),
+        };
+        let crs_json = format!(
+            "\"{VERSION}{}{G1_GEN}{g1_zero}{g1_zero}{G1_GEN}{}{G2_GEN}{g2_zero}{g2_zero}{G2_GEN}{G1_GEN}{G2_GEN}{gt_one}\"",
+            len(2),
+            len(2)
+        );
+        assert_eq!(serde_json::to_string(&crs).unwrap(), crs_json);
+        assert_eq!(
+            serde_json::from_str::<CRS<F>>(&crs_json)
+                .unwrap()
+                .fingerprint(),
+            crs.fingerprint()
+        );
+
+        // The randomness of a published proof is empty
+        let proof = EquProof::<F>::from_parts(
+            vec![Com2::<F>(h, G2Affine::zero())],
+            vec![Com1::<F>(g, G1Affine::zero())],
+            EquType::Quadratic,
+        )
+        .unwrap();
+        let proof_hex = format!(
+            "{VERSION}{}{G2_GEN}{g2_zero}{}{G1_GEN}{g1_zero}03{no_rand}",
+            len(1),
+            len(1)
+        );
+        let proof_json = format!("\"{proof_hex}\"");
+        assert_eq!(serde_json::to_string(&proof).unwrap(), proof_json);
+        assert_eq!(
+            serde_json::from_str::<EquProof<F>>(&proof_json).unwrap(),
+            proof
+        );
+
+        let com_proof = CProof::<F> {
+            xcoms: Commit1::<F>::from_coms(vec![Com1::<F>(g, G1Affine::zero())]),
+            ycoms: Commit2::<F>::from_coms(vec![]),
+            equ_proofs: vec![proof],
+            crs_fingerprint: 1,
+        };
+        let com_proof_json = format!(
+            "\"{VERSION}{VERSION}{}{G1_GEN}{g1_zero}{no_rand}{VERSION}{}{no_rand}{}{proof_hex}01{}\"",
+            len(1),
+            len(0),
+            len(1),
+            "0".repeat(14)
+        );
+        assert_eq!(serde_json::to_string(&com_proof).unwrap(), com_proof_json);
+        assert_eq!(
+            serde_json::from_str::<CProof<F>>(&com_proof_json).unwrap(),
+            com_proof
+        );
+    }
+
+    #[test]
+    fn malformed_encodings_are_rejected() {
+        let com = Com1::<F>(G1Affine::generator(), G1Affine::zero());
+        let json = serde_json::to_string(&com).unwrap();
+
+        // Trailing bytes, an odd number of digits, and non-hex digits
+        let trailing = format!("{}00\"", &json[..json.len() - 1]);
+        assert!(serde_json::from_str::<Com1<F>>(&trailing).is_err());
+        let odd = format!("{}0\"", &json[..json.len() - 1]);
+        assert!(serde_json::from_str::<Com1<F>>(&odd).is_err());
+        let non_hex = json.replacen("97", "zz", 1);
+        assert!(serde_json::from_str::<Com1<F>>(&non_hex).is_err());
+
+        // A truncated binary encoding
+        let mut bytes = bincode::serialize(&com).unwrap();
+        bytes.pop();
+        assert!(bincode::deserialize::<Com1<F>>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+#![allow(non_snake_case)]
+// Run with e.g. `wasm-pack test --node`; rayon is not built for wasm32, so the matrix products run on
+// the current thread even with the default `parallel` feature
+#![cfg(target_arch = "wasm32")]
+
+#[cfg(test)]
+mod SXDH_wasm_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::{Pairing, PairingOutput};
+    use ark_ec::AffineRepr;
+    use ark_std::{test_rng, UniformRand, Zero};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use groth_sahai::prelude::*;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+    type GT = PairingOutput<F>;
+
+    #[wasm_bindgen_test]
+    fn pairing_product_equation_round_trip() {
+        // Any caller-supplied RNG works, as nothing in the crate samples OS randomness
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, Y_1)^3 * e(X_2, c) = t for random variables
+        let xvars: Vec<G1Affine> = vec![G1Affine::rand(&mut rng), G1Affine::rand(&mut rng)];
+        let yvars: Vec<G2Affine> = vec![G2Affine::rand(&mut rng)];
+        let c = G2Affine::rand(&mut rng);
+        let target: GT = F::pairing(xvars[0], yvars[0]) * Fr::from(3u64) + F::pairing(xvars[1], c);
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts: vec![G2Affine::zero(), c],
+            gamma: vec![vec![Fr::from(3u64)], vec![Fr::zero()]],
+            target,
+        };
+
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs));
+
+        let bad_equ: PPE<F> = PPE::<F> {
+            target: target + F::pairing(crs.g1_gen, crs.g2_gen),
+            ..equ
+        };
+        assert!(!bad_equ.verify(&proof, &crs));
+    }
+}
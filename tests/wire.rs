@@ -0,0 +1,139 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod SXDH_wire_tests {
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::Pairing;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::UniformRand;
+
+    use groth_sahai::data_structures::ShapeError;
+    use groth_sahai::prelude::*;
+    use groth_sahai::wire::*;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+
+    // The proof of `small_proof`, field by field
+    const PROOF_HEX: &str = concat!(
+        "47535046",         // magic "GSPF"
+        "01",               // version
+        "01",               // BLS12-381
+        "00",               // PPE
+        "e38d7593f0742b78", // CRS fingerprint
+        "00000000",         // m = 0 x commitments
+        "00000001",         // n = 1 y commitment
+        "00",               // p = 0 elements of pi
+        "02",               // q = 2 elements of theta
+        // The y commitment in B2
+        "91cb56c8a30ad81ca265b7bebe0978984f957208c3e7dac0b7485823cad3ec9376cfb30173b748be055e61f4c97a634b014c465ffaf3b3d6c514eacbb7146b4a4323c8b71524218db21d408ad313496af709a2fa622c91e8875dacc8027d5b7d",
+        "a11f757b7a5c4154118d8236ab27d1ba7dc26236005000bf35efd707bcfb4e83516d685f301a3eabdf2c34fa4440d7dc0392cb741376eaf152e04629e65260f4841a3463b57ccb90e89f33a48b7f5415c4c391c04d96a112034b794b7f0055df",
+        // theta in B1
+        "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "b11cd56f2a356215df646614bc497620bb94a8d262371e724e20824c72d03ad1df799536cef8e6652e0c29ad8dac3e13",
+        "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "a6390ebb8ea8d479d65636c14b396d4da489c38d075ff4b459a1268bdbed2c37f57b6f5ce11dc63cd2ff828072e69ed3",
+    );
+    const COMMITMENTS_HEX: &str = concat!(
+        "4753434d", // magic "GSCM"
+        "01",       // version
+        "01",       // BLS12-381
+        "00000000", // m = 0 x commitments
+        "00000001", // n = 1 y commitment
+        "91cb56c8a30ad81ca265b7bebe0978984f957208c3e7dac0b7485823cad3ec9376cfb30173b748be055e61f4c97a634b014c465ffaf3b3d6c514eacbb7146b4a4323c8b71524218db21d408ad313496af709a2fa622c91e8875dacc8027d5b7d",
+        "a11f757b7a5c4154118d8236ab27d1ba7dc26236005000bf35efd707bcfb4e83516d685f301a3eabdf2c34fa4440d7dc0392cb741376eaf152e04629e65260f4841a3463b57ccb90e89f33a48b7f5415c4c391c04d96a112034b794b7f0055df",
+    );
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // e(a, Y_1) = t for a random a and Y_1, with a proof of only theta
+    fn small_proof() -> (CRS<F>, PPE<F>, CProof<F>) {
+        let mut rng = StdRng::seed_from_u64(1142);
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let a = G1Affine::rand(&mut rng);
+        let yvars = vec![G2Affine::rand(&mut rng)];
+        let equ = PPE::<F> {
+            a_consts: vec![a],
+            b_consts: vec![],
+            gamma: vec![],
+            target: F::pairing(a, yvars[0]),
+        };
+        let proof = equ.commit_and_prove(&[], &yvars, &crs, &mut rng);
+        (crs, equ, proof)
+    }
+
+    #[test]
+    fn proof_matches_test_vector() {
+        let (crs, equ, proof) = small_proof();
+        assert_eq!(hex(&encode_proof(&proof).unwrap()), PROOF_HEX);
+
+        let decoded = decode_proof::<F>(&unhex(PROOF_HEX)).unwrap();
+        assert_eq!(decoded, proof.without_randomness());
+        assert!(equ.verify(&decoded, &crs));
+    }
+
+    #[test]
+    fn commitments_match_test_vector() {
+        let (_, _, proof) = small_proof();
+        let bytes = encode_commitments(&proof.xcoms, &proof.ycoms).unwrap();
+        assert_eq!(hex(&bytes), COMMITMENTS_HEX);
+
+        let (xcoms, ycoms) = decode_commitments::<F>(&unhex(COMMITMENTS_HEX)).unwrap();
+        assert!(xcoms.coms.is_empty());
+        assert_eq!(ycoms.coms, proof.ycoms.coms);
+    }
+
+    #[test]
+    fn malformed_encodings_are_rejected() {
+        let bytes = unhex(PROOF_HEX);
+        let with = |pos: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[pos] = byte;
+            decode_proof::<F>(&bytes)
+        };
+
+        assert_eq!(
+            decode_commitments::<F>(&bytes).unwrap_err(),
+            WireError::Magic
+        );
+        assert_eq!(with(4, 2).unwrap_err(), WireError::UnknownVersion(2));
+        assert_eq!(with(5, 9).unwrap_err(), WireError::UnknownCurve(9));
+        assert_eq!(with(6, 7).unwrap_err(), WireError::UnknownEquType(7));
+        // A count of 3 elements of theta, and a PPE proof with a single element of theta
+        assert_eq!(with(24, 3).unwrap_err(), WireError::Truncated);
+        let mut single = bytes.clone();
+        single[24] = 1;
+        single.truncate(bytes.len() - 96);
+        assert_eq!(
+            decode_proof::<F>(&single).unwrap_err(),
+            WireError::Shape(ShapeError { expected: (2, 1) })
+        );
+        // The first byte of the y commitment, with every flag set
+        assert_eq!(with(25, 0xff).unwrap_err(), WireError::InvalidElement);
+        assert_eq!(
+            decode_proof::<F>(&bytes[..bytes.len() - 1]).unwrap_err(),
+            WireError::Truncated
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            decode_proof::<F>(&trailing).unwrap_err(),
+            WireError::TrailingBytes
+        );
+
+        // Only a proof for a single equation has an encoding
+        let (_, _, mut proof) = small_proof();
+        proof.equ_proofs.push(proof.equ_proofs[0].clone());
+        assert_eq!(encode_proof(&proof).unwrap_err(), WireError::ProofCount(2));
+    }
+}